@@ -0,0 +1,118 @@
+//! Derive macros for inkwell's traits, which are re-exported by inkwell
+//! alongside the traits themselves.
+
+extern crate proc_macro;
+extern crate proc_macro2;
+extern crate quote;
+extern crate syn;
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, Ident, Meta, NestedMeta, parse_macro_input, parse_quote};
+
+fn panic_with_usage() -> ! {
+    panic!("RustStruct can only be derived for structs with a #[repr(C)] or #[repr(C, packed)] attribute");
+}
+
+// Gets whether the struct is packed, while ensuring it has a C compatible layout
+fn is_packed(input: &DeriveInput) -> bool {
+    let mut is_repr_c = false;
+    let mut is_packed = false;
+
+    for attribute in &input.attrs {
+        let list = match attribute.parse_meta() {
+            Ok(Meta::List(ref list)) if list.ident == "repr" => list.nested.clone(),
+            _ => continue,
+        };
+
+        for nested in list {
+            match nested {
+                NestedMeta::Meta(Meta::Word(ref ident)) if ident == "C" => is_repr_c = true,
+                NestedMeta::Meta(Meta::Word(ref ident)) if ident == "packed" => is_packed = true,
+                // Other alignments, ie align(8) or packed(2), can't be expressed in LLVM struct types
+                _ => panic_with_usage(),
+            }
+        }
+    }
+
+    if !is_repr_c {
+        panic_with_usage();
+    }
+
+    is_packed
+}
+
+#[proc_macro_derive(RustStruct)]
+pub fn derive_rust_struct(input: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(input as DeriveInput);
+    let packed = is_packed(&input);
+    let fields = match input.data {
+        Data::Struct(ref data) => data.fields.clone(),
+        _ => panic_with_usage(),
+    };
+    // Tuple struct fields are named by their index, as they are accessed in Rust
+    let names: Vec<String> = match fields {
+        Fields::Named(ref fields) => fields.named.iter().map(|field| field.ident.as_ref().unwrap().to_string()).collect(),
+        Fields::Unnamed(ref fields) => (0..fields.unnamed.len()).map(|index| index.to_string()).collect(),
+        Fields::Unit => Vec::new(),
+    };
+    let types: Vec<_> = fields.iter().map(|field| field.ty.clone()).collect();
+
+    {
+        let where_clause = input.generics.make_where_clause();
+
+        for type_ in &types {
+            where_clause.predicates.push(parse_quote!(#type_: _inkwell::types::RustType));
+        }
+    }
+
+    // repr(C) lays fields out in order, each at the next multiple of its alignment (or immediately
+    // after the previous one when packed). Following those rules avoids needing an instance of the
+    // struct, whose packed fields couldn't be borrowed anyway.
+    let offsets: Vec<_> = types.iter().map(|type_| quote! {{
+        let align = if #packed { 1 } else { ::std::mem::align_of::<#type_>() };
+        let offset = (end + align - 1) / align * align;
+
+        end = offset + ::std::mem::size_of::<#type_>();
+        offset as u64
+    }}).collect();
+    let ident = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let dummy_const = Ident::new(&format!("_IMPL_RUST_STRUCT_FOR_{}", ident), Span::call_site());
+
+    let q = quote! {
+        #[allow(non_upper_case_globals, unused_attributes, unused_qualifications)]
+        const #dummy_const: () = {
+            extern crate inkwell as _inkwell;
+
+            impl #impl_generics _inkwell::types::RustStruct for #ident #type_generics #where_clause {
+                const FIELD_NAMES: &'static [&'static str] = &[#(#names),*];
+
+                fn struct_type(context: &_inkwell::context::Context) -> _inkwell::types::StructType {
+                    let field_types = [#(<#types as _inkwell::types::RustType>::basic_type(context)),*];
+
+                    context.struct_type(&field_types, #packed)
+                }
+
+                // The end of the last field is never read, and there are no fields at all in unit structs
+                #[allow(unused_assignments, unused_mut, unused_variables)]
+                fn field_offsets() -> Vec<u64> {
+                    let mut end = 0usize;
+
+                    vec![#(#offsets),*]
+                }
+            }
+
+            unsafe impl #impl_generics _inkwell::types::RustType for #ident #type_generics #where_clause {
+                fn basic_type(context: &_inkwell::context::Context) -> _inkwell::types::BasicTypeEnum {
+                    let struct_type = <Self as _inkwell::types::RustStruct>::struct_type(context);
+
+                    _inkwell::types::BasicType::as_basic_type_enum(&struct_type)
+                }
+            }
+        };
+    };
+
+    q.into()
+}
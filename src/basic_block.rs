@@ -1,11 +1,12 @@
 //! A `BasicBlock` is a container of instructions.
 
-use llvm_sys::core::{LLVMGetBasicBlockParent, LLVMGetBasicBlockTerminator, LLVMGetNextBasicBlock, LLVMInsertBasicBlock, LLVMIsABasicBlock, LLVMIsConstant, LLVMMoveBasicBlockAfter, LLVMMoveBasicBlockBefore, LLVMPrintTypeToString, LLVMPrintValueToString, LLVMTypeOf, LLVMDeleteBasicBlock, LLVMGetPreviousBasicBlock, LLVMRemoveBasicBlockFromParent, LLVMGetFirstInstruction, LLVMGetLastInstruction, LLVMGetTypeContext, LLVMBasicBlockAsValue};
+use llvm_sys::core::{LLVMGetBasicBlockParent, LLVMGetBasicBlockTerminator, LLVMGetNextBasicBlock, LLVMInsertBasicBlock, LLVMIsABasicBlock, LLVMIsConstant, LLVMMoveBasicBlockAfter, LLVMMoveBasicBlockBefore, LLVMPrintTypeToString, LLVMPrintValueToString, LLVMTypeOf, LLVMDeleteBasicBlock, LLVMGetPreviousBasicBlock, LLVMRemoveBasicBlockFromParent, LLVMGetFirstInstruction, LLVMGetLastInstruction, LLVMGetTypeContext, LLVMBasicBlockAsValue, LLVMGetNextInstruction, LLVMGetPreviousInstruction};
 #[llvm_versions(3.9 => latest)]
 use llvm_sys::core::LLVMGetBasicBlockName;
 use llvm_sys::prelude::{LLVMValueRef, LLVMBasicBlockRef};
 
 use context::{Context, ContextRef};
+use support::next_in_list;
 use values::{FunctionValue, InstructionValue};
 
 use std::fmt;
@@ -293,6 +294,41 @@ impl BasicBlock {
         Some(InstructionValue::new(value))
     }
 
+    /// Gets an iterator over the `InstructionValue`s in this `BasicBlock`, in order.
+    /// The iterator is double-ended, so it may also be walked from the last instruction.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::values::InstructionOpcode;
+    ///
+    /// let context = Context::create();
+    /// let builder = context.create_builder();
+    /// let module = context.create_module("my_module");
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+    /// let function = module.add_function("double", fn_type, None);
+    /// let basic_block = context.append_basic_block(&function, "entry");
+    /// let x = function.get_first_param().unwrap().into_int_value();
+    ///
+    /// builder.position_at_end(&basic_block);
+    /// builder.build_return(Some(&builder.build_int_mul(x, i32_type.const_int(2, false), "double")));
+    ///
+    /// let opcodes: Vec<_> = basic_block.get_instructions().map(|instruction| instruction.get_opcode()).collect();
+    ///
+    /// assert_eq!(opcodes, vec![InstructionOpcode::Mul, InstructionOpcode::Return]);
+    /// ```
+    pub fn get_instructions(&self) -> InstructionIter {
+        let (front, back) = unsafe {
+            (LLVMGetFirstInstruction(self.basic_block), LLVMGetLastInstruction(self.basic_block))
+        };
+
+        InstructionIter {
+            front,
+            back,
+        }
+    }
+
     /// Obtains the terminating `InstructionValue` in this `BasicBlock`, if any. A `BasicBlock` must have a terminating instruction to be valid.
     ///
     /// # Example
@@ -468,3 +504,24 @@ impl fmt::Debug for BasicBlock {
             .finish()
     }
 }
+
+/// An iterator over the `InstructionValue`s of a `BasicBlock`, created by `BasicBlock::get_instructions`.
+#[derive(Debug)]
+pub struct InstructionIter {
+    front: LLVMValueRef,
+    back: LLVMValueRef,
+}
+
+impl Iterator for InstructionIter {
+    type Item = InstructionValue;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_in_list(&mut self.front, &mut self.back, LLVMGetNextInstruction).map(InstructionValue::new)
+    }
+}
+
+impl DoubleEndedIterator for InstructionIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        next_in_list(&mut self.back, &mut self.front, LLVMGetPreviousInstruction).map(InstructionValue::new)
+    }
+}
@@ -1,12 +1,20 @@
 use either::{Either, Left, Right};
-use llvm_sys::core::{LLVMBuildAdd, LLVMBuildAlloca, LLVMBuildAnd, LLVMBuildArrayAlloca, LLVMBuildArrayMalloc, LLVMBuildBr, LLVMBuildCall, LLVMBuildCast, LLVMBuildCondBr, LLVMBuildExtractValue, LLVMBuildFAdd, LLVMBuildFCmp, LLVMBuildFDiv, LLVMBuildFence, LLVMBuildFMul, LLVMBuildFNeg, LLVMBuildFree, LLVMBuildFSub, LLVMBuildGEP, LLVMBuildICmp, LLVMBuildInsertValue, LLVMBuildIsNotNull, LLVMBuildIsNull, LLVMBuildLoad, LLVMBuildMalloc, LLVMBuildMul, LLVMBuildNeg, LLVMBuildNot, LLVMBuildOr, LLVMBuildPhi, LLVMBuildPointerCast, LLVMBuildRet, LLVMBuildRetVoid, LLVMBuildStore, LLVMBuildSub, LLVMBuildUDiv, LLVMBuildUnreachable, LLVMBuildXor, LLVMDisposeBuilder, LLVMGetElementType, LLVMGetInsertBlock, LLVMGetReturnType, LLVMGetTypeKind, LLVMInsertIntoBuilder, LLVMPositionBuilderAtEnd, LLVMTypeOf, LLVMBuildExtractElement, LLVMBuildInsertElement, LLVMBuildIntToPtr, LLVMBuildPtrToInt, LLVMInsertIntoBuilderWithName, LLVMClearInsertionPosition, LLVMCreateBuilder, LLVMPositionBuilder, LLVMPositionBuilderBefore, LLVMBuildAggregateRet, LLVMBuildStructGEP, LLVMBuildInBoundsGEP, LLVMBuildPtrDiff, LLVMBuildNSWAdd, LLVMBuildNUWAdd, LLVMBuildNSWSub, LLVMBuildNUWSub, LLVMBuildNSWMul, LLVMBuildNUWMul, LLVMBuildSDiv, LLVMBuildSRem, LLVMBuildURem, LLVMBuildFRem, LLVMBuildNSWNeg, LLVMBuildNUWNeg, LLVMBuildFPToUI, LLVMBuildFPToSI, LLVMBuildSIToFP, LLVMBuildUIToFP, LLVMBuildFPTrunc, LLVMBuildFPExt, LLVMBuildIntCast, LLVMBuildFPCast, LLVMBuildSExtOrBitCast, LLVMBuildZExtOrBitCast, LLVMBuildTruncOrBitCast, LLVMBuildSwitch, LLVMAddCase, LLVMBuildShl, LLVMBuildAShr, LLVMBuildLShr, LLVMBuildGlobalString, LLVMBuildGlobalStringPtr, LLVMBuildExactSDiv, LLVMBuildTrunc, LLVMBuildSExt, LLVMBuildZExt, LLVMBuildSelect, LLVMBuildAddrSpaceCast, LLVMBuildBitCast, LLVMBuildShuffleVector, LLVMBuildVAArg, LLVMBuildIndirectBr, LLVMAddDestination};
+use llvm_sys::core::{LLVMBuildAdd, LLVMBuildAlloca, LLVMBuildAnd, LLVMBuildArrayAlloca, LLVMBuildArrayMalloc, LLVMBuildBr, LLVMBuildCall, LLVMBuildCast, LLVMBuildCondBr, LLVMBuildExtractValue, LLVMBuildFAdd, LLVMBuildFCmp, LLVMBuildFDiv, LLVMBuildFence, LLVMBuildFMul, LLVMBuildFNeg, LLVMBuildFree, LLVMBuildFSub, LLVMBuildGEP, LLVMBuildICmp, LLVMBuildInsertValue, LLVMBuildIsNotNull, LLVMBuildIsNull, LLVMBuildLoad, LLVMBuildMalloc, LLVMBuildMul, LLVMBuildNeg, LLVMBuildNot, LLVMBuildOr, LLVMBuildPhi, LLVMBuildPointerCast, LLVMBuildRet, LLVMBuildRetVoid, LLVMBuildStore, LLVMBuildSub, LLVMBuildUDiv, LLVMBuildUnreachable, LLVMBuildXor, LLVMDisposeBuilder, LLVMGetElementType, LLVMGetInsertBlock, LLVMGetReturnType, LLVMGetTypeKind, LLVMInsertIntoBuilder, LLVMPositionBuilderAtEnd, LLVMTypeOf, LLVMBuildExtractElement, LLVMBuildInsertElement, LLVMBuildIntToPtr, LLVMBuildPtrToInt, LLVMInsertIntoBuilderWithName, LLVMClearInsertionPosition, LLVMCreateBuilder, LLVMPositionBuilder, LLVMPositionBuilderBefore, LLVMBuildAggregateRet, LLVMBuildStructGEP, LLVMBuildInBoundsGEP, LLVMBuildPtrDiff, LLVMBuildNSWAdd, LLVMBuildNUWAdd, LLVMBuildNSWSub, LLVMBuildNUWSub, LLVMBuildNSWMul, LLVMBuildNUWMul, LLVMBuildSDiv, LLVMBuildSRem, LLVMBuildURem, LLVMBuildFRem, LLVMBuildNSWNeg, LLVMBuildNUWNeg, LLVMBuildFPToUI, LLVMBuildFPToSI, LLVMBuildSIToFP, LLVMBuildUIToFP, LLVMBuildFPTrunc, LLVMBuildFPExt, LLVMBuildIntCast, LLVMBuildFPCast, LLVMBuildSExtOrBitCast, LLVMBuildZExtOrBitCast, LLVMBuildTruncOrBitCast, LLVMBuildSwitch, LLVMAddCase, LLVMBuildShl, LLVMBuildAShr, LLVMBuildLShr, LLVMBuildGlobalString, LLVMBuildGlobalStringPtr, LLVMBuildExactSDiv, LLVMBuildTrunc, LLVMBuildSExt, LLVMBuildZExt, LLVMBuildSelect, LLVMBuildAddrSpaceCast, LLVMBuildBitCast, LLVMBuildShuffleVector, LLVMBuildVAArg, LLVMBuildIndirectBr, LLVMAddDestination, LLVMBuildInvoke, LLVMBuildLandingPad, LLVMBuildResume, LLVMBuildAtomicRMW};
 use llvm_sys::prelude::{LLVMBuilderRef, LLVMValueRef};
+#[llvm_versions(7.0 => latest)]
+use llvm_sys::prelude::LLVMModuleRef;
 use llvm_sys::{LLVMTypeKind};
 
-use {AtomicOrdering, IntPredicate, FloatPredicate};
+use {AtomicOrdering, AtomicRMWBinOp, IntPredicate, FloatPredicate};
 use basic_block::BasicBlock;
-use values::{AggregateValue, AggregateValueEnum, AsValueRef, BasicValue, BasicValueEnum, PhiValue, FunctionValue, IntValue, PointerValue, VectorValue, InstructionValue, GlobalValue, IntMathValue, FloatMathValue, PointerMathValue, InstructionOpcode, CallSiteValue};
-use types::{AsTypeRef, BasicType, IntMathType, FloatMathType, PointerType, PointerMathType};
+#[llvm_versions(7.0 => latest)]
+use context::Context;
+#[llvm_versions(7.0 => latest)]
+use debug_info::DILocation;
+#[llvm_versions(7.0 => latest)]
+use intrinsics::Intrinsic;
+use values::{AggregateValue, AggregateValueEnum, AsValueRef, BasicValue, BasicValueEnum, PhiValue, FunctionValue, IntValue, PointerValue, StructValue, VectorValue, InstructionValue, GlobalValue, IntMathValue, FloatMathValue, PointerMathValue, InstructionOpcode, CallSiteValue, LandingPadValue, GEPError, get_gep_element_type};
+use types::{AnyType, AnyTypeEnum, AsTypeRef, BasicType, FieldNameError, IntMathType, FloatMathType, PointerType, PointerMathType};
 
 use std::ffi::CString;
 
@@ -92,43 +100,385 @@ impl Builder {
         CallSiteValue::new(value)
     }
 
-    // REVIEW: Doesn't GEP work on array too?
-    /// GEP is very likely to segfault if indexes are used incorrectly, and is therefore an unsafe function. Maybe we can change this in the future.
-    pub unsafe fn build_gep(&self, ptr: PointerValue, ordered_indexes: &[IntValue], name: &str) -> PointerValue {
+    /// Builds an invoke instruction, which calls `function` and continues at `then_block` if it
+    /// returns normally, or unwinds to `catch_block` if it throws.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let void_type = context.void_type();
+    /// let i32_type = context.i32_type();
+    /// let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    /// let exception_type = context.struct_type(&[i8_ptr_type.into(), i32_type.into()], false);
+    /// let personality = module.add_function("__gxx_personality_v0", i32_type.fn_type(&[], true), None);
+    /// let may_throw = module.add_function("may_throw", void_type.fn_type(&[], false), None);
+    /// let fn_value = module.add_function("my_fn", void_type.fn_type(&[], false), None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let then_block = fn_value.append_basic_block("then");
+    /// let catch_block = fn_value.append_basic_block("catch");
+    ///
+    /// fn_value.set_personality_function(personality);
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_invoke(may_throw, &[], &then_block, &catch_block, "call");
+    ///
+    /// builder.position_at_end(&then_block);
+    /// builder.build_return(None);
+    ///
+    /// builder.position_at_end(&catch_block);
+    ///
+    /// let landing_pad = builder.build_landing_pad(exception_type, personality, "lpad");
+    ///
+    /// landing_pad.set_cleanup(true);
+    /// builder.build_resume(landing_pad.as_basic_value());
+    /// ```
+    pub fn build_invoke<F>(&self, function: F, args: &[BasicValueEnum], then_block: &BasicBlock, catch_block: &BasicBlock, name: &str) -> CallSiteValue
+    where
+        F: Into<FunctionOrPointerValue>,
+    {
+        let fn_val_ref = match function.into() {
+            Left(val) => val.as_value_ref(),
+            Right(val) => val.as_value_ref(),
+        };
+
+        // LLVM gets upset when void return calls are named because they don't return anything
+        let name = unsafe {
+            match LLVMGetTypeKind(LLVMGetReturnType(LLVMGetElementType(LLVMTypeOf(fn_val_ref)))) {
+                LLVMTypeKind::LLVMVoidTypeKind => "",
+                _ => name,
+            }
+        };
+
+        let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
+        let mut args: Vec<LLVMValueRef> = args.iter()
+                                              .map(|val| val.as_value_ref())
+                                              .collect();
+        let value = unsafe {
+            LLVMBuildInvoke(self.builder, fn_val_ref, args.as_mut_ptr(), args.len() as u32, then_block.basic_block, catch_block.basic_block, c_string.as_ptr())
+        };
+
+        CallSiteValue::new(value)
+    }
+
+    // TODOC: The landing pad must be the first non phi instruction of the block an invoke unwinds to.
+    /// Builds a landing pad producing a value of type `type_`. Clauses may be added to it with
+    /// `LandingPadValue::add_clause`. The containing function should use `personality_function`
+    /// as its personality.
+    pub fn build_landing_pad<T: BasicType>(&self, type_: T, personality_function: FunctionValue, name: &str) -> LandingPadValue {
+        let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
+
+        let value = unsafe {
+            LLVMBuildLandingPad(self.builder, type_.as_type_ref(), personality_function.as_value_ref(), 0, c_string.as_ptr())
+        };
+
+        LandingPadValue::new(value)
+    }
+
+    /// Builds a resume instruction, which continues unwinding with an exception value
+    /// previously produced by a landing pad.
+    pub fn build_resume<BV: BasicValue>(&self, value: BV) -> InstructionValue {
+        let value = unsafe {
+            LLVMBuildResume(self.builder, value.as_value_ref())
+        };
+
+        InstructionValue::new(value)
+    }
+
+    /// Builds a catchswitch instruction, which dispatches an exception to one of the
+    /// `handlers` blocks, each of which must begin with a catchpad. If no handler is chosen,
+    /// unwinding continues at `unwind_block`, or in the caller if it is `None`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let void_type = context.void_type();
+    /// let i32_type = context.i32_type();
+    /// let personality = module.add_function("__CxxFrameHandler3", i32_type.fn_type(&[], true), None);
+    /// let may_throw = module.add_function("may_throw", void_type.fn_type(&[], false), None);
+    /// let fn_value = module.add_function("my_fn", void_type.fn_type(&[], false), None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let dispatch = fn_value.append_basic_block("dispatch");
+    /// let handler = fn_value.append_basic_block("handler");
+    /// let exit = fn_value.append_basic_block("exit");
+    ///
+    /// fn_value.set_personality_function(personality);
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_invoke(may_throw, &[], &exit, &dispatch, "call");
+    ///
+    /// builder.position_at_end(&dispatch);
+    ///
+    /// let catch_switch = builder.build_catch_switch(None, None, &[&handler], "switch");
+    ///
+    /// builder.position_at_end(&handler);
+    ///
+    /// let null = context.i8_type().ptr_type(AddressSpace::Generic).const_null();
+    /// let catch_pad = builder.build_catch_pad(catch_switch, &[null.into(), i32_type.const_int(64, false).into(), null.into()], "catch");
+    ///
+    /// builder.build_catch_ret(catch_pad, &exit);
+    ///
+    /// builder.position_at_end(&exit);
+    /// builder.build_return(None);
+    /// ```
+    #[llvm_versions(6.0 => latest)]
+    pub fn build_catch_switch(&self, parent_pad: Option<InstructionValue>, unwind_block: Option<&BasicBlock>, handlers: &[&BasicBlock], name: &str) -> InstructionValue {
+        use llvm_sys::core::{LLVMAddHandler, LLVMBuildCatchSwitch};
+        use std::ptr;
+
+        // A null parent pad is taken by LLVM to mean the token "none"
+        let parent_pad = parent_pad.map_or(ptr::null_mut(), |pad| pad.as_value_ref());
+        let unwind_block = unwind_block.map_or(ptr::null_mut(), |bb| bb.basic_block);
+        let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
+
+        let catch_switch = unsafe {
+            LLVMBuildCatchSwitch(self.builder, parent_pad, unwind_block, handlers.len() as u32, c_string.as_ptr())
+        };
+
+        for handler in handlers {
+            unsafe {
+                LLVMAddHandler(catch_switch, handler.basic_block)
+            }
+        }
+
+        InstructionValue::new(catch_switch)
+    }
+
+    /// Builds a catchpad instruction within a catchswitch. The meaning of `args` is
+    /// determined by the function's personality.
+    #[llvm_versions(6.0 => latest)]
+    pub fn build_catch_pad(&self, catch_switch: InstructionValue, args: &[BasicValueEnum], name: &str) -> InstructionValue {
+        use llvm_sys::core::LLVMBuildCatchPad;
+
+        let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
+        let mut args: Vec<LLVMValueRef> = args.iter()
+                                              .map(|val| val.as_value_ref())
+                                              .collect();
+        let value = unsafe {
+            LLVMBuildCatchPad(self.builder, catch_switch.as_value_ref(), args.as_mut_ptr(), args.len() as u32, c_string.as_ptr())
+        };
+
+        InstructionValue::new(value)
+    }
+
+    /// Builds a cleanuppad instruction, nested within `parent_pad` if it is provided.
+    /// The meaning of `args` is determined by the function's personality.
+    #[llvm_versions(6.0 => latest)]
+    pub fn build_cleanup_pad(&self, parent_pad: Option<InstructionValue>, args: &[BasicValueEnum], name: &str) -> InstructionValue {
+        use llvm_sys::core::LLVMBuildCleanupPad;
+        use std::ptr;
+
+        // A null parent pad is taken by LLVM to mean the token "none"
+        let parent_pad = parent_pad.map_or(ptr::null_mut(), |pad| pad.as_value_ref());
+        let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
+        let mut args: Vec<LLVMValueRef> = args.iter()
+                                              .map(|val| val.as_value_ref())
+                                              .collect();
+        let value = unsafe {
+            LLVMBuildCleanupPad(self.builder, parent_pad, args.as_mut_ptr(), args.len() as u32, c_string.as_ptr())
+        };
+
+        InstructionValue::new(value)
+    }
+
+    /// Builds a catchret instruction, which exits `catch_pad` and continues at `basic_block`.
+    #[llvm_versions(6.0 => latest)]
+    pub fn build_catch_ret(&self, catch_pad: InstructionValue, basic_block: &BasicBlock) -> InstructionValue {
+        use llvm_sys::core::LLVMBuildCatchRet;
+
+        let value = unsafe {
+            LLVMBuildCatchRet(self.builder, catch_pad.as_value_ref(), basic_block.basic_block)
+        };
+
+        InstructionValue::new(value)
+    }
+
+    /// Builds a cleanupret instruction, which exits `cleanup_pad` and continues unwinding
+    /// at `unwind_block`, or in the caller if it is `None`.
+    #[llvm_versions(6.0 => latest)]
+    pub fn build_cleanup_ret(&self, cleanup_pad: InstructionValue, unwind_block: Option<&BasicBlock>) -> InstructionValue {
+        use llvm_sys::core::LLVMBuildCleanupRet;
+        use std::ptr;
+
+        let unwind_block = unwind_block.map_or(ptr::null_mut(), |bb| bb.basic_block);
+        let value = unsafe {
+            LLVMBuildCleanupRet(self.builder, cleanup_pad.as_value_ref(), unwind_block)
+        };
+
+        InstructionValue::new(value)
+    }
+
+    /// Builds a GEP instruction, returning a pointer to the element selected by `ordered_indexes`.
+    ///
+    /// The indices are checked against the pointer's element type before the instruction is
+    /// built: struct indices must be constant `i32`s naming an existing field, and constant
+    /// array or vector indices must be within bounds (or one past the end, as the final index).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let array_type = i32_type.array_type(4);
+    /// let fn_type = context.void_type().fn_type(&[array_type.ptr_type(AddressSpace::Generic).into()], false);
+    /// let fn_value = module.add_function("my_fn", fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    /// let zero = i32_type.const_zero();
+    /// let elem_ptr = builder.build_gep(ptr, &[zero, i32_type.const_int(2, false)], "elem_ptr").unwrap();
+    ///
+    /// assert_eq!(elem_ptr.get_type(), i32_type.ptr_type(AddressSpace::Generic));
+    /// assert!(builder.build_gep(ptr, &[zero, i32_type.const_int(7, false)], "oob").is_err());
+    /// ```
+    pub fn build_gep(&self, ptr: PointerValue, ordered_indexes: &[IntValue], name: &str) -> Result<PointerValue, GEPError> {
+        let element_type = get_gep_element_type(ptr.get_type(), ordered_indexes)?;
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let mut index_values: Vec<LLVMValueRef> = ordered_indexes.iter()
                                                                  .map(|val| val.as_value_ref())
                                                                  .collect();
-        let value = LLVMBuildGEP(self.builder, ptr.as_value_ref(), index_values.as_mut_ptr(), index_values.len() as u32, c_string.as_ptr());
+        let value = unsafe {
+            LLVMBuildGEP(self.builder, ptr.as_value_ref(), index_values.as_mut_ptr(), index_values.len() as u32, c_string.as_ptr())
+        };
+        let ptr_value = PointerValue::new(value);
 
-        PointerValue::new(value)
+        debug_assert_eq!(ptr_value.get_type().get_element_type(), element_type);
+
+        Ok(ptr_value)
     }
 
-    // REVIEW: Doesn't GEP work on array too?
     // REVIEW: This could be merge in with build_gep via a in_bounds: bool param
-    /// GEP is very likely to segfault if indexes are used incorrectly, and is therefore an unsafe function. Maybe we can change this in the future.
-    pub unsafe fn build_in_bounds_gep(&self, ptr: PointerValue, ordered_indexes: &[IntValue], name: &str) -> PointerValue {
+    /// Builds an in bounds GEP instruction. The indices are checked in the same way as `build_gep`.
+    pub fn build_in_bounds_gep(&self, ptr: PointerValue, ordered_indexes: &[IntValue], name: &str) -> Result<PointerValue, GEPError> {
+        let element_type = get_gep_element_type(ptr.get_type(), ordered_indexes)?;
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
         let mut index_values: Vec<LLVMValueRef> = ordered_indexes.iter()
                                                                  .map(|val| val.as_value_ref())
                                                                  .collect();
-        let value = LLVMBuildInBoundsGEP(self.builder, ptr.as_value_ref(), index_values.as_mut_ptr(), index_values.len() as u32, c_string.as_ptr());
+        let value = unsafe {
+            LLVMBuildInBoundsGEP(self.builder, ptr.as_value_ref(), index_values.as_mut_ptr(), index_values.len() as u32, c_string.as_ptr())
+        };
+        let ptr_value = PointerValue::new(value);
 
-        PointerValue::new(value)
+        debug_assert_eq!(ptr_value.get_type().get_element_type(), element_type);
+
+        Ok(ptr_value)
     }
 
     // REVIEW: Shouldn't this take a StructValue? Or does it still need to be PointerValue<StructValue>?
-    // I think it's the latter. This might not be as unsafe as regular GEP. Should check to see if it lets us
-    // go OOB. Maybe we could use the PointerValue<StructValue>'s struct info to do bounds checking...
-    /// GEP is very likely to segfault if indexes are used incorrectly, and is therefore an unsafe function. Maybe we can change this in the future.
-    pub unsafe fn build_struct_gep(&self, ptr: PointerValue, index: u32, name: &str) -> PointerValue {
+    /// Builds a GEP to the field at `index` of the struct `ptr` points to. Returns an error if
+    /// `ptr` does not point to a struct with a body, or if the struct has no such field.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let f64_type = context.f64_type();
+    /// let struct_type = context.struct_type(&[i32_type.into(), f64_type.into()], false);
+    /// let fn_type = context.void_type().fn_type(&[struct_type.ptr_type(AddressSpace::Generic).into()], false);
+    /// let fn_value = module.add_function("my_fn", fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    /// let field_ptr = builder.build_struct_gep(ptr, 1, "field_ptr").unwrap();
+    ///
+    /// assert_eq!(field_ptr.get_type(), f64_type.ptr_type(AddressSpace::Generic));
+    /// assert!(builder.build_struct_gep(ptr, 2, "oob").is_err());
+    /// ```
+    pub fn build_struct_gep(&self, ptr: PointerValue, index: u32, name: &str) -> Result<PointerValue, GEPError> {
+        let struct_type = match ptr.get_type().get_element_type() {
+            AnyTypeEnum::StructType(struct_type) => struct_type,
+            _ => return Err(GEPError::NonStructPointee),
+        };
+
+        if struct_type.is_opaque() {
+            return Err(GEPError::OpaqueStruct { position: 1 });
+        }
+
+        let field_count = struct_type.count_fields();
+
+        if index >= field_count {
+            return Err(GEPError::StructIndexOutOfBounds { position: 1, index: index as u64, field_count });
+        }
+
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
 
-        let value = LLVMBuildStructGEP(self.builder, ptr.as_value_ref(), index, c_string.as_ptr());
+        let value = unsafe {
+            LLVMBuildStructGEP(self.builder, ptr.as_value_ref(), index, c_string.as_ptr())
+        };
 
-        PointerValue::new(value)
+        Ok(PointerValue::new(value))
+    }
+
+    /// Builds a GEP to the field named `field_name` of the struct `ptr` points to, as set
+    /// via `StructType::set_field_names`. Returns an error if `ptr` does not point to a struct,
+    /// or if the struct has no field with that name.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let f64_type = context.f64_type();
+    /// let struct_type = context.opaque_struct_type("entry");
+    ///
+    /// struct_type.set_body(&[i32_type.into(), f64_type.into()], false);
+    /// struct_type.set_field_names(&["key", "value"]);
+    ///
+    /// let fn_type = context.void_type().fn_type(&[struct_type.ptr_type(AddressSpace::Generic).into()], false);
+    /// let fn_value = module.add_function("my_fn", fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    /// let value_ptr = builder.build_struct_gep_by_name(ptr, "value", "value_ptr").unwrap();
+    ///
+    /// assert_eq!(value_ptr.get_type(), f64_type.ptr_type(AddressSpace::Generic));
+    /// assert!(builder.build_struct_gep_by_name(ptr, "missing", "missing_ptr").is_err());
+    /// ```
+    pub fn build_struct_gep_by_name(&self, ptr: PointerValue, field_name: &str, name: &str) -> Result<PointerValue, FieldNameError> {
+        let struct_type = match ptr.get_type().get_element_type() {
+            AnyTypeEnum::StructType(struct_type) => struct_type,
+            _ => return Err(FieldNameError::NonStructType),
+        };
+        let index = struct_type.get_field_index(field_name)?;
+
+        // Field names are only set on structs with a body, for each of their fields
+        Ok(self.build_struct_gep(ptr, index, name).expect("Named field should be in bounds"))
     }
 
     pub fn build_ptr_diff(&self, lhs_ptr: PointerValue, rhs_ptr: PointerValue, name: &str) -> IntValue {
@@ -227,6 +577,163 @@ impl Builder {
         InstructionValue::new(val)
     }
 
+    /// Builds a call to `llvm.memcpy`, which copies `size` bytes from `src` to `dest`, declaring
+    /// the intrinsic in the current module if need be. The two regions must not overlap.
+    ///
+    /// An alignment of 0 means nothing is known about the alignment of that pointer. Returns
+    /// an error if an alignment is not 0 or a power of two, the builder isn't positioned in a function,
+    /// or the module has a function of a different type named after the intrinsic.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_ptr_type = context.i32_type().ptr_type(AddressSpace::Generic);
+    /// let i64_type = context.i64_type();
+    /// let fn_type = context.void_type().fn_type(&[i32_ptr_type.into(), i32_ptr_type.into(), i64_type.into()], false);
+    /// let fn_value = module.add_function("copy", fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let dest = fn_value.get_nth_param(0).unwrap().into_pointer_value();
+    /// let src = fn_value.get_nth_param(1).unwrap().into_pointer_value();
+    /// let size = fn_value.get_nth_param(2).unwrap().into_int_value();
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_memcpy(dest, 4, src, 4, size, false).unwrap();
+    /// builder.build_return(None);
+    ///
+    /// assert!(module.get_function("llvm.memcpy.p0i8.p0i8.i64").is_some());
+    /// ```
+    #[llvm_versions(7.0 => latest)]
+    pub fn build_memcpy(&self, dest: PointerValue, dest_align_bytes: u32, src: PointerValue, src_align_bytes: u32, size: IntValue, is_volatile: bool) -> Result<CallSiteValue, &'static str> {
+        self.build_memory_transfer("llvm.memcpy", dest, dest_align_bytes, src, src_align_bytes, size, is_volatile)
+    }
+
+    /// Builds a call to `llvm.memmove`, which copies `size` bytes from `src` to `dest`, declaring
+    /// the intrinsic in the current module if need be. Unlike `build_memcpy`, the two regions may overlap.
+    ///
+    /// An alignment of 0 means nothing is known about the alignment of that pointer. Returns
+    /// an error if an alignment is not 0 or a power of two, the builder isn't positioned in a function,
+    /// or the module has a function of a different type named after the intrinsic.
+    #[llvm_versions(7.0 => latest)]
+    pub fn build_memmove(&self, dest: PointerValue, dest_align_bytes: u32, src: PointerValue, src_align_bytes: u32, size: IntValue, is_volatile: bool) -> Result<CallSiteValue, &'static str> {
+        self.build_memory_transfer("llvm.memmove", dest, dest_align_bytes, src, src_align_bytes, size, is_volatile)
+    }
+
+    #[llvm_versions(7.0 => latest)]
+    #[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+    fn build_memory_transfer(&self, intrinsic_name: &str, dest: PointerValue, dest_align_bytes: u32, src: PointerValue, src_align_bytes: u32, size: IntValue, is_volatile: bool) -> Result<CallSiteValue, &'static str> {
+        if (!dest_align_bytes.is_power_of_two() && dest_align_bytes != 0) || (!src_align_bytes.is_power_of_two() && src_align_bytes != 0) {
+            return Err("Alignment must be 0 or a power of two");
+        }
+
+        let module = self.get_insert_module().ok_or("Builder must be positioned inside a function")?;
+        let dest = self.build_byte_pointer_cast(dest);
+        let src = self.build_byte_pointer_cast(src);
+        let overload_types = [dest.get_type().into(), src.get_type().into(), size.get_type().into()];
+        let function = Intrinsic::find(intrinsic_name)
+            .and_then(|intrinsic| intrinsic.get_declaration_in(module, &overload_types))
+            .ok_or("The module already has a function of a different type by the intrinsic's name")?;
+        let is_volatile = size.get_type().get_context().bool_type().const_int(is_volatile as u64, false);
+        let call_site_value = self.build_call(function, &[dest.into(), src.into(), size.into(), is_volatile.into()], "");
+
+        // Attribute index 0 is the return value, so parameters start at 1
+        if dest_align_bytes != 0 {
+            call_site_value.set_param_alignment_attribute(1, dest_align_bytes);
+        }
+
+        if src_align_bytes != 0 {
+            call_site_value.set_param_alignment_attribute(2, src_align_bytes);
+        }
+
+        Ok(call_site_value)
+    }
+
+    /// Builds a call to `llvm.memset`, which fills `size` bytes at `dest` with the byte `value`,
+    /// declaring the intrinsic in the current module if need be.
+    ///
+    /// An alignment of 0 means nothing is known about the alignment of `dest`. Returns an error if
+    /// the alignment is not 0 or a power of two, `value` is not an `i8`, the builder isn't positioned in a
+    /// function, or the module has a function of a different type named after the intrinsic.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i8_type = context.i8_type();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = context.void_type().fn_type(&[i8_type.ptr_type(AddressSpace::Generic).into()], false);
+    /// let fn_value = module.add_function("zero", fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let dest = fn_value.get_nth_param(0).unwrap().into_pointer_value();
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_memset(dest, 1, i8_type.const_int(0, false), i32_type.const_int(16, false), false).unwrap();
+    /// builder.build_return(None);
+    ///
+    /// assert!(module.get_function("llvm.memset.p0i8.i32").is_some());
+    /// ```
+    #[llvm_versions(7.0 => latest)]
+    pub fn build_memset(&self, dest: PointerValue, dest_align_bytes: u32, value: IntValue, size: IntValue, is_volatile: bool) -> Result<CallSiteValue, &'static str> {
+        if !dest_align_bytes.is_power_of_two() && dest_align_bytes != 0 {
+            return Err("Alignment must be 0 or a power of two");
+        }
+
+        if value.get_type().get_bit_width() != 8 {
+            return Err("Value must be an i8");
+        }
+
+        let module = self.get_insert_module().ok_or("Builder must be positioned inside a function")?;
+        let dest = self.build_byte_pointer_cast(dest);
+        let overload_types = [dest.get_type().into(), size.get_type().into()];
+        let function = Intrinsic::find("llvm.memset")
+            .and_then(|intrinsic| intrinsic.get_declaration_in(module, &overload_types))
+            .ok_or("The module already has a function of a different type by the intrinsic's name")?;
+        let is_volatile = size.get_type().get_context().bool_type().const_int(is_volatile as u64, false);
+        let call_site_value = self.build_call(function, &[dest.into(), value.into(), size.into(), is_volatile.into()], "");
+
+        if dest_align_bytes != 0 {
+            call_site_value.set_param_alignment_attribute(1, dest_align_bytes);
+        }
+
+        Ok(call_site_value)
+    }
+
+    // Memory intrinsics are declared on i8 pointers, the same way clang declares them
+    #[llvm_versions(7.0 => latest)]
+    fn build_byte_pointer_cast(&self, ptr: PointerValue) -> PointerValue {
+        let ptr_type = ptr.get_type();
+        let byte_ptr_type = ptr_type.get_context().i8_type().ptr_type(ptr_type.get_address_space());
+
+        self.build_pointer_cast(ptr, byte_ptr_type, "")
+    }
+
+    #[llvm_versions(7.0 => latest)]
+    fn get_insert_module(&self) -> Option<LLVMModuleRef> {
+        use llvm_sys::core::LLVMGetGlobalParent;
+
+        let function = self.get_insert_block()?.get_parent()?;
+
+        let module = unsafe {
+            LLVMGetGlobalParent(function.as_value_ref())
+        };
+
+        if module.is_null() {
+            return None;
+        }
+
+        Some(module)
+    }
+
     pub fn insert_instruction(&self, instruction: &InstructionValue, name: Option<&str>) {
         match name {
             Some(name) => {
@@ -242,6 +749,37 @@ impl Builder {
         }
     }
 
+    /// Sets the source location attached to all instructions built from now on.
+    #[llvm_versions(7.0 => latest)]
+    pub fn set_current_debug_location(&self, context: &Context, location: DILocation) {
+        use llvm_sys::core::{LLVMMetadataAsValue, LLVMSetCurrentDebugLocation};
+
+        unsafe {
+            LLVMSetCurrentDebugLocation(self.builder, LLVMMetadataAsValue(*context.context, location.metadata_ref))
+        }
+    }
+
+    /// Gets the source location attached to all instructions built from now on, if any.
+    #[llvm_versions(7.0 => latest)]
+    pub fn get_current_debug_location(&self) -> Option<DILocation<'_>> {
+        use llvm_sys::core::{LLVMGetCurrentDebugLocation, LLVMGetMDNodeNumOperands, LLVMValueAsMetadata};
+
+        let value = unsafe {
+            LLVMGetCurrentDebugLocation(self.builder)
+        };
+
+        // LLVM returns an empty node rather than null when there is no location
+        if value.is_null() || unsafe { LLVMGetMDNodeNumOperands(value) } == 0 {
+            return None;
+        }
+
+        let metadata_ref = unsafe {
+            LLVMValueAsMetadata(value)
+        };
+
+        Some(DILocation::new(metadata_ref))
+    }
+
     pub fn get_insert_block(&self) -> Option<BasicBlock> {
         let bb = unsafe {
             LLVMGetInsertBlock(self.builder)
@@ -1033,6 +1571,43 @@ impl Builder {
         Some(BasicValueEnum::new(value))
     }
 
+    /// Builds an extract value instruction which extracts the field named `field_name`
+    /// from a struct, as set via `StructType::set_field_names`. Returns an error if the
+    /// struct has no field with that name.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let f64_type = context.f64_type();
+    /// let struct_type = context.opaque_struct_type("entry");
+    ///
+    /// struct_type.set_body(&[i32_type.into(), f64_type.into()], false);
+    /// struct_type.set_field_names(&["key", "value"]);
+    ///
+    /// let fn_type = context.void_type().fn_type(&[struct_type.into()], false);
+    /// let fn_value = module.add_function("my_fn", fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let struct_value = fn_value.get_first_param().unwrap().into_struct_value();
+    /// let value = builder.build_extract_value_by_name(struct_value, "value", "value").unwrap();
+    ///
+    /// assert!(value.is_float_value());
+    /// assert!(builder.build_extract_value_by_name(struct_value, "missing", "missing").is_err());
+    /// ```
+    pub fn build_extract_value_by_name(&self, struct_value: StructValue, field_name: &str, name: &str) -> Result<BasicValueEnum, FieldNameError> {
+        let index = struct_value.get_type().get_field_index(field_name)?;
+
+        Ok(self.build_extract_value(struct_value, index, name).expect("Named field should be in bounds"))
+    }
+
     /// Builds an insert value instruction which inserts a `BasicValue` into a struct
     /// or array.
     ///
@@ -1129,6 +1704,247 @@ impl Builder {
         InstructionValue::new(val)
     }
 
+    /// Builds an atomicrmw instruction, which atomically applies `op` to the integer `ptr`
+    /// points to and `value`, returning the previous value. If `single_thread` is set, the
+    /// instruction is only synchronized with the current thread, ie with signal handlers,
+    /// rather than with all threads.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::{AddressSpace, AtomicOrdering, AtomicRMWBinOp};
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[i32_type.ptr_type(AddressSpace::Generic).into()], false);
+    /// let fn_value = module.add_function("fetch_add", fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    /// let one = i32_type.const_int(1, false);
+    /// let old_value = builder.build_atomicrmw(AtomicRMWBinOp::Add, ptr, one, AtomicOrdering::SequentiallyConsistent, false).unwrap();
+    ///
+    /// builder.build_return(Some(&old_value));
+    /// ```
+    pub fn build_atomicrmw(&self, op: AtomicRMWBinOp, ptr: PointerValue, value: IntValue, ordering: AtomicOrdering, single_thread: bool) -> Result<IntValue, &'static str> {
+        if ptr.get_type().get_element_type() != value.get_type().as_any_type_enum() {
+            return Err("Pointer's element type must match the value's type");
+        }
+
+        let bit_width = value.get_type().get_bit_width();
+
+        if bit_width < 8 || !bit_width.is_power_of_two() {
+            return Err("The value's bit width must be a power of two of at least 8");
+        }
+
+        match ordering {
+            AtomicOrdering::NotAtomic | AtomicOrdering::Unordered => return Err("The ordering must be at least monotonic"),
+            _ => {},
+        }
+
+        let value = unsafe {
+            LLVMBuildAtomicRMW(self.builder, op.as_llvm_enum(), ptr.as_value_ref(), value.as_value_ref(), ordering.as_llvm_enum(), single_thread as i32)
+        };
+
+        Ok(IntValue::new(value))
+    }
+
+    /// Builds a cmpxchg instruction, which atomically stores `new` to `ptr` if the value
+    /// there equals `cmp`. It returns a `{ value, i1 }` struct holding the previous value and
+    /// whether the exchange succeeded. The values must be integers or pointers, the failure
+    /// ordering may be neither `Release` nor `AcquireRelease`, and it may not be stronger than
+    /// the success ordering. If `single_thread` is set, the instruction is only synchronized
+    /// with the current thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::{AddressSpace, AtomicOrdering};
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = context.bool_type().fn_type(&[i32_type.ptr_type(AddressSpace::Generic).into()], false);
+    /// let fn_value = module.add_function("try_lock", fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    /// let zero = i32_type.const_zero();
+    /// let one = i32_type.const_int(1, false);
+    /// let result = builder.build_cmpxchg(ptr, zero, one, AtomicOrdering::AcquireRelease, AtomicOrdering::Acquire, false).unwrap();
+    /// let success = builder.build_extract_value(result, 1, "success").unwrap();
+    ///
+    /// builder.build_return(Some(&success));
+    /// ```
+    #[llvm_versions(3.9 => latest)]
+    pub fn build_cmpxchg<V: BasicValue>(&self, ptr: PointerValue, cmp: V, new: V, success: AtomicOrdering, failure: AtomicOrdering, single_thread: bool) -> Result<StructValue, &'static str> {
+        use llvm_sys::core::LLVMBuildAtomicCmpXchg;
+
+        let cmp_type = cmp.as_basic_value_enum().get_type();
+
+        if cmp_type != new.as_basic_value_enum().get_type() {
+            return Err("The compared and new values must have the same type");
+        }
+
+        if ptr.get_type().get_element_type() != cmp_type.as_any_type_enum() {
+            return Err("Pointer's element type must match the values' type");
+        }
+
+        if !cmp_type.is_int_type() && !cmp_type.is_pointer_type() {
+            return Err("The values must be integers or pointers");
+        }
+
+        match success {
+            AtomicOrdering::NotAtomic | AtomicOrdering::Unordered => return Err("The success ordering must be at least monotonic"),
+            _ => {},
+        }
+
+        match (failure, success) {
+            (AtomicOrdering::NotAtomic, _) | (AtomicOrdering::Unordered, _) => return Err("The failure ordering must be at least monotonic"),
+            (AtomicOrdering::Release, _) | (AtomicOrdering::AcquireRelease, _) => return Err("The failure ordering may not be release or acquire-release"),
+            (AtomicOrdering::Acquire, AtomicOrdering::Monotonic) |
+            (AtomicOrdering::SequentiallyConsistent, AtomicOrdering::Monotonic) |
+            (AtomicOrdering::SequentiallyConsistent, AtomicOrdering::Acquire) |
+            (AtomicOrdering::SequentiallyConsistent, AtomicOrdering::Release) |
+            (AtomicOrdering::SequentiallyConsistent, AtomicOrdering::AcquireRelease) => return Err("The failure ordering may not be stronger than the success ordering"),
+            _ => {},
+        }
+
+        let value = unsafe {
+            LLVMBuildAtomicCmpXchg(self.builder, ptr.as_value_ref(), cmp.as_value_ref(), new.as_value_ref(), success.as_llvm_enum(), failure.as_llvm_enum(), single_thread as i32)
+        };
+
+        Ok(StructValue::new(value))
+    }
+
+    /// Builds an atomic load from `ptr`, which must point to an integer whose bit width is
+    /// a power of two of at least 8, a pointer or a floating point number. Atomic loads must
+    /// have an explicit, power of two `alignment`, and may not be `Release` or `AcquireRelease`.
+    ///
+    /// The load is synchronized with all threads. LLVM 7's C API can only set the sync scope of
+    /// atomicrmw and cmpxchg instructions, so there is no way to restrict it to a single thread.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::{AddressSpace, AtomicOrdering};
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[i32_type.ptr_type(AddressSpace::Generic).into()], false);
+    /// let fn_value = module.add_function("load_acquire", fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    /// let value = builder.build_atomic_load(ptr, 4, AtomicOrdering::Acquire, "value").unwrap();
+    ///
+    /// builder.build_return(Some(&value));
+    /// ```
+    pub fn build_atomic_load(&self, ptr: PointerValue, alignment: u32, ordering: AtomicOrdering, name: &str) -> Result<BasicValueEnum, &'static str> {
+        Builder::check_atomic_access(ptr.get_type().get_element_type(), alignment, ordering)?;
+
+        match ordering {
+            AtomicOrdering::Release | AtomicOrdering::AcquireRelease => return Err("Loads may not have release or acquire-release ordering"),
+            _ => {},
+        }
+
+        let value = self.build_load(ptr, name);
+        let instruction = value.as_instruction_value().expect("Loads are always instructions");
+
+        instruction.set_alignment(alignment)?;
+        instruction.set_atomic_ordering(ordering)?;
+
+        Ok(value)
+    }
+
+    /// Builds an atomic store of `value` to `ptr`, which must be an integer whose bit width is
+    /// a power of two of at least 8, a pointer or a floating point number. Atomic stores must
+    /// have an explicit, power of two `alignment`, and may not be `Acquire` or `AcquireRelease`.
+    ///
+    /// As with `build_atomic_load`, the store is always synchronized with all threads.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::{AddressSpace, AtomicOrdering};
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = context.void_type().fn_type(&[i32_type.ptr_type(AddressSpace::Generic).into()], false);
+    /// let fn_value = module.add_function("store_release", fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    ///
+    /// builder.build_atomic_store(ptr, i32_type.const_int(1, false), 4, AtomicOrdering::Release).unwrap();
+    /// builder.build_return(None);
+    /// ```
+    pub fn build_atomic_store<V: BasicValue>(&self, ptr: PointerValue, value: V, alignment: u32, ordering: AtomicOrdering) -> Result<InstructionValue, &'static str> {
+        let element_type = ptr.get_type().get_element_type();
+
+        if element_type != value.as_basic_value_enum().get_type().as_any_type_enum() {
+            return Err("Pointer's element type must match the value's type");
+        }
+
+        Builder::check_atomic_access(element_type, alignment, ordering)?;
+
+        match ordering {
+            AtomicOrdering::Acquire | AtomicOrdering::AcquireRelease => return Err("Stores may not have acquire or acquire-release ordering"),
+            _ => {},
+        }
+
+        let instruction = self.build_store(ptr, value);
+
+        instruction.set_alignment(alignment)?;
+        instruction.set_atomic_ordering(ordering)?;
+
+        Ok(instruction)
+    }
+
+    // Checks shared by atomic loads and stores, which are otherwise left for the verifier to reject
+    fn check_atomic_access(element_type: AnyTypeEnum, alignment: u32, ordering: AtomicOrdering) -> Result<(), &'static str> {
+        match element_type {
+            AnyTypeEnum::IntType(int_type) => {
+                let bit_width = int_type.get_bit_width();
+
+                if bit_width < 8 || !bit_width.is_power_of_two() {
+                    return Err("The value's bit width must be a power of two of at least 8");
+                }
+            },
+            AnyTypeEnum::PointerType(_) | AnyTypeEnum::FloatType(_) => {},
+            _ => return Err("The value must be an integer, pointer or floating point number"),
+        }
+
+        if !alignment.is_power_of_two() {
+            return Err("Atomic loads and stores must have a power of two alignment");
+        }
+
+        if ordering == AtomicOrdering::NotAtomic {
+            return Err("The ordering must be atomic");
+        }
+
+        Ok(())
+    }
+
     // SubType: <P>(&self, ptr: &PointerValue<P>, name) -> IntValue<bool> {
     pub fn build_is_null<T: PointerMathValue>(&self, ptr: T, name: &str) -> <<T::BaseType as PointerMathType>::PtrConvType as IntMathType>::ValueType {
         let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
@@ -1,21 +1,30 @@
 //! A `Context` is an opaque owner and manager of core global data.
 
-use llvm_sys::core::{LLVMAppendBasicBlockInContext, LLVMContextCreate, LLVMContextDispose, LLVMCreateBuilderInContext, LLVMDoubleTypeInContext, LLVMFloatTypeInContext, LLVMFP128TypeInContext, LLVMInsertBasicBlockInContext, LLVMInt16TypeInContext, LLVMInt1TypeInContext, LLVMInt32TypeInContext, LLVMInt64TypeInContext, LLVMInt8TypeInContext, LLVMIntTypeInContext, LLVMModuleCreateWithNameInContext, LLVMStructCreateNamed, LLVMStructTypeInContext, LLVMVoidTypeInContext, LLVMHalfTypeInContext, LLVMGetGlobalContext, LLVMPPCFP128TypeInContext, LLVMConstStructInContext, LLVMMDNodeInContext, LLVMMDStringInContext, LLVMGetMDKindIDInContext, LLVMX86FP80TypeInContext, LLVMConstStringInContext, LLVMContextSetDiagnosticHandler};
+use llvm_sys::core::{LLVMAppendBasicBlockInContext, LLVMContextCreate, LLVMContextDispose, LLVMCreateBuilderInContext, LLVMDoubleTypeInContext, LLVMFloatTypeInContext, LLVMFP128TypeInContext, LLVMInsertBasicBlockInContext, LLVMInt16TypeInContext, LLVMInt1TypeInContext, LLVMInt32TypeInContext, LLVMInt64TypeInContext, LLVMInt8TypeInContext, LLVMIntTypeInContext, LLVMModuleCreateWithNameInContext, LLVMStructCreateNamed, LLVMStructTypeInContext, LLVMVoidTypeInContext, LLVMHalfTypeInContext, LLVMGetGlobalContext, LLVMPPCFP128TypeInContext, LLVMConstStructInContext, LLVMMDNodeInContext, LLVMMDStringInContext, LLVMGetMDKindIDInContext, LLVMX86FP80TypeInContext, LLVMConstStringInContext, LLVMContextSetDiagnosticHandler, LLVMContextGetDiagnosticHandler, LLVMContextGetDiagnosticContext};
 #[llvm_versions(4.0 => latest)]
 use llvm_sys::core::{LLVMCreateEnumAttribute, LLVMCreateStringAttribute};
-use llvm_sys::prelude::{LLVMContextRef, LLVMTypeRef, LLVMValueRef, LLVMDiagnosticInfoRef};
+use llvm_sys::prelude::{LLVMContextRef, LLVMTypeRef, LLVMValueRef};
 use llvm_sys::ir_reader::LLVMParseIRInContext;
+use llvm_sys::LLVMDiagnosticHandler;
 use libc::c_void;
 
 #[llvm_versions(4.0 => latest)]
 use attributes::Attribute;
+#[llvm_versions(7.0 => latest)]
+use InlineAsmDialect;
 use basic_block::BasicBlock;
 use builder::Builder;
 use memory_buffer::MemoryBuffer;
 use module::Module;
 use support::LLVMString;
-use types::{BasicTypeEnum, FloatType, IntType, StructType, VoidType, AsTypeRef};
-use values::{AsValueRef, FunctionValue, StructValue, MetadataValue, BasicValueEnum, VectorValue};
+use support::error_handling::{DiagnosticHandler, DiagnosticInfo, closure_diagnostic_handler};
+use types::{BasicTypeEnum, FloatType, IntType, StructType, VoidType, AsTypeRef, remove_field_names};
+use values::{AsValueRef, FunctionValue, IntoConst, StructValue, MetadataValue, BasicValueEnum, VectorValue};
+
+#[llvm_versions(7.0 => latest)]
+use types::FunctionType;
+#[llvm_versions(7.0 => latest)]
+use values::PointerValue;
 
 use std::ffi::CString;
 use std::mem::forget;
@@ -534,6 +543,24 @@ impl Context {
         StructValue::new(value)
     }
 
+    /// Creates a constant from a Rust value, such as a primitive, slice, array, tuple
+    /// or string. See `IntoConst` for the types of the resulting constants.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let table = context.const_value(&[1i32, 2, 3]).into_array_value();
+    ///
+    /// assert_eq!(table.get_type(), context.i32_type().array_type(3));
+    /// assert!(table.is_const());
+    /// ```
+    pub fn const_value<V: IntoConst + ?Sized>(&self, value: &V) -> BasicValueEnum {
+        value.const_value(self)
+    }
+
     /// Append a named `BasicBlock` at the end of the referenced `FunctionValue`.
     ///
     /// # Example
@@ -717,16 +744,60 @@ impl Context {
         }
     }
 
-    // LLVM 3.9+
-    // pub fn get_diagnostic_handler(&self) -> DiagnosticHandler {
-    //     let handler = unsafe {
-    //         LLVMContextGetDiagnosticHandler(self.context)
-    //     };
+    /// Sets a closure to be called with each diagnostic (error, warning, remark or note) LLVM
+    /// reports in this `Context`, ie from passes or codegen, instead of printing it to stderr.
+    /// This replaces any handler previously set on this `Context`.
+    ///
+    /// A `DiagnosticInfo` is only valid for the duration of the call. Note that LLVM may still
+    /// abort the process after an error if the operation which reported it cannot recover.
+    /// The process is also aborted if the closure panics, as it is called from within LLVM.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::support::error_handling::DiagnosticSeverity;
+    ///
+    /// let context = Context::create();
+    ///
+    /// context.set_diagnostic_handler(|diagnostic_info| {
+    ///     if diagnostic_info.get_severity() != DiagnosticSeverity::Remark {
+    ///         eprintln!("{:?}: {}", diagnostic_info.get_severity(), diagnostic_info.get_description().to_string());
+    ///     }
+    /// });
+    /// ```
+    pub fn set_diagnostic_handler<F: Fn(&DiagnosticInfo) + 'static>(&self, handler: F) {
+        self.clear_diagnostic_handler();
+        self.set_raw_diagnostic_handler(Some(closure_diagnostic_handler), DiagnosticHandler::into_raw(handler));
+    }
 
-    //     // REVIEW: Can this be null?
+    /// Removes the closure set by `set_diagnostic_handler`, if any, so that LLVM
+    /// goes back to printing diagnostics itself.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    ///
+    /// context.set_diagnostic_handler(|_| ());
+    /// context.clear_diagnostic_handler();
+    /// ```
+    pub fn clear_diagnostic_handler(&self) {
+        let (handler, void_ptr) = self.get_raw_diagnostic_handler();
 
-    //     DiagnosticHandler::new(handler)
-    // }
+        if handler.map(|handler| handler as *const ()) != Some(closure_diagnostic_handler as *const ()) {
+            return;
+        }
+
+        self.set_raw_diagnostic_handler(None, ptr::null_mut());
+
+        // Freeing is deferred if this is called from within the handler itself
+        unsafe {
+            DiagnosticHandler::release(void_ptr);
+        }
+    }
 
     /// Creates an enum `Attribute` in this `Context`.
     ///
@@ -791,16 +862,86 @@ impl Context {
         VectorValue::new(ptr)
     }
 
-    pub(crate) fn set_diagnostic_handler(&self, handler: extern "C" fn (LLVMDiagnosticInfoRef, *mut c_void), void_ptr: *mut c_void) {
+    /// Creates an inline assembly value, which may be called with `Builder::build_call`
+    /// like a function of type `fn_type`. Output operands are returned from the call,
+    /// in a struct if there are several, and input operands are passed as its arguments.
+    ///
+    /// `constraints` is a comma separated list of LLVM's operand and clobber constraints,
+    /// such as `"={eax},{ecx},~{dirflag}"`. Asm with side effects is never optimized away,
+    /// and `align_stack` realigns the stack before the asm runs. `None` as the dialect means AT&T.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i64_type = context.i64_type();
+    /// let fn_value = module.add_function("read_tsc", i64_type.fn_type(&[], false), None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let rdtsc = context.create_inline_asm(i64_type.fn_type(&[], false), "rdtsc", "=A", true, false, None);
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let tsc = builder.build_call(rdtsc, &[], "tsc").try_as_basic_value().left().unwrap();
+    ///
+    /// builder.build_return(Some(&tsc));
+    /// ```
+    #[llvm_versions(7.0 => latest)]
+    pub fn create_inline_asm(&self, fn_type: FunctionType, asm: &str, constraints: &str, has_side_effects: bool, align_stack: bool, dialect: Option<InlineAsmDialect>) -> PointerValue {
+        use llvm_sys::core::LLVMGetInlineAsm;
+
+        let dialect = dialect.unwrap_or(InlineAsmDialect::ATT);
+
+        let value = unsafe {
+            LLVMGetInlineAsm(fn_type.as_type_ref(), asm.as_ptr() as *mut i8, asm.len(), constraints.as_ptr() as *mut i8, constraints.len(), has_side_effects as i32, align_stack as i32, dialect.as_llvm_enum())
+        };
+
+        PointerValue::new(value)
+    }
+
+    pub(crate) fn get_raw_diagnostic_handler(&self) -> (LLVMDiagnosticHandler, *mut c_void) {
+        unsafe {
+            (LLVMContextGetDiagnosticHandler(*self.context), LLVMContextGetDiagnosticContext(*self.context))
+        }
+    }
+
+    pub(crate) fn set_raw_diagnostic_handler(&self, handler: LLVMDiagnosticHandler, void_ptr: *mut c_void) {
         unsafe {
-            LLVMContextSetDiagnosticHandler(*self.context, Some(handler), void_ptr)
+            LLVMContextSetDiagnosticHandler(*self.context, handler, void_ptr)
         }
     }
+
+    // Whether the given Modules (and other_references handles to this Context besides them) are the only
+    // owners of this Context, so that they may all be moved to another thread together. Closures set
+    // via set_diagnostic_handler aren't required to be Send, so they must not be moved along with them.
+    pub(crate) fn is_only_owned_by(&self, modules: &[Module], other_references: usize) -> bool {
+        let (handler, _) = self.get_raw_diagnostic_handler();
+        let has_closure_handler = handler.map(|handler| handler as *const ()) == Some(closure_diagnostic_handler as *const ());
+        let belongs_to_self = |module: &Module| {
+            let shares_rc = match module.non_global_context {
+                Some(ref context) => Rc::ptr_eq(&context.context, &self.context),
+                None => false,
+            };
+
+            shares_rc && module.owned_by_ee.borrow().is_none()
+        };
+
+        Rc::strong_count(&self.context) == modules.len() + other_references
+            && Rc::weak_count(&self.context) == 0
+            && !has_closure_handler
+            && modules.iter().all(belongs_to_self)
+    }
 }
 
 impl Drop for Context {
     fn drop(&mut self) {
         if Rc::strong_count(&self.context) == 1 {
+            self.clear_diagnostic_handler();
+            remove_field_names(*self.context);
+
             unsafe {
                 LLVMContextDispose(*self.context);
             }
@@ -844,3 +985,88 @@ impl Drop for ContextRef {
         forget(self.context.take());
     }
 }
+
+/// A `Context` bundled with every `Module` created in it, so that they can be moved to
+/// another thread together, ie to be compiled on a worker pool. On the receiving thread,
+/// `into_inner` gives them back.
+///
+/// A `ThreadSafeContext` can only be created when nothing else holds on to the `Context`,
+/// such as a `Module` not passed to `ThreadSafeContext::new` or an `ExecutionEngine`, and no
+/// closure has been set via `Context::set_diagnostic_handler`. Types, values and builders don't
+/// hold on to it though, which is why `ThreadSafeContext::new` is unsafe.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::context::{Context, ThreadSafeContext};
+///
+/// let context = Context::create();
+/// let module = context.create_module("my_module");
+/// let thread_safe_context = unsafe { ThreadSafeContext::new(context, vec![module]).unwrap() };
+///
+/// let handle = std::thread::spawn(move || {
+///     let (context, modules) = thread_safe_context.into_inner();
+///     let fn_type = context.void_type().fn_type(&[], false);
+///
+///     modules[0].add_function("my_fn", fn_type, None);
+///
+///     unsafe { ThreadSafeContext::new(context, modules).unwrap() }
+/// });
+///
+/// let (_context, modules) = handle.join().unwrap().into_inner();
+///
+/// assert!(modules[0].get_function("my_fn").is_some());
+/// ```
+#[derive(Debug)]
+pub struct ThreadSafeContext {
+    context: Context,
+    modules: Vec<Module>,
+}
+
+// A Context is only !Send because of its Rc and the raw pointers it and its Modules hold. Since
+// ThreadSafeContext::new checks that this Rc isn't shared with anything else, and its caller
+// guarantees nothing else will use the LLVMContext on this thread, moving them all together to
+// another thread can't lead to it being used from two threads at once.
+unsafe impl Send for ThreadSafeContext {}
+
+impl ThreadSafeContext {
+    /// Bundles a `Context` with all of the `Module`s which reference it. If anything else
+    /// still references the `Context`, they are returned as is instead.
+    ///
+    /// # Safety
+    ///
+    /// Types, values, basic blocks and `Builder`s don't keep the `Context` alive, so they
+    /// can't be checked for. None of them, nor any `ContextRef` to the `Context`, may be used
+    /// on the current thread afterwards, since the `Context` could then be in use on two
+    /// threads at once.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::{Context, ThreadSafeContext};
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let other_module = context.create_module("my_other_module");
+    ///
+    /// // other_module still references the context
+    /// let (context, modules) = unsafe { ThreadSafeContext::new(context, vec![module]).unwrap_err() };
+    ///
+    /// assert!(unsafe { ThreadSafeContext::new(context, vec![other_module]) }.is_err());
+    /// ```
+    pub unsafe fn new(context: Context, modules: Vec<Module>) -> Result<Self, (Context, Vec<Module>)> {
+        if !context.is_only_owned_by(&modules, 1) {
+            return Err((context, modules));
+        }
+
+        Ok(ThreadSafeContext {
+            context,
+            modules,
+        })
+    }
+
+    /// Gets back the `Context` and `Module`s, ie once they have been moved to another thread.
+    pub fn into_inner(self) -> (Context, Vec<Module>) {
+        (self.context, self.modules)
+    }
+}
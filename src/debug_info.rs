@@ -0,0 +1,699 @@
+//! Debug information, which describes a program's source level constructs so that
+//! debuggers such as gdb and lldb can step through the generated code.
+//!
+//! Debug info is emitted through a `DebugInfoBuilder`, created from the `Module` it
+//! describes. Every function with debug info needs a `DISubprogram`, and the
+//! instructions within it need a `DILocation`, which can be attached to all newly
+//! built instructions with `Builder::set_current_debug_location`. The builder must be
+//! finalized before the module is verified or compiled.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use inkwell::context::Context;
+//! use inkwell::debug_info::{DIFlags, DWARFEmissionKind, DWARFSourceLanguage, DWARFTypeEncoding, debug_metadata_version};
+//! use inkwell::module::FlagBehavior;
+//!
+//! let context = Context::create();
+//! let module = context.create_module("my_mod");
+//! let builder = context.create_builder();
+//! let debug_version = context.i32_type().const_int(debug_metadata_version() as u64, false);
+//!
+//! module.add_basic_value_flag("Debug Info Version", FlagBehavior::Warning, debug_version);
+//!
+//! let di_builder = module.create_debug_info_builder(true);
+//! let file = di_builder.create_file("main.src", ".");
+//! let compile_unit = di_builder.create_compile_unit(DWARFSourceLanguage::C, file, "my_compiler", false, "", 0, "", DWARFEmissionKind::Full, 0, false, false);
+//! let i32_di_type = di_builder.create_basic_type("int", 32, DWARFTypeEncoding::Signed);
+//! let fn_di_type = di_builder.create_subroutine_type(file, Some(i32_di_type), &[], DIFlags::ZERO);
+//! let subprogram = di_builder.create_function(compile_unit, "main", None, file, 1, fn_di_type, false, true, 1, DIFlags::ZERO, false);
+//!
+//! let fn_value = module.add_function("main", context.i32_type().fn_type(&[], false), None);
+//! let entry = fn_value.append_basic_block("entry");
+//!
+//! fn_value.set_subprogram(subprogram);
+//! builder.position_at_end(&entry);
+//!
+//! let location = di_builder.create_debug_location(&context, 2, 5, subprogram, None);
+//!
+//! builder.set_current_debug_location(&context, location);
+//! builder.build_return(Some(&context.i32_type().const_zero()));
+//!
+//! di_builder.finalize();
+//!
+//! assert!(module.verify().is_ok());
+//! ```
+
+use llvm_sys::debuginfo::{LLVMDIBuilderCreateArrayType, LLVMDIBuilderCreateAutoVariable, LLVMDIBuilderCreateBasicType, LLVMDIBuilderCreateCompileUnit, LLVMDIBuilderCreateDebugLocation, LLVMDIBuilderCreateExpression, LLVMDIBuilderCreateFile, LLVMDIBuilderCreateFunction, LLVMDIBuilderCreateLexicalBlock, LLVMDIBuilderCreateMemberType, LLVMDIBuilderCreateParameterVariable, LLVMDIBuilderCreatePointerType, LLVMDIBuilderCreateStructType, LLVMDIBuilderCreateSubroutineType, LLVMDIBuilderFinalize, LLVMDIBuilderGetOrCreateSubrange, LLVMDIBuilderInsertDeclareAtEnd, LLVMDIBuilderInsertDeclareBefore, LLVMDIBuilderInsertDbgValueBefore, LLVMDILocationGetColumn, LLVMDILocationGetLine, LLVMDILocationGetScope, LLVMDITypeGetAlignInBits, LLVMDITypeGetOffsetInBits, LLVMDITypeGetSizeInBits, LLVMDebugMetadataVersion, LLVMDisposeDIBuilder, LLVMDWARFEmissionKind, LLVMDWARFSourceLanguage, LLVMDIFlags};
+use llvm_sys::prelude::{LLVMDIBuilderRef, LLVMMetadataRef};
+
+use std::marker::PhantomData;
+use std::ops::{BitOr, Range};
+use std::ptr;
+
+use AddressSpace;
+use basic_block::BasicBlock;
+use context::Context;
+use module::Module;
+use values::{AsValueRef, BasicValue, InstructionValue, PointerValue};
+
+/// Gets the version of debug metadata produced by the current LLVM version. This
+/// should be added to a module as the "Debug Info Version" flag, otherwise LLVM will
+/// discard the module's debug info.
+pub fn debug_metadata_version() -> u32 {
+    unsafe {
+        LLVMDebugMetadataVersion()
+    }
+}
+
+enum_rename!{
+    /// Source languages known by DWARF.
+    DWARFSourceLanguage <=> LLVMDWARFSourceLanguage {
+        C89 <=> LLVMDWARFSourceLanguageC89,
+        C <=> LLVMDWARFSourceLanguageC,
+        Ada83 <=> LLVMDWARFSourceLanguageAda83,
+        CPlusPlus <=> LLVMDWARFSourceLanguageC_plus_plus,
+        Cobol74 <=> LLVMDWARFSourceLanguageCobol74,
+        Cobol85 <=> LLVMDWARFSourceLanguageCobol85,
+        Fortran77 <=> LLVMDWARFSourceLanguageFortran77,
+        Fortran90 <=> LLVMDWARFSourceLanguageFortran90,
+        Pascal83 <=> LLVMDWARFSourceLanguagePascal83,
+        Modula2 <=> LLVMDWARFSourceLanguageModula2,
+        Java <=> LLVMDWARFSourceLanguageJava,
+        C99 <=> LLVMDWARFSourceLanguageC99,
+        Ada95 <=> LLVMDWARFSourceLanguageAda95,
+        Fortran95 <=> LLVMDWARFSourceLanguageFortran95,
+        PLI <=> LLVMDWARFSourceLanguagePLI,
+        ObjC <=> LLVMDWARFSourceLanguageObjC,
+        ObjCPlusPlus <=> LLVMDWARFSourceLanguageObjC_plus_plus,
+        UPC <=> LLVMDWARFSourceLanguageUPC,
+        D <=> LLVMDWARFSourceLanguageD,
+        Python <=> LLVMDWARFSourceLanguagePython,
+        OpenCL <=> LLVMDWARFSourceLanguageOpenCL,
+        Go <=> LLVMDWARFSourceLanguageGo,
+        Modula3 <=> LLVMDWARFSourceLanguageModula3,
+        Haskell <=> LLVMDWARFSourceLanguageHaskell,
+        CPlusPlus03 <=> LLVMDWARFSourceLanguageC_plus_plus_03,
+        CPlusPlus11 <=> LLVMDWARFSourceLanguageC_plus_plus_11,
+        OCaml <=> LLVMDWARFSourceLanguageOCaml,
+        Rust <=> LLVMDWARFSourceLanguageRust,
+        C11 <=> LLVMDWARFSourceLanguageC11,
+        Swift <=> LLVMDWARFSourceLanguageSwift,
+        Julia <=> LLVMDWARFSourceLanguageJulia,
+        Dylan <=> LLVMDWARFSourceLanguageDylan,
+        CPlusPlus14 <=> LLVMDWARFSourceLanguageC_plus_plus_14,
+        Fortran03 <=> LLVMDWARFSourceLanguageFortran03,
+        Fortran08 <=> LLVMDWARFSourceLanguageFortran08,
+        RenderScript <=> LLVMDWARFSourceLanguageRenderScript,
+        BLISS <=> LLVMDWARFSourceLanguageBLISS,
+        MipsAssembler <=> LLVMDWARFSourceLanguageMips_Assembler,
+        GoogleRenderScript <=> LLVMDWARFSourceLanguageGOOGLE_RenderScript,
+        BorlandDelphi <=> LLVMDWARFSourceLanguageBORLAND_Delphi,
+    }
+}
+
+enum_rename!{
+    /// The amount of debug information to emit.
+    DWARFEmissionKind <=> LLVMDWARFEmissionKind {
+        /// No debug information.
+        None <=> LLVMDWARFEmissionKindNone,
+        /// Full debug information.
+        Full <=> LLVMDWARFEmissionKindFull,
+        /// Only enough debug information to produce line tables.
+        LineTablesOnly <=> LLVMDWARFEmissionKindLineTablesOnly,
+    }
+}
+
+/// Describes how the bits of a basic type are to be interpreted.
+///
+/// # Remarks
+/// See also: DWARF v5, section 7.8
+#[repr(u32)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum DWARFTypeEncoding {
+    Address        = 0x01,
+    Boolean        = 0x02,
+    ComplexFloat   = 0x03,
+    Float          = 0x04,
+    Signed         = 0x05,
+    SignedChar     = 0x06,
+    Unsigned       = 0x07,
+    UnsignedChar   = 0x08,
+    ImaginaryFloat = 0x09,
+    PackedDecimal  = 0x0a,
+    NumericString  = 0x0b,
+    Edited         = 0x0c,
+    SignedFixed    = 0x0d,
+    UnsignedFixed  = 0x0e,
+    DecimalFloat   = 0x0f,
+    UTF            = 0x10,
+}
+
+/// Flags which describe the properties of a debug info node, combined with `|`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct DIFlags(LLVMDIFlags);
+
+impl DIFlags {
+    pub const ZERO: DIFlags = DIFlags(0);
+    pub const PRIVATE: DIFlags = DIFlags(1);
+    pub const PROTECTED: DIFlags = DIFlags(2);
+    pub const PUBLIC: DIFlags = DIFlags(3);
+    pub const FWD_DECL: DIFlags = DIFlags(1 << 2);
+    pub const APPLE_BLOCK: DIFlags = DIFlags(1 << 3);
+    pub const VIRTUAL: DIFlags = DIFlags(1 << 5);
+    pub const ARTIFICIAL: DIFlags = DIFlags(1 << 6);
+    pub const EXPLICIT: DIFlags = DIFlags(1 << 7);
+    pub const PROTOTYPED: DIFlags = DIFlags(1 << 8);
+    pub const OBJC_CLASS_COMPLETE: DIFlags = DIFlags(1 << 9);
+    pub const OBJECT_POINTER: DIFlags = DIFlags(1 << 10);
+    pub const VECTOR: DIFlags = DIFlags(1 << 11);
+    pub const STATIC_MEMBER: DIFlags = DIFlags(1 << 12);
+    pub const LVALUE_REFERENCE: DIFlags = DIFlags(1 << 13);
+    pub const RVALUE_REFERENCE: DIFlags = DIFlags(1 << 14);
+    pub const BIT_FIELD: DIFlags = DIFlags(1 << 19);
+    pub const NO_RETURN: DIFlags = DIFlags(1 << 20);
+    pub const TYPE_PASS_BY_VALUE: DIFlags = DIFlags(1 << 22);
+    pub const TYPE_PASS_BY_REFERENCE: DIFlags = DIFlags(1 << 23);
+    pub const THUNK: DIFlags = DIFlags(1 << 25);
+}
+
+impl BitOr for DIFlags {
+    type Output = DIFlags;
+
+    fn bitor(self, other: DIFlags) -> DIFlags {
+        DIFlags(self.0 | other.0)
+    }
+}
+
+macro_rules! di_node {
+    ($(#[$attrs:meta])* $name:ident) => (
+        $(#[$attrs])*
+        #[derive(Debug, PartialEq, Eq, Copy, Clone)]
+        pub struct $name<'m> {
+            pub(crate) metadata_ref: LLVMMetadataRef,
+            _module: PhantomData<&'m Module>,
+        }
+
+        impl<'m> $name<'m> {
+            pub(crate) fn new(metadata_ref: LLVMMetadataRef) -> Self {
+                assert!(!metadata_ref.is_null());
+
+                $name {
+                    metadata_ref,
+                    _module: PhantomData,
+                }
+            }
+        }
+    );
+}
+
+di_node!(
+    /// A source file.
+    DIFile
+);
+di_node!(
+    /// A compilation unit, which is the root scope of a module's debug info.
+    DICompileUnit
+);
+di_node!(
+    /// A function's debug info.
+    DISubprogram
+);
+di_node!(
+    /// A scope nested within a function, such as the body of a loop.
+    DILexicalBlock
+);
+di_node!(
+    /// A source location of an instruction.
+    DILocation
+);
+di_node!(
+    /// A local variable or function parameter.
+    DILocalVariable
+);
+di_node!(
+    /// An expression which describes how to compute a variable's value from its storage.
+    DIExpression
+);
+di_node!(
+    /// A type descriptor, such as a basic, pointer, struct, member or array type.
+    DIType
+);
+di_node!(
+    /// A function type descriptor.
+    DISubroutineType
+);
+di_node!(
+    /// Any scope debug info may be nested within.
+    DIScope
+);
+
+/// Implemented by all debug info nodes which may be used as a scope.
+pub trait AsDIScope<'m> {
+    /// Gets this node as a generic `DIScope`.
+    fn as_debug_info_scope(&self) -> DIScope<'m>;
+}
+
+macro_rules! impl_as_di_scope {
+    ($($name:ident),*) => (
+        $(
+            impl<'m> AsDIScope<'m> for $name<'m> {
+                fn as_debug_info_scope(&self) -> DIScope<'m> {
+                    DIScope::new(self.metadata_ref)
+                }
+            }
+        )*
+    );
+}
+
+impl_as_di_scope!(DIScope, DIFile, DICompileUnit, DISubprogram, DILexicalBlock, DIType, DISubroutineType);
+
+impl<'m> DILocation<'m> {
+    /// Gets the line number of this location.
+    pub fn get_line(&self) -> u32 {
+        unsafe {
+            LLVMDILocationGetLine(self.metadata_ref)
+        }
+    }
+
+    /// Gets the column number of this location.
+    pub fn get_column(&self) -> u32 {
+        unsafe {
+            LLVMDILocationGetColumn(self.metadata_ref)
+        }
+    }
+
+    /// Gets the scope this location is within.
+    pub fn get_scope(&self) -> DIScope<'m> {
+        let scope = unsafe {
+            LLVMDILocationGetScope(self.metadata_ref)
+        };
+
+        DIScope::new(scope)
+    }
+}
+
+impl<'m> DIType<'m> {
+    /// Gets the size of this type in bits.
+    pub fn get_size_in_bits(&self) -> u64 {
+        unsafe {
+            LLVMDITypeGetSizeInBits(self.metadata_ref)
+        }
+    }
+
+    /// Gets the alignment of this type in bits.
+    pub fn get_align_in_bits(&self) -> u32 {
+        unsafe {
+            LLVMDITypeGetAlignInBits(self.metadata_ref)
+        }
+    }
+
+    /// Gets the offset of this type in bits, which is only meaningful for member types.
+    pub fn get_offset_in_bits(&self) -> u64 {
+        unsafe {
+            LLVMDITypeGetOffsetInBits(self.metadata_ref)
+        }
+    }
+}
+
+/// Builds the debug info of a `Module`. It must be finalized, with `finalize`, before
+/// the module is verified or compiled. It borrows the `Module`, so neither it nor the
+/// debug info nodes it creates can outlive the `Module`.
+#[derive(Debug)]
+pub struct DebugInfoBuilder<'m> {
+    builder: LLVMDIBuilderRef,
+    // The builder refers to its module until it is disposed, and the nodes it creates are
+    // owned by the module's context
+    _module: PhantomData<&'m Module>,
+}
+
+impl<'m> DebugInfoBuilder<'m> {
+    pub(crate) fn new(builder: LLVMDIBuilderRef, _module: &'m Module) -> Self {
+        assert!(!builder.is_null());
+
+        DebugInfoBuilder {
+            builder,
+            _module: PhantomData,
+        }
+    }
+
+    /// Creates the compile unit for the module. A module should only have one.
+    #[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+    pub fn create_compile_unit(
+        &self,
+        language: DWARFSourceLanguage,
+        file: DIFile<'m>,
+        producer: &str,
+        is_optimized: bool,
+        flags: &str,
+        runtime_ver: u32,
+        split_name: &str,
+        kind: DWARFEmissionKind,
+        dwo_id: u32,
+        split_debug_inlining: bool,
+        debug_info_for_profiling: bool,
+    ) -> DICompileUnit<'m> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateCompileUnit(
+                self.builder,
+                language.as_llvm_enum(),
+                file.metadata_ref,
+                producer.as_ptr() as *const i8,
+                producer.len(),
+                is_optimized as i32,
+                flags.as_ptr() as *const i8,
+                flags.len(),
+                runtime_ver,
+                split_name.as_ptr() as *const i8,
+                split_name.len(),
+                kind.as_llvm_enum(),
+                dwo_id,
+                split_debug_inlining as i32,
+                debug_info_for_profiling as i32,
+            )
+        };
+
+        DICompileUnit::new(metadata_ref)
+    }
+
+    /// Creates a file descriptor from a file name and the directory it is in.
+    pub fn create_file(&self, filename: &str, directory: &str) -> DIFile<'m> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateFile(self.builder, filename.as_ptr() as *const i8, filename.len(), directory.as_ptr() as *const i8, directory.len())
+        };
+
+        DIFile::new(metadata_ref)
+    }
+
+    /// Creates the debug info of a function, which should then be attached to it with
+    /// `FunctionValue::set_subprogram`. `linkage_name` is the function's symbol name,
+    /// if it differs from `name`.
+    #[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+    pub fn create_function<S: AsDIScope<'m>>(
+        &self,
+        scope: S,
+        name: &str,
+        linkage_name: Option<&str>,
+        file: DIFile<'m>,
+        line_no: u32,
+        ditype: DISubroutineType<'m>,
+        is_local_to_unit: bool,
+        is_definition: bool,
+        scope_line: u32,
+        flags: DIFlags,
+        is_optimized: bool,
+    ) -> DISubprogram<'m> {
+        let linkage_name = linkage_name.unwrap_or(name);
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateFunction(
+                self.builder,
+                scope.as_debug_info_scope().metadata_ref,
+                name.as_ptr() as *const i8,
+                name.len(),
+                linkage_name.as_ptr() as *const i8,
+                linkage_name.len(),
+                file.metadata_ref,
+                line_no,
+                ditype.metadata_ref,
+                is_local_to_unit as i32,
+                is_definition as i32,
+                scope_line,
+                flags.0,
+                is_optimized as i32,
+            )
+        };
+
+        DISubprogram::new(metadata_ref)
+    }
+
+    /// Creates a lexical block nested within `parent_scope`.
+    pub fn create_lexical_block<S: AsDIScope<'m>>(&self, parent_scope: S, file: DIFile<'m>, line: u32, column: u32) -> DILexicalBlock<'m> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateLexicalBlock(self.builder, parent_scope.as_debug_info_scope().metadata_ref, file.metadata_ref, line, column)
+        };
+
+        DILexicalBlock::new(metadata_ref)
+    }
+
+    /// Creates a basic type, such as an integer or a float.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size_in_bits` is 0.
+    pub fn create_basic_type(&self, name: &str, size_in_bits: u64, encoding: DWARFTypeEncoding) -> DIType<'m> {
+        assert!(size_in_bits != 0, "Basic types must have a non zero size");
+
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateBasicType(self.builder, name.as_ptr() as *const i8, name.len(), size_in_bits, encoding as u32)
+        };
+
+        DIType::new(metadata_ref)
+    }
+
+    /// Creates a pointer type pointing to `pointee`.
+    pub fn create_pointer_type(&self, name: &str, pointee: DIType<'m>, size_in_bits: u64, align_in_bits: u32, address_space: AddressSpace) -> DIType<'m> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreatePointerType(self.builder, pointee.metadata_ref, size_in_bits, align_in_bits, address_space as u32, name.as_ptr() as *const i8, name.len())
+        };
+
+        DIType::new(metadata_ref)
+    }
+
+    /// Creates a struct type whose fields are described by `elements`, which are usually
+    /// created with `create_member_type`.
+    #[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+    pub fn create_struct_type<S: AsDIScope<'m>>(
+        &self,
+        scope: S,
+        name: &str,
+        file: DIFile<'m>,
+        line_no: u32,
+        size_in_bits: u64,
+        align_in_bits: u32,
+        flags: DIFlags,
+        derived_from: Option<DIType<'m>>,
+        elements: &[DIType<'m>],
+        runtime_lang: u32,
+        vtable_holder: Option<DIType<'m>>,
+        unique_id: &str,
+    ) -> DIType<'m> {
+        let mut elements: Vec<LLVMMetadataRef> = elements.iter()
+                                                         .map(|ty| ty.metadata_ref)
+                                                         .collect();
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateStructType(
+                self.builder,
+                scope.as_debug_info_scope().metadata_ref,
+                name.as_ptr() as *const i8,
+                name.len(),
+                file.metadata_ref,
+                line_no,
+                size_in_bits,
+                align_in_bits,
+                flags.0,
+                derived_from.map_or(ptr::null_mut(), |ty| ty.metadata_ref),
+                elements.as_mut_ptr(),
+                elements.len() as u32,
+                runtime_lang,
+                vtable_holder.map_or(ptr::null_mut(), |ty| ty.metadata_ref),
+                unique_id.as_ptr() as *const i8,
+                unique_id.len(),
+            )
+        };
+
+        DIType::new(metadata_ref)
+    }
+
+    /// Creates a member of a struct, located `offset_in_bits` from its start.
+    #[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+    pub fn create_member_type<S: AsDIScope<'m>>(
+        &self,
+        scope: S,
+        name: &str,
+        file: DIFile<'m>,
+        line_no: u32,
+        size_in_bits: u64,
+        align_in_bits: u32,
+        offset_in_bits: u64,
+        flags: DIFlags,
+        ty: DIType<'m>,
+    ) -> DIType<'m> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateMemberType(
+                self.builder,
+                scope.as_debug_info_scope().metadata_ref,
+                name.as_ptr() as *const i8,
+                name.len(),
+                file.metadata_ref,
+                line_no,
+                size_in_bits,
+                align_in_bits,
+                offset_in_bits,
+                flags.0,
+                ty.metadata_ref,
+            )
+        };
+
+        DIType::new(metadata_ref)
+    }
+
+    /// Creates an array type of `inner_type` with one dimension per subscript range.
+    pub fn create_array_type(&self, inner_type: DIType<'m>, size_in_bits: u64, align_in_bits: u32, subscripts: &[Range<i64>]) -> DIType<'m> {
+        let mut subscripts: Vec<LLVMMetadataRef> = subscripts.iter()
+                                                             .map(|range| unsafe {
+                                                                 LLVMDIBuilderGetOrCreateSubrange(self.builder, range.start, range.end - range.start)
+                                                             })
+                                                             .collect();
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateArrayType(self.builder, size_in_bits, align_in_bits, inner_type.metadata_ref, subscripts.as_mut_ptr(), subscripts.len() as u32)
+        };
+
+        DIType::new(metadata_ref)
+    }
+
+    /// Creates a function type. A `return_type` of `None` represents `void`.
+    pub fn create_subroutine_type(&self, file: DIFile<'m>, return_type: Option<DIType<'m>>, parameter_types: &[DIType<'m>], flags: DIFlags) -> DISubroutineType<'m> {
+        let mut types: Vec<LLVMMetadataRef> = Vec::with_capacity(parameter_types.len() + 1);
+
+        types.push(return_type.map_or(ptr::null_mut(), |ty| ty.metadata_ref));
+        types.extend(parameter_types.iter().map(|ty| ty.metadata_ref));
+
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateSubroutineType(self.builder, file.metadata_ref, types.as_mut_ptr(), types.len() as u32, flags.0)
+        };
+
+        DISubroutineType::new(metadata_ref)
+    }
+
+    /// Creates a local variable, which can be attached to its storage with `insert_declare_at_end`
+    /// or `insert_declare_before_instruction`.
+    #[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+    pub fn create_auto_variable<S: AsDIScope<'m>>(
+        &self,
+        scope: S,
+        name: &str,
+        file: DIFile<'m>,
+        line_no: u32,
+        ty: DIType<'m>,
+        always_preserve: bool,
+        flags: DIFlags,
+        align_in_bits: u32,
+    ) -> DILocalVariable<'m> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateAutoVariable(
+                self.builder,
+                scope.as_debug_info_scope().metadata_ref,
+                name.as_ptr() as *const i8,
+                name.len(),
+                file.metadata_ref,
+                line_no,
+                ty.metadata_ref,
+                always_preserve as i32,
+                flags.0,
+                align_in_bits,
+            )
+        };
+
+        DILocalVariable::new(metadata_ref)
+    }
+
+    /// Creates a function parameter variable. `arg_no` starts at 1.
+    #[cfg_attr(feature = "cargo-clippy", allow(too_many_arguments))]
+    pub fn create_parameter_variable<S: AsDIScope<'m>>(
+        &self,
+        scope: S,
+        name: &str,
+        arg_no: u32,
+        file: DIFile<'m>,
+        line_no: u32,
+        ty: DIType<'m>,
+        always_preserve: bool,
+        flags: DIFlags,
+    ) -> DILocalVariable<'m> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateParameterVariable(
+                self.builder,
+                scope.as_debug_info_scope().metadata_ref,
+                name.as_ptr() as *const i8,
+                name.len(),
+                arg_no,
+                file.metadata_ref,
+                line_no,
+                ty.metadata_ref,
+                always_preserve as i32,
+                flags.0,
+            )
+        };
+
+        DILocalVariable::new(metadata_ref)
+    }
+
+    /// Creates an expression from a list of DWARF operations. An empty list means a
+    /// variable's value is simply the value of its storage.
+    pub fn create_expression(&self, mut address_operations: Vec<i64>) -> DIExpression<'m> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateExpression(self.builder, address_operations.as_mut_ptr(), address_operations.len())
+        };
+
+        DIExpression::new(metadata_ref)
+    }
+
+    /// Creates a source location within `scope`. `inlined_at` is the location of the call
+    /// this location has been inlined into, if any.
+    pub fn create_debug_location<S: AsDIScope<'m>>(&self, context: &Context, line: u32, column: u32, scope: S, inlined_at: Option<DILocation<'m>>) -> DILocation<'m> {
+        let metadata_ref = unsafe {
+            LLVMDIBuilderCreateDebugLocation(*context.context, line, column, scope.as_debug_info_scope().metadata_ref, inlined_at.map_or(ptr::null_mut(), |loc| loc.metadata_ref))
+        };
+
+        DILocation::new(metadata_ref)
+    }
+
+    /// Inserts a call to `llvm.dbg.declare` at the end of `block`, declaring that `storage`
+    /// holds the variable `var_info`.
+    pub fn insert_declare_at_end(&self, storage: PointerValue, var_info: DILocalVariable<'m>, expr: Option<DIExpression<'m>>, debug_loc: DILocation<'m>, block: &BasicBlock) -> InstructionValue {
+        let expr = expr.unwrap_or_else(|| self.create_expression(vec![]));
+        let value = unsafe {
+            LLVMDIBuilderInsertDeclareAtEnd(self.builder, storage.as_value_ref(), var_info.metadata_ref, expr.metadata_ref, debug_loc.metadata_ref, block.basic_block)
+        };
+
+        InstructionValue::new(value)
+    }
+
+    /// Inserts a call to `llvm.dbg.declare` before `instruction`, declaring that `storage`
+    /// holds the variable `var_info`.
+    pub fn insert_declare_before_instruction(&self, storage: PointerValue, var_info: DILocalVariable<'m>, expr: Option<DIExpression<'m>>, debug_loc: DILocation<'m>, instruction: InstructionValue) -> InstructionValue {
+        let expr = expr.unwrap_or_else(|| self.create_expression(vec![]));
+        let value = unsafe {
+            LLVMDIBuilderInsertDeclareBefore(self.builder, storage.as_value_ref(), var_info.metadata_ref, expr.metadata_ref, debug_loc.metadata_ref, instruction.as_value_ref())
+        };
+
+        InstructionValue::new(value)
+    }
+
+    /// Inserts a call to `llvm.dbg.value` before `instruction`, declaring that the variable
+    /// `var_info` has the value `value` from that point onwards.
+    pub fn insert_dbg_value_before<BV: BasicValue>(&self, value: BV, var_info: DILocalVariable<'m>, expr: Option<DIExpression<'m>>, debug_loc: DILocation<'m>, instruction: InstructionValue) -> InstructionValue {
+        let expr = expr.unwrap_or_else(|| self.create_expression(vec![]));
+        let value = unsafe {
+            LLVMDIBuilderInsertDbgValueBefore(self.builder, value.as_value_ref(), var_info.metadata_ref, expr.metadata_ref, debug_loc.metadata_ref, instruction.as_value_ref())
+        };
+
+        InstructionValue::new(value)
+    }
+
+    /// Resolves all of the debug info created by this builder. This must be called before
+    /// the module is verified or compiled.
+    pub fn finalize(&self) {
+        unsafe {
+            LLVMDIBuilderFinalize(self.builder)
+        }
+    }
+}
+
+impl<'m> Drop for DebugInfoBuilder<'m> {
+    fn drop(&mut self) {
+        unsafe {
+            LLVMDisposeDIBuilder(self.builder)
+        }
+    }
+}
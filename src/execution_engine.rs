@@ -1,10 +1,14 @@
 use libc::c_int;
+use llvm_sys::core::LLVMGetTypeContext;
 use llvm_sys::execution_engine::{LLVMGetExecutionEngineTargetData, LLVMExecutionEngineRef, LLVMRunFunction, LLVMRunFunctionAsMain, LLVMDisposeExecutionEngine, LLVMGetFunctionAddress, LLVMAddModule, LLVMFindFunction, LLVMLinkInMCJIT, LLVMLinkInInterpreter, LLVMRemoveModule, LLVMGenericValueRef, LLVMFreeMachineCodeForFunction, LLVMAddGlobalMapping, LLVMRunStaticConstructors, LLVMRunStaticDestructors};
 
-use context::Context;
+use context::{Context, ContextRef};
 use module::Module;
+#[llvm_versions(7.0 => latest)]
+use orc::OrcEngineInner;
 use support::LLVMString;
 use targets::TargetData;
+use types::{AnyType, AnyTypeEnum, AsTypeRef, FunctionType, RustType};
 use values::{AnyValue, AsValueRef, FunctionValue, GenericValue};
 
 use std::error::Error;
@@ -20,6 +24,7 @@ static EE_INNER_PANIC: &str = "ExecutionEngineInner should exist until Drop";
 pub enum FunctionLookupError {
     JITNotEnabled,
     FunctionNotFound, // 404!
+    SignatureMismatch,
 }
 
 impl Error for FunctionLookupError {
@@ -39,6 +44,7 @@ impl FunctionLookupError {
         match self {
             FunctionLookupError::JITNotEnabled => "ExecutionEngine does not have JIT functionality enabled",
             FunctionLookupError::FunctionNotFound => "Function not found in ExecutionEngine",
+            FunctionLookupError::SignatureMismatch => "Function's type does not match the requested signature",
         }
     }
 }
@@ -339,11 +345,61 @@ impl ExecutionEngine {
         let execution_engine = self.execution_engine.as_ref().expect(EE_INNER_PANIC);
 
         Ok(JitFunction {
-            _execution_engine: execution_engine.clone(),
+            _owner: JitOwner::ExecutionEngine(execution_engine.clone()),
             inner: transmute_copy(&address),
         })
     }
 
+    /// Loads a function from the execution engine like `get_function`, but first checks
+    /// that the Rust signature `F` matches the function's LLVM `FunctionType`, returning
+    /// `FunctionLookupError::SignatureMismatch` if it does not.
+    ///
+    /// Each parameter and the return type must implement [`JitType`], which is implemented
+    /// for `()` and every `RustType`, including structs deriving `RustStruct` behind a pointer.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::execution_engine::FunctionLookupError;
+    /// use inkwell::OptimizationLevel;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("test");
+    /// let builder = context.create_builder();
+    /// let i64_type = context.i64_type();
+    /// let fn_value = module.add_function("double", i64_type.fn_type(&[i64_type.into()], false), None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let x = fn_value.get_first_param().unwrap().into_int_value();
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_return(Some(&builder.build_int_add(x, x, "double")));
+    ///
+    /// let ee = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    ///
+    /// assert_eq!(ee.get_checked_function::<unsafe extern "C" fn(i32) -> i64>("double").unwrap_err(), FunctionLookupError::SignatureMismatch);
+    ///
+    /// let double = ee.get_checked_function::<unsafe extern "C" fn(i64) -> i64>("double").unwrap();
+    ///
+    /// assert_eq!(unsafe { double.call(21) }, 42);
+    /// ```
+    ///
+    /// [`JitType`]: trait.JitType.html
+    pub fn get_checked_function<F>(&self, fn_name: &str) -> Result<JitFunction<F>, FunctionLookupError>
+    where
+        F: JitSignature,
+    {
+        let function = self.get_function_value(fn_name)?;
+
+        if !F::matches_fn_type(function.get_type()) {
+            return Err(FunctionLookupError::SignatureMismatch);
+        }
+
+        unsafe {
+            self.get_function(fn_name)
+        }
+    }
+
     // REVIEW: Not sure if an EE's target data can change.. if so we might want to update the value
     // when making this call
     pub fn get_target_data(&self) -> &TargetData {
@@ -465,14 +521,35 @@ impl Deref for ExecEngineInner {
     }
 }
 
+/// Whichever JIT a `JitFunction` was compiled by. It is never read, only held
+/// on to so the JIT's code isn't freed while it can still be called.
+#[allow(dead_code)]
+#[derive(Clone)]
+enum JitOwner {
+    ExecutionEngine(ExecEngineInner),
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9",
+                  feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
+    Orc(Rc<OrcEngineInner>),
+}
+
 /// A wrapper around a function pointer which ensures the function being pointed
 /// to doesn't accidentally outlive its execution engine.
 #[derive(Clone)]
 pub struct JitFunction<F> {
-    _execution_engine: ExecEngineInner,
+    _owner: JitOwner,
     inner: F,
 }
 
+impl<F> JitFunction<F> {
+    #[llvm_versions(7.0 => latest)]
+    pub(crate) fn from_orc(orc_engine: Rc<OrcEngineInner>, inner: F) -> Self {
+        JitFunction {
+            _owner: JitOwner::Orc(orc_engine),
+            inner,
+        }
+    }
+}
+
 impl<F> Debug for JitFunction<F> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         f.debug_tuple("JitFunction")
@@ -490,10 +567,107 @@ mod private {
     ///
     /// See https://rust-lang-nursery.github.io/api-guidelines/future-proofing.html
     pub trait SealedUnsafeFunctionPointer: Copy {}
+
+    /// A sealed trait which ensures `JitType` is only implemented for `()` and `RustType`s.
+    pub trait SealedJitType {}
 }
 
 impl<F: private::SealedUnsafeFunctionPointer> UnsafeFunctionPointer for F {}
 
+/// A Rust type which can be passed to or returned from JIT compiled code. It is implemented for `()`,
+/// which matches `void`, and for every `RustType`, which matches the LLVM type given by `RustType::basic_type`.
+/// Structs are compared field by field, so a struct deriving `RustStruct` also matches a named LLVM struct
+/// with the same fields.
+///
+/// LLVM doesn't lower structs and arrays passed by value to the C ABI, so `get_checked_function` only
+/// accepts them behind a pointer.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::context::Context;
+/// use inkwell::execution_engine::JitType;
+/// use inkwell::types::{AnyType, RustStruct};
+///
+/// #[derive(RustStruct)]
+/// #[repr(C)]
+/// struct Point {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// let context = Context::create();
+/// let f64_type = context.f64_type();
+/// let point_type = context.opaque_struct_type("Point");
+///
+/// point_type.set_body(&[f64_type.into(), f64_type.into()], false);
+///
+/// assert!(Point::is_llvm_type(point_type.as_any_type_enum()));
+/// assert!(!bool::is_llvm_type(context.bool_type().as_any_type_enum()));
+/// ```
+pub trait JitType: private::SealedJitType {
+    /// Determines whether `type_` is passed the same way as this Rust type.
+    fn is_llvm_type(type_: AnyTypeEnum) -> bool;
+}
+
+impl private::SealedJitType for () {}
+
+impl JitType for () {
+    fn is_llvm_type(type_: AnyTypeEnum) -> bool {
+        type_.is_void_type()
+    }
+}
+
+impl<T: RustType> private::SealedJitType for T {}
+
+impl<T: RustType> JitType for T {
+    fn is_llvm_type(type_: AnyTypeEnum) -> bool {
+        if type_.is_void_type() || type_.is_function_type() {
+            return false;
+        }
+
+        let context = unsafe {
+            LLVMGetTypeContext(type_.as_type_ref())
+        };
+        let context = ContextRef::new(Context::new(Rc::new(context)));
+
+        has_same_layout(T::basic_type(&context).as_any_type_enum(), type_)
+    }
+}
+
+// Named structs are only equal to themselves, so structs are compared field by field instead
+fn has_same_layout(expected: AnyTypeEnum, type_: AnyTypeEnum) -> bool {
+    match (expected, type_) {
+        (AnyTypeEnum::PointerType(expected), AnyTypeEnum::PointerType(ptr_type)) => {
+            expected.get_address_space() == ptr_type.get_address_space() &&
+                has_same_layout(expected.get_element_type(), ptr_type.get_element_type())
+        },
+        (AnyTypeEnum::ArrayType(expected), AnyTypeEnum::ArrayType(array_type)) => {
+            expected.len() == array_type.len() &&
+                has_same_layout(expected.get_element_type().as_any_type_enum(), array_type.get_element_type().as_any_type_enum())
+        },
+        (AnyTypeEnum::StructType(expected), AnyTypeEnum::StructType(struct_type)) => {
+            !struct_type.is_opaque() && expected.is_packed() == struct_type.is_packed() &&
+                expected.count_fields() == struct_type.count_fields() &&
+                expected.get_field_types().iter().zip(struct_type.get_field_types().iter())
+                    .all(|(expected, field_type)| has_same_layout(expected.as_any_type_enum(), field_type.as_any_type_enum()))
+        },
+        (expected, type_) => expected == type_,
+    }
+}
+
+// Aggregates by value aren't lowered to the C ABI (ie sret or split into registers)
+fn is_passed_by_value(type_: AnyTypeEnum) -> bool {
+    !type_.is_struct_type() && !type_.is_array_type()
+}
+
+/// An `UnsafeFunctionPointer` whose parameter and return types all implement `JitType`,
+/// so that it can be checked against a `FunctionType`.
+pub trait JitSignature: UnsafeFunctionPointer {
+    /// Determines whether this signature may be used to call a function of type `fn_type`.
+    fn matches_fn_type(fn_type: FunctionType) -> bool;
+}
+
 macro_rules! impl_unsafe_fn {
     (@recurse $first:ident $( , $rest:ident )*) => {
         impl_unsafe_fn!($( $rest ),*);
@@ -501,9 +675,34 @@ macro_rules! impl_unsafe_fn {
 
     (@recurse) => {};
 
+    (@count) => { 0 };
+
+    (@count $first:ident $( $rest:ident )*) => { 1 + impl_unsafe_fn!(@count $( $rest )*) };
+
     ($( $param:ident ),*) => {
         impl<Output, $( $param ),*> private::SealedUnsafeFunctionPointer for unsafe extern "C" fn($( $param ),*) -> Output {}
 
+        impl<Output: JitType, $( $param: JitType ),*> JitSignature for unsafe extern "C" fn($( $param ),*) -> Output {
+            #[allow(unused_mut, unused_variables)]
+            fn matches_fn_type(fn_type: FunctionType) -> bool {
+                let return_type = match fn_type.get_return_type() {
+                    Some(return_type) => return_type.as_any_type_enum(),
+                    None => fn_type.get_context().void_type().as_any_type_enum(),
+                };
+                let mut param_types = fn_type.get_param_types().into_iter();
+
+                !fn_type.is_var_arg() &&
+                    is_passed_by_value(return_type) && Output::is_llvm_type(return_type) &&
+                    fn_type.count_param_types() == impl_unsafe_fn!(@count $( $param )*) &&
+                    $( param_types.next().map_or(false, |param_type| {
+                        let param_type = param_type.as_any_type_enum();
+
+                        is_passed_by_value(param_type) && $param::is_llvm_type(param_type)
+                    }) && )*
+                    true
+            }
+        }
+
         impl<Output, $( $param ),*> JitFunction<unsafe extern "C" fn($( $param ),*) -> Output> {
             /// This method allows you to call the underlying function while making
             /// sure that the backing storage is not dropped too early and
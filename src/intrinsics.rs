@@ -0,0 +1,365 @@
+//! Intrinsics are functions built into LLVM, which are declared like any other
+//! function but are instead implemented by the code generator.
+//!
+//! Overloaded intrinsics are generic over one or more types, which must be
+//! mangled into the name they are declared with (ie `llvm.ctpop.i32`).
+//! An `Intrinsic` takes care of that mangling as well as of building its `FunctionType`.
+//!
+//! The signatures known here are those of LLVM 7, such as `llvm.memcpy` taking its
+//! alignments as parameter attributes, so this module is only available from LLVM 7 onwards.
+
+use llvm_sys::core::{LLVMAddFunction, LLVMGetModuleContext, LLVMGetNamedFunction, LLVMGetTypeKind, LLVMGetIntTypeWidth, LLVMGetPointerAddressSpace, LLVMGetElementType, LLVMGetArrayLength, LLVMGetVectorSize, LLVMGetStructName, LLVMCountStructElementTypes, LLVMStructGetTypeAtIndex, LLVMGetReturnType, LLVMCountParamTypes, LLVMGetParamTypes, LLVMIsFunctionVarArg};
+use llvm_sys::prelude::{LLVMModuleRef, LLVMTypeRef};
+use llvm_sys::LLVMTypeKind;
+
+use AddressSpace;
+use context::{Context, ContextRef};
+use module::Module;
+use types::{AsTypeRef, BasicType, BasicTypeEnum, FunctionType};
+use values::FunctionValue;
+
+use std::ffi::{CStr, CString};
+use std::rc::Rc;
+
+/// The kind of type an overloaded intrinsic accepts in one of its overloaded positions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum OverloadKind {
+    /// An integer or a vector of integers.
+    Int,
+    /// A float or a vector of floats.
+    Float,
+    /// A pointer in any address space.
+    Pointer,
+}
+
+impl OverloadKind {
+    fn accepts(&self, type_: &BasicTypeEnum) -> bool {
+        match (self, type_) {
+            (OverloadKind::Int, BasicTypeEnum::IntType(_)) => true,
+            (OverloadKind::Int, BasicTypeEnum::VectorType(vec_type)) => vec_type.get_element_type().is_int_type(),
+            (OverloadKind::Float, BasicTypeEnum::FloatType(_)) => true,
+            (OverloadKind::Float, BasicTypeEnum::VectorType(vec_type)) => vec_type.get_element_type().is_float_type(),
+            (OverloadKind::Pointer, BasicTypeEnum::PointerType(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+/// A return or parameter type of an intrinsic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IntrinsicType {
+    Void,
+    Int(u32),
+    I8Ptr,
+    /// The overload type at the given index.
+    Overloaded(usize),
+    /// `{ T, i1 }` where `T` is the overload type at the given index.
+    WithOverflow(usize),
+}
+
+impl IntrinsicType {
+    fn get_basic_type(&self, context: &Context, overload_types: &[BasicTypeEnum]) -> Option<BasicTypeEnum> {
+        let type_ = match *self {
+            IntrinsicType::Void => return None,
+            IntrinsicType::Int(bits) => context.custom_width_int_type(bits).as_basic_type_enum(),
+            IntrinsicType::I8Ptr => context.i8_type().ptr_type(AddressSpace::Generic).as_basic_type_enum(),
+            IntrinsicType::Overloaded(index) => overload_types[index],
+            IntrinsicType::WithOverflow(index) => {
+                context.struct_type(&[overload_types[index], context.bool_type().into()], false).as_basic_type_enum()
+            },
+        };
+
+        Some(type_)
+    }
+}
+
+/// An LLVM intrinsic function, such as `llvm.memcpy` or `llvm.sadd.with.overflow`.
+///
+/// Only a commonly used subset of LLVM's intrinsics is known to `Intrinsic::find`.
+/// Others may still be declared manually with `Module::add_function`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Intrinsic {
+    name: &'static str,
+    overloads: &'static [OverloadKind],
+    return_type: IntrinsicType,
+    param_types: &'static [IntrinsicType],
+}
+
+macro_rules! intrinsics {
+    ($($name:expr => ($($overload:ident),*) ($($param:expr),*) -> $ret:expr;)*) => {
+        static INTRINSICS: &[Intrinsic] = &[
+            $(
+                Intrinsic {
+                    name: $name,
+                    overloads: &[$(OverloadKind::$overload),*],
+                    return_type: $ret,
+                    param_types: &[$($param),*],
+                },
+            )*
+        ];
+    };
+}
+
+use self::IntrinsicType::{Int, I8Ptr, Overloaded as T, Void, WithOverflow};
+
+intrinsics! {
+    "llvm.memcpy" => (Pointer, Pointer, Int) (T(0), T(1), T(2), Int(1)) -> Void;
+    "llvm.memmove" => (Pointer, Pointer, Int) (T(0), T(1), T(2), Int(1)) -> Void;
+    "llvm.memset" => (Pointer, Int) (T(0), Int(8), T(1), Int(1)) -> Void;
+
+    "llvm.ctpop" => (Int) (T(0)) -> T(0);
+    "llvm.ctlz" => (Int) (T(0), Int(1)) -> T(0);
+    "llvm.cttz" => (Int) (T(0), Int(1)) -> T(0);
+    "llvm.bswap" => (Int) (T(0)) -> T(0);
+    "llvm.bitreverse" => (Int) (T(0)) -> T(0);
+    "llvm.fshl" => (Int) (T(0), T(0), T(0)) -> T(0);
+    "llvm.fshr" => (Int) (T(0), T(0), T(0)) -> T(0);
+
+    "llvm.sadd.with.overflow" => (Int) (T(0), T(0)) -> WithOverflow(0);
+    "llvm.uadd.with.overflow" => (Int) (T(0), T(0)) -> WithOverflow(0);
+    "llvm.ssub.with.overflow" => (Int) (T(0), T(0)) -> WithOverflow(0);
+    "llvm.usub.with.overflow" => (Int) (T(0), T(0)) -> WithOverflow(0);
+    "llvm.smul.with.overflow" => (Int) (T(0), T(0)) -> WithOverflow(0);
+    "llvm.umul.with.overflow" => (Int) (T(0), T(0)) -> WithOverflow(0);
+
+    "llvm.sqrt" => (Float) (T(0)) -> T(0);
+    "llvm.sin" => (Float) (T(0)) -> T(0);
+    "llvm.cos" => (Float) (T(0)) -> T(0);
+    "llvm.exp" => (Float) (T(0)) -> T(0);
+    "llvm.exp2" => (Float) (T(0)) -> T(0);
+    "llvm.log" => (Float) (T(0)) -> T(0);
+    "llvm.log10" => (Float) (T(0)) -> T(0);
+    "llvm.log2" => (Float) (T(0)) -> T(0);
+    "llvm.fabs" => (Float) (T(0)) -> T(0);
+    "llvm.floor" => (Float) (T(0)) -> T(0);
+    "llvm.ceil" => (Float) (T(0)) -> T(0);
+    "llvm.trunc" => (Float) (T(0)) -> T(0);
+    "llvm.rint" => (Float) (T(0)) -> T(0);
+    "llvm.nearbyint" => (Float) (T(0)) -> T(0);
+    "llvm.round" => (Float) (T(0)) -> T(0);
+    "llvm.canonicalize" => (Float) (T(0)) -> T(0);
+    "llvm.pow" => (Float) (T(0), T(0)) -> T(0);
+    "llvm.powi" => (Float) (T(0), Int(32)) -> T(0);
+    "llvm.minnum" => (Float) (T(0), T(0)) -> T(0);
+    "llvm.maxnum" => (Float) (T(0), T(0)) -> T(0);
+    "llvm.copysign" => (Float) (T(0), T(0)) -> T(0);
+    "llvm.fma" => (Float) (T(0), T(0), T(0)) -> T(0);
+    "llvm.fmuladd" => (Float) (T(0), T(0), T(0)) -> T(0);
+
+    "llvm.expect" => (Int) (T(0), T(0)) -> T(0);
+    "llvm.assume" => () (Int(1)) -> Void;
+    "llvm.lifetime.start" => (Pointer) (Int(64), T(0)) -> Void;
+    "llvm.lifetime.end" => (Pointer) (Int(64), T(0)) -> Void;
+
+    "llvm.trap" => () () -> Void;
+    "llvm.debugtrap" => () () -> Void;
+    "llvm.stacksave" => () () -> I8Ptr;
+    "llvm.stackrestore" => () (I8Ptr) -> Void;
+    "llvm.readcyclecounter" => () () -> Int(64);
+    "llvm.prefetch" => () (I8Ptr, Int(32), Int(32), Int(32)) -> Void;
+    "llvm.va_start" => () (I8Ptr) -> Void;
+    "llvm.va_end" => () (I8Ptr) -> Void;
+    "llvm.va_copy" => () (I8Ptr, I8Ptr) -> Void;
+}
+
+impl Intrinsic {
+    /// Finds an intrinsic by its unmangled name, ie `llvm.ctpop` rather than `llvm.ctpop.i32`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::intrinsics::Intrinsic;
+    ///
+    /// let ctpop = Intrinsic::find("llvm.ctpop").unwrap();
+    ///
+    /// assert!(ctpop.is_overloaded());
+    /// assert!(Intrinsic::find("llvm.ctpop.i32").is_none());
+    /// ```
+    pub fn find(name: &str) -> Option<Self> {
+        INTRINSICS.iter().find(|intrinsic| intrinsic.name == name).cloned()
+    }
+
+    /// Gets the unmangled name of this intrinsic.
+    pub fn get_name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Determines whether this intrinsic is generic over some of its types, which
+    /// must then be provided in order to declare it.
+    pub fn is_overloaded(&self) -> bool {
+        !self.overloads.is_empty()
+    }
+
+    /// Gets the number of overload types this intrinsic needs to be declared with.
+    pub fn count_overload_types(&self) -> u32 {
+        self.overloads.len() as u32
+    }
+
+    fn check_overload_types(&self, overload_types: &[BasicTypeEnum]) -> bool {
+        overload_types.len() == self.overloads.len() &&
+            self.overloads.iter().zip(overload_types).all(|(kind, type_)| kind.accepts(type_))
+    }
+
+    /// Gets the name this intrinsic is declared with for the given overload types,
+    /// or `None` if they are not valid for this intrinsic.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    /// use inkwell::intrinsics::Intrinsic;
+    ///
+    /// let context = Context::create();
+    /// let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    /// let i64_type = context.i64_type();
+    /// let memcpy = Intrinsic::find("llvm.memcpy").unwrap();
+    ///
+    /// assert_eq!(memcpy.get_mangled_name(&[i8_ptr_type.into(), i8_ptr_type.into(), i64_type.into()]).unwrap(), "llvm.memcpy.p0i8.p0i8.i64");
+    /// assert!(memcpy.get_mangled_name(&[i64_type.into()]).is_none());
+    /// ```
+    pub fn get_mangled_name(&self, overload_types: &[BasicTypeEnum]) -> Option<String> {
+        if !self.check_overload_types(overload_types) {
+            return None;
+        }
+
+        let mut name = self.name.to_string();
+
+        for type_ in overload_types {
+            name.push('.');
+            name.push_str(&mangle_type(type_.as_type_ref()));
+        }
+
+        Some(name)
+    }
+
+    /// Gets the `FunctionType` of this intrinsic for the given overload types,
+    /// or `None` if they are not valid for this intrinsic.
+    pub fn get_fn_type(&self, context: &Context, overload_types: &[BasicTypeEnum]) -> Option<FunctionType> {
+        if !self.check_overload_types(overload_types) {
+            return None;
+        }
+
+        let param_types: Vec<BasicTypeEnum> = self.param_types.iter()
+            .map(|param_type| param_type.get_basic_type(context, overload_types).expect("Intrinsic parameters cannot be void"))
+            .collect();
+
+        let fn_type = match self.return_type.get_basic_type(context, overload_types) {
+            Some(return_type) => return_type.fn_type(&param_types, false),
+            None => context.void_type().fn_type(&param_types, false),
+        };
+
+        Some(fn_type)
+    }
+
+    /// Declares this intrinsic in a `Module` for the given overload types, or gets the
+    /// existing declaration. Returns `None` if the overload types are not valid for this intrinsic,
+    /// or if the module already has a function of a different type by the intrinsic's name.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::intrinsics::Intrinsic;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let f64_type = context.f64_type();
+    /// let fma = Intrinsic::find("llvm.fma").unwrap();
+    /// let fma_f64 = fma.get_declaration(&module, &[f64_type.into()]).unwrap();
+    ///
+    /// assert_eq!(fma_f64.get_name().to_str(), Ok("llvm.fma.f64"));
+    /// assert_ne!(fma_f64.get_intrinsic_id(), 0);
+    /// ```
+    pub fn get_declaration(&self, module: &Module, overload_types: &[BasicTypeEnum]) -> Option<FunctionValue> {
+        self.get_declaration_in(module.module.get(), overload_types)
+    }
+
+    // The builder only has a raw reference to the module it is inserting into
+    pub(crate) fn get_declaration_in(&self, module: LLVMModuleRef, overload_types: &[BasicTypeEnum]) -> Option<FunctionValue> {
+        let name = self.get_mangled_name(overload_types)?;
+        let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
+
+        let context = unsafe {
+            ContextRef::new(Context::new(Rc::new(LLVMGetModuleContext(module))))
+        };
+        let fn_type = self.get_fn_type(&context, overload_types)?;
+        let function = unsafe {
+            LLVMGetNamedFunction(module, c_string.as_ptr())
+        };
+
+        // Calls to a function which merely shares the name wouldn't pass verification
+        if !function.is_null() {
+            return FunctionValue::new(function).filter(|function| function.get_type() == fn_type);
+        }
+
+        let function = unsafe {
+            LLVMAddFunction(module, c_string.as_ptr(), fn_type.as_type_ref())
+        };
+
+        FunctionValue::new(function)
+    }
+}
+
+// Mirrors the type mangling LLVM uses for overloaded intrinsic names
+fn mangle_type(type_: LLVMTypeRef) -> String {
+    unsafe {
+        match LLVMGetTypeKind(type_) {
+            LLVMTypeKind::LLVMIntegerTypeKind => format!("i{}", LLVMGetIntTypeWidth(type_)),
+            LLVMTypeKind::LLVMHalfTypeKind => "f16".into(),
+            LLVMTypeKind::LLVMFloatTypeKind => "f32".into(),
+            LLVMTypeKind::LLVMDoubleTypeKind => "f64".into(),
+            LLVMTypeKind::LLVMX86_FP80TypeKind => "f80".into(),
+            LLVMTypeKind::LLVMFP128TypeKind => "f128".into(),
+            LLVMTypeKind::LLVMPPC_FP128TypeKind => "ppcf128".into(),
+            LLVMTypeKind::LLVMX86_MMXTypeKind => "x86mmx".into(),
+            LLVMTypeKind::LLVMVoidTypeKind => "isVoid".into(),
+            LLVMTypeKind::LLVMMetadataTypeKind => "Metadata".into(),
+            LLVMTypeKind::LLVMLabelTypeKind => "label".into(),
+            LLVMTypeKind::LLVMTokenTypeKind => "token".into(),
+            LLVMTypeKind::LLVMPointerTypeKind => {
+                format!("p{}{}", LLVMGetPointerAddressSpace(type_), mangle_type(LLVMGetElementType(type_)))
+            },
+            LLVMTypeKind::LLVMArrayTypeKind => {
+                format!("a{}{}", LLVMGetArrayLength(type_), mangle_type(LLVMGetElementType(type_)))
+            },
+            LLVMTypeKind::LLVMVectorTypeKind => {
+                format!("v{}{}", LLVMGetVectorSize(type_), mangle_type(LLVMGetElementType(type_)))
+            },
+            LLVMTypeKind::LLVMStructTypeKind => {
+                let name = LLVMGetStructName(type_);
+
+                if !name.is_null() && *name != 0 {
+                    return format!("s_{}", CStr::from_ptr(name).to_string_lossy());
+                }
+
+                let mut mangled = String::from("sl_");
+
+                for index in 0..LLVMCountStructElementTypes(type_) {
+                    mangled.push_str(&mangle_type(LLVMStructGetTypeAtIndex(type_, index)));
+                }
+
+                mangled.push('s');
+                mangled
+            },
+            LLVMTypeKind::LLVMFunctionTypeKind => {
+                let mut param_types = vec![::std::ptr::null_mut(); LLVMCountParamTypes(type_) as usize];
+
+                LLVMGetParamTypes(type_, param_types.as_mut_ptr());
+
+                let mut mangled = format!("f_{}", mangle_type(LLVMGetReturnType(type_)));
+
+                for param_type in param_types {
+                    mangled.push_str(&mangle_type(param_type));
+                }
+
+                if LLVMIsFunctionVarArg(type_) == 1 {
+                    mangled.push_str("vararg");
+                }
+
+                mangled.push('f');
+                mangled
+            },
+        }
+    }
+}
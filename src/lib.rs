@@ -16,6 +16,7 @@ extern crate libc;
 extern crate llvm_sys;
 #[macro_use]
 extern crate inkwell_internal_macros;
+extern crate inkwell_derive;
 
 #[macro_use]
 pub mod support;
@@ -32,17 +33,32 @@ pub mod builder;
 #[deny(missing_docs)]
 pub mod context;
 pub mod data_layout;
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9",
+              feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
+pub mod debug_info;
 pub mod execution_engine;
+#[deny(missing_docs)]
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9",
+              feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
+pub mod intrinsics;
+#[deny(missing_docs)]
+#[cfg(feature = "lto")]
+pub mod lto;
 pub mod memory_buffer;
 #[deny(missing_docs)]
 pub mod module;
 pub mod object_file;
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9",
+              feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
+pub mod orc;
 pub mod passes;
 pub mod targets;
 pub mod types;
 pub mod values;
 
-use llvm_sys::{LLVMIntPredicate, LLVMRealPredicate, LLVMVisibility, LLVMThreadLocalMode, LLVMDLLStorageClass, LLVMAtomicOrdering};
+use llvm_sys::{LLVMIntPredicate, LLVMRealPredicate, LLVMVisibility, LLVMThreadLocalMode, LLVMDLLStorageClass, LLVMAtomicOrdering, LLVMAtomicRMWBinOp};
+#[llvm_versions(7.0 => latest)]
+use llvm_sys::LLVMInlineAsmDialect;
 
 // Thanks to kennytm for coming up with assert_unique_features!
 // which ensures that the LLVM feature flags are mutually exclusive
@@ -181,6 +197,34 @@ enum_rename!{
     }
 }
 
+enum_rename!{
+    /// Defines the operation an atomicrmw instruction performs.
+    AtomicRMWBinOp <=> LLVMAtomicRMWBinOp {
+        /// Stores the new value, returning the old one.
+        Xchg <=> LLVMAtomicRMWBinOpXchg,
+        /// Adds to the old value.
+        Add <=> LLVMAtomicRMWBinOpAdd,
+        /// Subtracts from the old value.
+        Sub <=> LLVMAtomicRMWBinOpSub,
+        /// Bitwise ands with the old value.
+        And <=> LLVMAtomicRMWBinOpAnd,
+        /// Bitwise nands with the old value.
+        Nand <=> LLVMAtomicRMWBinOpNand,
+        /// Bitwise ors with the old value.
+        Or <=> LLVMAtomicRMWBinOpOr,
+        /// Bitwise xors with the old value.
+        Xor <=> LLVMAtomicRMWBinOpXor,
+        /// Stores the signed maximum of the old and new values.
+        Max <=> LLVMAtomicRMWBinOpMax,
+        /// Stores the signed minimum of the old and new values.
+        Min <=> LLVMAtomicRMWBinOpMin,
+        /// Stores the unsigned maximum of the old and new values.
+        UMax <=> LLVMAtomicRMWBinOpUMax,
+        /// Stores the unsigned minimum of the old and new values.
+        UMin <=> LLVMAtomicRMWBinOpUMin,
+    }
+}
+
 /// Defines the optimization level used to compile a `Module`.
 ///
 /// # Remarks
@@ -201,6 +245,17 @@ impl Default for OptimizationLevel {
     }
 }
 
+#[llvm_versions(7.0 => latest)]
+enum_rename!{
+    /// Defines which syntax the assembly string of an inline asm value is written in.
+    InlineAsmDialect <=> LLVMInlineAsmDialect {
+        /// AT&T syntax, ie `movl $1, %eax`.
+        ATT <=> LLVMInlineAsmDialectATT,
+        /// Intel syntax, ie `mov eax, 1`.
+        Intel <=> LLVMInlineAsmDialectIntel,
+    }
+}
+
 enum_rename!{
     GlobalVisibility <=> LLVMVisibility {
         Default <=> LLVMDefaultVisibility,
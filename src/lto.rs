@@ -0,0 +1,280 @@
+//! Link time optimization of bitcode via LLVM's libLTO, by merging all of the modules
+//! into one (`LTOCodeGenerator`).
+//!
+//! ThinLTO isn't supported, since it requires bitcode with a module summary, which
+//! inkwell has no way to produce.
+//!
+//! libLTO is a separate shared library from the rest of LLVM, so this module is only
+//! available with the `lto` feature enabled.
+
+use libc::{c_char, size_t};
+use llvm_sys::core::LLVMCreateMemoryBufferWithMemoryRangeCopy;
+use llvm_sys::lto::{lto_code_gen_t, lto_codegen_model, lto_get_error_message, lto_module_create_in_codegen_context, lto_module_create_in_local_context, lto_module_dispose, lto_codegen_create_in_local_context, lto_codegen_dispose, lto_codegen_add_module, lto_codegen_add_must_preserve_symbol, lto_codegen_set_pic_model, lto_codegen_set_cpu, lto_codegen_debug_options, lto_codegen_compile, lto_codegen_write_merged_modules};
+
+use OptimizationLevel;
+use memory_buffer::MemoryBuffer;
+use targets::RelocMode;
+
+use std::error::Error;
+use std::ffi::{CStr, CString};
+use std::fmt::{self, Display, Formatter};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// The lto_* functions are exported by libLTO rather than the libraries llvm-sys links
+#[link(name = "LTO")]
+extern "C" {}
+
+// libLTO reads code generators' options once per process, the first time one writes or compiles
+static LTO_OPTIONS_READ: AtomicBool = AtomicBool::new(false);
+
+/// An error reported by libLTO, ie when given invalid bitcode or failing to generate code.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LTOError {
+    message: String,
+}
+
+impl LTOError {
+    // libLTO keeps the message of the most recent error in a global string
+    fn last() -> Self {
+        let message = unsafe {
+            CStr::from_ptr(lto_get_error_message())
+        };
+
+        LTOError {
+            message: message.to_string_lossy().into_owned(),
+        }
+    }
+
+    /// Gets the message libLTO gave for this error.
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Error for LTOError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+
+    fn cause(&self) -> Option<&dyn Error> {
+        None
+    }
+}
+
+impl Display for LTOError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "LTOError({})", self.message)
+    }
+}
+
+fn reloc_mode_to_model(reloc_mode: RelocMode) -> lto_codegen_model {
+    match reloc_mode {
+        RelocMode::Default => lto_codegen_model::LTO_CODEGEN_PIC_MODEL_DEFAULT,
+        RelocMode::Static => lto_codegen_model::LTO_CODEGEN_PIC_MODEL_STATIC,
+        RelocMode::PIC => lto_codegen_model::LTO_CODEGEN_PIC_MODEL_DYNAMIC,
+        RelocMode::DynamicNoPic => lto_codegen_model::LTO_CODEGEN_PIC_MODEL_DYNAMIC_NO_PIC,
+    }
+}
+
+fn optimization_level_option(optimization_level: OptimizationLevel) -> &'static CStr {
+    let option: &'static [u8] = match optimization_level {
+        OptimizationLevel::None => b"-O0\0",
+        OptimizationLevel::Less => b"-O1\0",
+        OptimizationLevel::Default => b"-O2\0",
+        OptimizationLevel::Aggressive => b"-O3\0",
+    };
+
+    CStr::from_bytes_with_nul(option).expect("Option should be null terminated")
+}
+
+// Parses bitcode into a throwaway lto_module_t in order to validate it
+fn check_bitcode(bitcode: &[u8], name: &CStr) -> Result<(), LTOError> {
+    let module = unsafe {
+        lto_module_create_in_local_context(bitcode.as_ptr() as *const _, bitcode.len(), name.as_ptr())
+    };
+
+    if module.is_null() {
+        return Err(LTOError::last());
+    }
+
+    unsafe {
+        lto_module_dispose(module);
+    }
+
+    Ok(())
+}
+
+fn copy_to_memory_buffer(start: *const c_char, size: size_t, name: &str) -> MemoryBuffer {
+    let name = CString::new(name).expect("Conversion to CString failed unexpectedly");
+    let memory_buffer = unsafe {
+        LLVMCreateMemoryBufferWithMemoryRangeCopy(start, size, name.as_ptr())
+    };
+
+    MemoryBuffer::new(memory_buffer)
+}
+
+/// Performs full LTO: all of the bitcode added to it is linked into a single module, which
+/// is then internalized, optimized and compiled to a single native object file.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::context::Context;
+/// use inkwell::lto::LTOCodeGenerator;
+///
+/// let context = Context::create();
+/// let module = context.create_module("my_module");
+/// let fn_type = context.i32_type().fn_type(&[], false);
+/// let function = module.add_function("main", fn_type, None);
+/// let builder = context.create_builder();
+///
+/// builder.position_at_end(&function.append_basic_block("entry"));
+/// builder.build_return(Some(&context.i32_type().const_int(0, false)));
+///
+/// let code_generator = LTOCodeGenerator::create();
+///
+/// code_generator.add_bitcode(&module.write_bitcode_to_memory(), "my_module").unwrap();
+/// code_generator.add_must_preserve_symbol("main");
+///
+/// let object = code_generator.compile().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct LTOCodeGenerator {
+    code_gen: lto_code_gen_t,
+}
+
+impl LTOCodeGenerator {
+    /// Creates a new `LTOCodeGenerator` with its own LLVM context.
+    pub fn create() -> Self {
+        let code_gen = unsafe {
+            lto_codegen_create_in_local_context()
+        };
+
+        assert!(!code_gen.is_null());
+
+        LTOCodeGenerator {
+            code_gen,
+        }
+    }
+
+    /// Parses a `MemoryBuffer` of bitcode and links it into the merged module. The name is
+    /// used to identify it in error messages.
+    pub fn add_bitcode(&self, buffer: &MemoryBuffer, name: &str) -> Result<(), LTOError> {
+        let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
+        let bitcode = buffer.as_slice();
+
+        // lto_module_create_in_codegen_context doesn't check whether parsing succeeded, returning
+        // an invalid pointer when it didn't, so the bitcode has to be validated beforehand
+        check_bitcode(bitcode, &c_string)?;
+
+        let module = unsafe {
+            lto_module_create_in_codegen_context(bitcode.as_ptr() as *const _, bitcode.len(), c_string.as_ptr(), self.code_gen)
+        };
+
+        // The module's contents are moved into the merged module, but it must still be disposed of
+        let failed = unsafe {
+            let failed = lto_codegen_add_module(self.code_gen, module);
+
+            lto_module_dispose(module);
+
+            failed
+        };
+
+        if failed != 0 {
+            return Err(LTOError::last());
+        }
+
+        Ok(())
+    }
+
+    /// Prevents a symbol from being internalized or removed, ie because it is the entry
+    /// point or is referenced by native object files the result will be linked with.
+    pub fn add_must_preserve_symbol(&self, name: &str) {
+        let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
+
+        unsafe {
+            lto_codegen_add_must_preserve_symbol(self.code_gen, c_string.as_ptr())
+        }
+    }
+
+    /// Sets the `RelocMode` of the generated code.
+    pub fn set_reloc_mode(&self, reloc_mode: RelocMode) {
+        // Only fails for unknown models
+        unsafe {
+            lto_codegen_set_pic_model(self.code_gen, reloc_mode_to_model(reloc_mode));
+        }
+    }
+
+    /// Sets the CPU to generate code for, ie "x86-64" or "cortex-a53".
+    pub fn set_cpu(&self, cpu: &str) {
+        let c_string = CString::new(cpu).expect("Conversion to CString failed unexpectedly");
+
+        unsafe {
+            lto_codegen_set_cpu(self.code_gen, c_string.as_ptr())
+        }
+    }
+
+    /// Sets the `OptimizationLevel` of both the LTO passes and code generation, which is
+    /// `OptimizationLevel::Default` unless otherwise specified.
+    ///
+    /// libLTO only reads this along with its other options once per process, when the first
+    /// `LTOCodeGenerator` writes or compiles its merged module. Any later ones use
+    /// `OptimizationLevel::Default` regardless, so this returns false once that has happened.
+    pub fn set_optimization_level(&self, optimization_level: OptimizationLevel) -> bool {
+        if LTO_OPTIONS_READ.load(Ordering::SeqCst) {
+            return false;
+        }
+
+        let option = optimization_level_option(optimization_level);
+
+        unsafe {
+            lto_codegen_debug_options(self.code_gen, option.as_ptr())
+        }
+
+        true
+    }
+
+    /// Writes the merged module to a bitcode file, without optimizing it.
+    pub fn write_merged_modules(&self, path: &Path) -> Result<(), LTOError> {
+        let path = path.to_str().expect("Did not find a valid Unicode path string");
+        let c_string = CString::new(path).expect("Conversion to CString failed unexpectedly");
+        LTO_OPTIONS_READ.store(true, Ordering::SeqCst);
+
+        let failed = unsafe {
+            lto_codegen_write_merged_modules(self.code_gen, c_string.as_ptr())
+        };
+
+        if failed != 0 {
+            return Err(LTOError::last());
+        }
+
+        Ok(())
+    }
+
+    /// Optimizes the merged module and compiles it into a native object file.
+    pub fn compile(&self) -> Result<MemoryBuffer, LTOError> {
+        let mut size = 0;
+
+        LTO_OPTIONS_READ.store(true, Ordering::SeqCst);
+
+        let start = unsafe {
+            lto_codegen_compile(self.code_gen, &mut size)
+        };
+
+        if start.is_null() {
+            return Err(LTOError::last());
+        }
+
+        // The object file is owned by the code generator and is freed when compiling again
+        Ok(copy_to_memory_buffer(start as *const c_char, size, "lto.o"))
+    }
+}
+
+impl Drop for LTOCodeGenerator {
+    fn drop(&mut self) {
+        unsafe {
+            lto_codegen_dispose(self.code_gen)
+        }
+    }
+}
@@ -27,12 +27,12 @@ impl MemoryBuffer {
 
     pub fn create_from_file(path: &Path) -> Result<Self, LLVMString> {
         let path = path.to_str().expect("Did not find a valid Unicode path string");
+        let path = CString::new(path).expect("Conversion to CString failed unexpectedly");
         let mut memory_buffer = ptr::null_mut();
         let mut err_string = unsafe { zeroed() };
 
         let return_code = unsafe {
-            // REVIEW: Unclear why this expects *const i8 instead of *const u8
-            LLVMCreateMemoryBufferWithContentsOfFile(path.as_ptr() as *const i8, &mut memory_buffer, &mut err_string)
+            LLVMCreateMemoryBufferWithContentsOfFile(path.as_ptr(), &mut memory_buffer, &mut err_string)
         };
 
         // TODO: Verify 1 is error code (LLVM can be inconsistent)
@@ -117,6 +117,8 @@ impl MemoryBuffer {
     // a double free in valgrind when the MemoryBuffer drops so we are `forget`ting MemoryBuffer here
     // for now until we can confirm this is the correct thing to do
     pub fn create_object_file(self) -> Option<ObjectFile> {
+        // The object file takes ownership of the buffer, so its contents stay valid for as long as it does
+        let (start, size) = (self.as_slice().as_ptr(), self.get_size());
         let object_file = unsafe {
             LLVMCreateObjectFile(self.memory_buffer)
         };
@@ -127,7 +129,7 @@ impl MemoryBuffer {
             return None;
         }
 
-        Some(ObjectFile::new(object_file))
+        Some(ObjectFile::new(object_file, start, size))
     }
 }
 
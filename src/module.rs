@@ -4,11 +4,14 @@ use llvm_sys::analysis::{LLVMVerifyModule, LLVMVerifierFailureAction};
 #[allow(deprecated)]
 use llvm_sys::bit_reader::{LLVMParseBitcode, LLVMParseBitcodeInContext};
 use llvm_sys::bit_writer::{LLVMWriteBitcodeToFile, LLVMWriteBitcodeToMemoryBuffer};
-use llvm_sys::core::{LLVMAddFunction, LLVMAddGlobal, LLVMDumpModule, LLVMGetNamedFunction, LLVMGetTypeByName, LLVMSetDataLayout, LLVMSetTarget, LLVMCloneModule, LLVMDisposeModule, LLVMGetTarget, LLVMModuleCreateWithName, LLVMGetModuleContext, LLVMGetFirstFunction, LLVMGetLastFunction, LLVMAddGlobalInAddressSpace, LLVMPrintModuleToString, LLVMGetNamedMetadataNumOperands, LLVMAddNamedMetadataOperand, LLVMGetNamedMetadataOperands, LLVMGetFirstGlobal, LLVMGetLastGlobal, LLVMGetNamedGlobal, LLVMPrintModuleToFile};
+use llvm_sys::core::{LLVMAddFunction, LLVMAddGlobal, LLVMDumpModule, LLVMGetNamedFunction, LLVMGetTypeByName, LLVMSetDataLayout, LLVMSetTarget, LLVMCloneModule, LLVMDisposeModule, LLVMGetTarget, LLVMModuleCreateWithName, LLVMGetModuleContext, LLVMGetFirstFunction, LLVMGetLastFunction, LLVMAddGlobalInAddressSpace, LLVMPrintModuleToString, LLVMGetNamedMetadataNumOperands, LLVMAddNamedMetadataOperand, LLVMGetNamedMetadataOperands, LLVMGetFirstGlobal, LLVMGetLastGlobal, LLVMGetNamedGlobal, LLVMPrintModuleToFile, LLVMGetNextFunction, LLVMGetPreviousFunction, LLVMGetNextGlobal, LLVMGetPreviousGlobal};
 #[llvm_versions(3.9 => latest)]
 use llvm_sys::core::{LLVMGetModuleIdentifier, LLVMSetModuleIdentifier};
 #[llvm_versions(7.0 => latest)]
-use llvm_sys::core::{LLVMGetModuleFlag, LLVMAddModuleFlag};
+use llvm_sys::core::{LLVMGetModuleFlag, LLVMAddModuleFlag, LLVMGetFirstGlobalAlias, LLVMGetLastGlobalAlias, LLVMGetNextGlobalAlias, LLVMGetPreviousGlobalAlias, LLVMAddAlias, LLVMGetNamedGlobalAlias};
+use llvm_sys::core::{LLVMDeleteFunction, LLVMDeleteGlobal, LLVMGetElementType, LLVMGetFirstUse, LLVMGetGC, LLVMGetNextUse, LLVMGetUser, LLVMGetInitializer, LLVMGetLinkage, LLVMGetPointerAddressSpace, LLVMGetNumOperands, LLVMGetOperand, LLVMGetUndef, LLVMIsAConstant, LLVMIsAFunction, LLVMIsAGlobalValue, LLVMIsAGlobalVariable, LLVMIsDeclaration, LLVMReplaceAllUsesWith, LLVMSetGC, LLVMSetLinkage, LLVMTypeOf};
+#[llvm_versions(3.7 => latest)]
+use llvm_sys::core::{LLVMGetPersonalityFn, LLVMHasPersonalityFn};
 use llvm_sys::execution_engine::{LLVMCreateInterpreterForModule, LLVMCreateJITCompilerForModule, LLVMCreateExecutionEngineForModule};
 use llvm_sys::prelude::{LLVMValueRef, LLVMModuleRef};
 use llvm_sys::LLVMLinkage;
@@ -16,6 +19,9 @@ use llvm_sys::LLVMLinkage;
 use llvm_sys::LLVMModuleFlagBehavior;
 
 use std::cell::{Cell, RefCell, Ref};
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
 #[llvm_versions(3.9 => latest)]
 use std::ffi::CStr;
 use std::ffi::CString;
@@ -24,21 +30,23 @@ use std::mem::{forget, zeroed};
 use std::path::Path;
 use std::ptr;
 use std::rc::Rc;
-use std::slice::from_raw_parts;
+use std::slice::{self, from_raw_parts};
 
-use {AddressSpace, OptimizationLevel};
+use {AddressSpace, GlobalVisibility, OptimizationLevel};
 #[llvm_versions(7.0 => latest)]
 use comdat::Comdat;
+#[llvm_versions(7.0 => latest)]
+use debug_info::DebugInfoBuilder;
 use context::{Context, ContextRef};
 use data_layout::DataLayout;
 use execution_engine::ExecutionEngine;
 use memory_buffer::MemoryBuffer;
-use support::LLVMString;
+use support::{LLVMString, next_in_list};
 use targets::{Target, InitializationConfig};
 use types::{AsTypeRef, BasicType, FunctionType, BasicTypeEnum};
 use values::{AsValueRef, FunctionValue, GlobalValue, MetadataValue};
 #[llvm_versions(7.0 => latest)]
-use values::BasicValue;
+use values::{BasicValue, GlobalAliasValue, PointerValue};
 
 enum_rename!{
     /// This enum defines how to link a global variable or function in a module. The variant documenation is
@@ -150,6 +158,24 @@ impl Module {
         }
     }
 
+    /// Gives up ownership of the underlying LLVM module without disposing it,
+    /// returning it along with the context it was created in.
+    #[llvm_versions(7.0 => latest)]
+    pub(crate) fn into_raw(self) -> (LLVMModuleRef, Option<Context>) {
+        let module = self.module.get();
+        let context = unsafe { ptr::read(&self.non_global_context) };
+
+        // Fields other than the context must still be dropped normally
+        unsafe {
+            drop(ptr::read(&self.data_layout));
+            drop(ptr::read(&self.owned_by_ee));
+        }
+
+        forget(self);
+
+        (module, context)
+    }
+
     /// Creates a named `Module`. Will be automatically assigned the global context.
     ///
     /// To use your own `Context`, see [inkwell::context::create_module()](../context/struct.Context.html#method.create_module)
@@ -289,6 +315,33 @@ impl Module {
         FunctionValue::new(function)
     }
 
+    /// Gets an iterator over the `FunctionValue`s defined in this `Module`, in order.
+    /// The iterator is double-ended, so it may also be walked from the last function.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let first_fn = module.add_function("first", fn_type, None);
+    /// let second_fn = module.add_function("second", fn_type, None);
+    ///
+    /// assert_eq!(module.get_functions().collect::<Vec<_>>(), vec![first_fn, second_fn]);
+    /// assert_eq!(module.get_functions().rev().next(), Some(second_fn));
+    /// ```
+    pub fn get_functions(&self) -> FunctionIter {
+        let (front, back) = unsafe {
+            (LLVMGetFirstFunction(self.module.get()), LLVMGetLastFunction(self.module.get()))
+        };
+
+        FunctionIter {
+            front,
+            back,
+        }
+    }
+
     /// Gets a `FunctionValue` defined in this `Module` by its name.
     ///
     /// # Example
@@ -564,6 +617,70 @@ impl Module {
         GlobalValue::new(value)
     }
 
+    /// Creates a `GlobalAliasValue` named `name` which aliases `aliasee`, typically
+    /// a function or global variable (or a constant expression derived from one).
+    /// The alias has the same type as its aliasee. Returns `None` if `aliasee` isn't
+    /// a constant, as an alias can't refer to instructions or arguments.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("mod");
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let fn_value = module.add_function("my_fn", fn_type, None);
+    /// let alias = module.add_global_alias(fn_value.as_global_value().as_pointer_value(), "my_fn_alias").unwrap();
+    ///
+    /// assert_eq!(alias.get_aliasee(), fn_value.as_global_value().as_pointer_value());
+    /// assert_eq!(module.get_global_alias("my_fn_alias"), Some(alias));
+    /// ```
+    #[llvm_versions(7.0 => latest)]
+    pub fn add_global_alias(&self, aliasee: PointerValue, name: &str) -> Option<GlobalAliasValue> {
+        if !aliasee.is_const() {
+            return None;
+        }
+
+        let c_string = CString::new(name).expect("Conversion to CString failed unexpectedly");
+
+        let value = unsafe {
+            LLVMAddAlias(self.module.get(), aliasee.get_type().as_type_ref(), aliasee.as_value_ref(), c_string.as_ptr())
+        };
+
+        Some(GlobalAliasValue::new(value))
+    }
+
+    /// Gets a named `GlobalAliasValue` in a module.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("mod");
+    /// let global = module.add_global(context.i8_type(), None, "my_global");
+    ///
+    /// assert!(module.get_global_alias("my_alias").is_none());
+    ///
+    /// let alias = module.add_global_alias(global.as_pointer_value(), "my_alias").unwrap();
+    ///
+    /// assert_eq!(module.get_global_alias("my_alias"), Some(alias));
+    /// ```
+    #[llvm_versions(7.0 => latest)]
+    pub fn get_global_alias(&self, name: &str) -> Option<GlobalAliasValue> {
+        let value = unsafe {
+            LLVMGetNamedGlobalAlias(self.module.get(), name.as_ptr() as *const i8, name.len())
+        };
+
+        if value.is_null() {
+            return None;
+        }
+
+        Some(GlobalAliasValue::new(value))
+    }
+
     /// Writes a `Module` to a `Path`.
     ///
     /// # Example
@@ -999,6 +1116,66 @@ impl Module {
         Some(GlobalValue::new(value))
     }
 
+    /// Gets an iterator over the `GlobalValue`s defined in this `Module`, in order.
+    /// The iterator is double-ended, so it may also be walked from the last global.
+    ///
+    /// Aliases are iterated separately by `get_aliases`. There are no iterators over
+    /// ifuncs or named metadata, as llvm-sys 70 doesn't bind `LLVMGetFirstGlobalIFunc`
+    /// or `LLVMGetFirstNamedMetadata`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("mod");
+    /// let i8_type = context.i8_type();
+    /// let global = module.add_global(i8_type, None, "my_global");
+    /// let other_global = module.add_global(i8_type, None, "my_other_global");
+    ///
+    /// assert_eq!(module.get_globals().collect::<Vec<_>>(), vec![global, other_global]);
+    /// assert_eq!(module.get_globals().rev().next(), Some(other_global));
+    /// ```
+    pub fn get_globals(&self) -> GlobalIter {
+        let (front, back) = unsafe {
+            (LLVMGetFirstGlobal(self.module.get()), LLVMGetLastGlobal(self.module.get()))
+        };
+
+        GlobalIter {
+            front,
+            back,
+        }
+    }
+
+    /// Gets an iterator over the `GlobalAliasValue`s defined in this `Module`, in order.
+    /// The iterator is double-ended, so it may also be walked from the last alias.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("mod");
+    /// let global = module.add_global(context.i8_type(), None, "my_global");
+    /// let alias = module.add_global_alias(global.as_pointer_value(), "my_alias").unwrap();
+    /// let other_alias = module.add_global_alias(global.as_pointer_value(), "my_other_alias").unwrap();
+    ///
+    /// assert_eq!(module.get_aliases().collect::<Vec<_>>(), vec![alias, other_alias]);
+    /// ```
+    #[llvm_versions(7.0 => latest)]
+    pub fn get_aliases(&self) -> GlobalAliasIter {
+        let (front, back) = unsafe {
+            (LLVMGetFirstGlobalAlias(self.module.get()), LLVMGetLastGlobalAlias(self.module.get()))
+        };
+
+        GlobalAliasIter {
+            front,
+            back,
+        }
+    }
+
     /// Gets a named `GlobalValue` in a module.
     ///
     /// # Example
@@ -1150,6 +1327,292 @@ impl Module {
         Self::parse_bitcode_from_buffer_in_context(&buffer, &context)
     }
 
+    /// Parses a `Module` from a file containing textual LLVM IR (ie a `.ll` file), in the global `Context`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::module::Module;
+    /// use std::path::Path;
+    ///
+    /// let path = Path::new("foo/bar.ll");
+    /// let module = Module::parse_ir_from_path(&path);
+    ///
+    /// assert_eq!(module.unwrap().get_context(), Context::get_global());
+    /// ```
+    pub fn parse_ir_from_path<P: AsRef<Path>>(path: P) -> Result<Self, IRParseError> {
+        let buffer = MemoryBuffer::create_from_file(path.as_ref()).map_err(IRParseError::from_file_error)?;
+
+        Self::parse_ir_from_buffer_in(buffer, None)
+    }
+
+    /// Parses a `Module` from a file containing textual LLVM IR (ie a `.ll` file), in the given `Context`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::module::Module;
+    /// use std::path::Path;
+    ///
+    /// let path = Path::new("foo/bar.ll");
+    /// let context = Context::create();
+    /// let module = Module::parse_ir_from_path_in_context(&path, &context);
+    ///
+    /// assert_eq!(*module.unwrap().get_context(), context);
+    /// ```
+    pub fn parse_ir_from_path_in_context<P: AsRef<Path>>(path: P, context: &Context) -> Result<Self, IRParseError> {
+        let buffer = MemoryBuffer::create_from_file(path.as_ref()).map_err(IRParseError::from_file_error)?;
+
+        Self::parse_ir_from_buffer_in(buffer, Some(context))
+    }
+
+    /// Parses a `Module` from a string of textual LLVM IR, in the global `Context`.
+    /// `name` is used as the file name in diagnostics.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::module::Module;
+    ///
+    /// let module = Module::parse_ir_from_str("define i32 @one() {\n  ret i32 1\n}\n", "one.ll").unwrap();
+    ///
+    /// assert!(module.get_function("one").is_some());
+    /// ```
+    pub fn parse_ir_from_str(ir: &str, name: &str) -> Result<Self, IRParseError> {
+        // The lexer expects a null terminator at the end of the buffer, which only the copy guarantees
+        let buffer = MemoryBuffer::create_from_memory_range_copy(ir, name);
+
+        Self::parse_ir_from_buffer_in(buffer, None)
+    }
+
+    /// Parses a `Module` from a string of textual LLVM IR, in the given `Context`.
+    /// `name` is used as the file name in diagnostics.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::module::Module;
+    ///
+    /// let context = Context::create();
+    /// let err = Module::parse_ir_from_str_in_context("define i32 @one() {\n  ret i64 1\n}\n", "one.ll", &context).unwrap_err();
+    ///
+    /// assert_eq!(err.get_line(), Some(2));
+    /// assert_eq!(err.get_column(), Some(7));
+    /// ```
+    pub fn parse_ir_from_str_in_context(ir: &str, name: &str, context: &Context) -> Result<Self, IRParseError> {
+        let buffer = MemoryBuffer::create_from_memory_range_copy(ir, name);
+
+        Self::parse_ir_from_buffer_in(buffer, Some(context))
+    }
+
+    fn parse_ir_from_buffer_in(buffer: MemoryBuffer, context: Option<&Context>) -> Result<Self, IRParseError> {
+        use llvm_sys::core::LLVMGetGlobalContext;
+        use llvm_sys::ir_reader::LLVMParseIRInContext;
+
+        let mut module = ptr::null_mut();
+        let mut err_string = ptr::null_mut();
+
+        let code = unsafe {
+            let raw_context = match context {
+                Some(context) => *context.context,
+                None => LLVMGetGlobalContext(),
+            };
+
+            LLVMParseIRInContext(raw_context, buffer.memory_buffer, &mut module, &mut err_string)
+        };
+
+        // LLVM takes ownership of the buffer, see Context::create_module_from_ir
+        forget(buffer);
+
+        if code != 0 {
+            return Err(IRParseError::new(LLVMString::new(err_string)));
+        }
+
+        Ok(Module::new(module, context))
+    }
+
+    /// Creates a new `Module` in the same `Context` holding only copies of the given functions,
+    /// similar to `llvm-extract`. Everything they transitively reference is carried over: global
+    /// variables keep their initializers, while other functions are turned into declarations.
+    /// Anything unreferenced is removed.
+    ///
+    /// This `Module` is left untouched. Global aliases are always kept along with their aliasees,
+    /// since LLVM's C API provides no way of removing them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any of the functions do not belong to this `Module`, or if this `Module` is not valid.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let builder = context.create_builder();
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let callee = module.add_function("callee", fn_type, None);
+    /// let caller = module.add_function("caller", fn_type, None);
+    /// let unrelated = module.add_function("unrelated", fn_type, None);
+    ///
+    /// for function in &[callee, unrelated] {
+    ///     builder.position_at_end(&function.append_basic_block("entry"));
+    ///     builder.build_return(None);
+    /// }
+    ///
+    /// builder.position_at_end(&caller.append_basic_block("entry"));
+    /// builder.build_call(callee, &[], "call");
+    /// builder.build_return(None);
+    ///
+    /// let extracted = module.extract_functions(&[caller]);
+    ///
+    /// assert!(extracted.get_function("caller").unwrap().get_first_basic_block().is_some());
+    /// assert!(extracted.get_function("callee").unwrap().get_first_basic_block().is_none());
+    /// assert!(extracted.get_function("unrelated").is_none());
+    /// ```
+    pub fn extract_functions(&self, functions: &[FunctionValue]) -> Module {
+        let indices: Vec<usize> = functions.iter().map(|function| {
+            self.get_functions().position(|f| f == *function).expect("FunctionValue does not belong to this Module")
+        }).collect();
+        let module = self.clone();
+        let clone_functions: Vec<LLVMValueRef> = module.get_functions().map(|function| function.as_value_ref()).collect();
+        let roots = indices.iter().map(|&index| clone_functions[index]);
+        let (live, defined) = find_live_globals(&module, roots);
+
+        for &function in &clone_functions {
+            if !live.contains(&function) {
+                delete_global(function);
+            } else if !defined.contains(&function) && unsafe { LLVMIsDeclaration(function) } == 0 {
+                replace_with_declaration(&module, FunctionValue::new(function).unwrap());
+            }
+        }
+
+        let globals: Vec<LLVMValueRef> = module.get_globals().map(|global| global.as_value_ref()).collect();
+
+        for global in globals {
+            if !live.contains(&global) {
+                delete_global(global);
+            }
+        }
+
+        module
+    }
+
+    /// Splits this `Module` into the given number of partitions, similar to LLVM's SplitModule,
+    /// so that they can be compiled in parallel. Each function and global variable definition is
+    /// placed in a single partition, with the others referring to it through declarations.
+    /// Linking the object files produced from all of the partitions gives the same result as
+    /// compiling this `Module` as a whole.
+    ///
+    /// Every partition is created in a new `Context` of its own so that no LLVM state is shared
+    /// between them, and can be wrapped in a `ThreadSafeModule` to be moved to another thread.
+    /// This `Module` is left untouched.
+    ///
+    /// In order for definitions to be referenced across partitions, internal and private
+    /// globals are given external linkage with hidden visibility. Global aliases, everything
+    /// they refer to and appending globals, such as `llvm.global_ctors`, are placed in the first
+    /// partition, while members of a comdat are kept together.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `partitions` is zero, or if this `Module` is not valid.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let builder = context.create_builder();
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    ///
+    /// for name in &["a", "b", "c", "d"] {
+    ///     let function = module.add_function(name, fn_type, None);
+    ///
+    ///     builder.position_at_end(&function.append_basic_block("entry"));
+    ///     builder.build_return(None);
+    /// }
+    ///
+    /// let partitions = module.split_into(2);
+    ///
+    /// assert_eq!(partitions.len(), 2);
+    ///
+    /// for partition in &partitions {
+    ///     let definitions = partition.get_functions().filter(|f| f.get_first_basic_block().is_some());
+    ///
+    ///     assert_eq!(definitions.count(), 2);
+    /// }
+    /// ```
+    pub fn split_into(&self, partitions: u32) -> Vec<Module> {
+        assert!(partitions > 0, "A Module must be split into at least one partition");
+
+        let module = self.clone();
+
+        externalize_local_globals(&module);
+
+        let (function_owners, global_owners) = assign_partitions(&module, partitions);
+        let bitcode = module.write_bitcode_to_memory();
+
+        (0..partitions).map(|partition| {
+            let context = Context::create();
+            let module = Module::parse_bitcode_from_buffer_in_context(&bitcode, &context).expect("Module bitcode should be valid");
+            let functions: Vec<FunctionValue> = module.get_functions().collect();
+            let globals: Vec<GlobalValue> = module.get_globals().collect();
+            let mut declarations = Vec::new();
+
+            for (function, owner) in functions.into_iter().zip(&function_owners) {
+                match *owner {
+                    Some(owner) if owner != partition => declarations.push(replace_with_declaration(&module, function).as_value_ref()),
+                    _ => {},
+                }
+            }
+
+            for (global, owner) in globals.into_iter().zip(&global_owners) {
+                let value = global.as_pointer_value().as_value_ref();
+
+                match *owner {
+                    // Appending globals can't be declared, and would be duplicated when linking otherwise
+                    Some(owner) if owner != partition && unsafe { LLVMGetLinkage(value) } == LLVMLinkage::LLVMAppendingLinkage => unsafe { LLVMDeleteGlobal(value) },
+                    Some(owner) if owner != partition => declarations.push(replace_global_with_declaration(&module, global).as_pointer_value().as_value_ref()),
+                    _ => {},
+                }
+            }
+
+            #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
+            {
+                let aliases: Vec<GlobalAliasValue> = module.get_aliases().collect();
+
+                if partition != 0 && !aliases.is_empty() {
+                    let placeholder = module.add_global(context.i8_type(), None, "");
+
+                    unsafe {
+                        LLVMSetLinkage(placeholder.as_pointer_value().as_value_ref(), LLVMLinkage::LLVMPrivateLinkage);
+                    }
+
+                    placeholder.set_initializer(&context.i8_type().const_zero());
+
+                    for alias in aliases {
+                        declarations.push(replace_alias_with_declaration(&module, alias, placeholder).as_pointer_value().as_value_ref());
+                    }
+                }
+            }
+
+            // Definitions from other partitions only need to be declared where they are used
+            for declaration in declarations {
+                if !is_used(declaration) {
+                    delete_global(declaration);
+                }
+            }
+
+            module
+        }).collect()
+    }
+
     /// Gets the name of this `Module`.
     ///
     /// # Example
@@ -1302,13 +1765,18 @@ impl Module {
             let char_ptr_ptr = &mut char_ptr as *mut *mut i8 as *mut *mut c_void as *mut c_void;
 
             // Newer LLVM versions don't use an out ptr anymore which was really straightforward...
-            // Here we assign an error handler to extract the error message, if any, for us.
-            context.set_diagnostic_handler(get_error_str_diagnostic_handler, char_ptr_ptr);
+            // Here we assign an error handler to extract the error message, if any, for us, and
+            // put back whichever handler was set before (ie via Context::set_diagnostic_handler) afterwards.
+            let (previous_handler, previous_void_ptr) = context.get_raw_diagnostic_handler();
+
+            context.set_raw_diagnostic_handler(Some(get_error_str_diagnostic_handler), char_ptr_ptr);
 
             let code = unsafe {
                 LLVMLinkModules2(self.module.get(), other.module.get())
             };
 
+            context.set_raw_diagnostic_handler(previous_handler, previous_void_ptr);
+
             forget(other);
 
             if code == 1 {
@@ -1335,6 +1803,60 @@ impl Module {
         Comdat::new(comdat_ptr)
     }
 
+    /// Creates a `DebugInfoBuilder` which emits debug info into this `Module`. If
+    /// `allow_unresolved` is false, finalizing the builder will fail on any cycles in the
+    /// debug info which have not been resolved.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::debug_info::{DWARFEmissionKind, DWARFSourceLanguage};
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let di_builder = module.create_debug_info_builder(true);
+    /// let file = di_builder.create_file("my_mod.src", "/src");
+    /// let compile_unit = di_builder.create_compile_unit(DWARFSourceLanguage::C, file, "my_compiler", false, "", 0, "", DWARFEmissionKind::Full, 0, false, false);
+    ///
+    /// di_builder.finalize();
+    /// ```
+    #[llvm_versions(7.0 => latest)]
+    pub fn create_debug_info_builder(&self, allow_unresolved: bool) -> DebugInfoBuilder<'_> {
+        use llvm_sys::debuginfo::{LLVMCreateDIBuilder, LLVMCreateDIBuilderDisallowUnresolved};
+
+        let builder = unsafe {
+            if allow_unresolved {
+                LLVMCreateDIBuilder(self.module.get())
+            } else {
+                LLVMCreateDIBuilderDisallowUnresolved(self.module.get())
+            }
+        };
+
+        DebugInfoBuilder::new(builder, self)
+    }
+
+    /// Gets the version of the debug metadata in this `Module`, as set by its
+    /// "Debug Info Version" flag, or 0 if there is none.
+    #[llvm_versions(7.0 => latest)]
+    pub fn get_debug_metadata_version(&self) -> u32 {
+        use llvm_sys::debuginfo::LLVMGetModuleDebugMetadataVersion;
+
+        unsafe {
+            LLVMGetModuleDebugMetadataVersion(self.module.get())
+        }
+    }
+
+    /// Removes all debug info from this `Module`, returning whether any was removed.
+    #[llvm_versions(7.0 => latest)]
+    pub fn strip_debug_info(&self) -> bool {
+        use llvm_sys::debuginfo::LLVMStripModuleDebugInfo;
+
+        unsafe {
+            LLVMStripModuleDebugInfo(self.module.get()) == 1
+        }
+    }
+
     /// Gets the `MetadataValue` flag associated with the key in this module, if any.
     /// If a `BasicValue` was used to create this flag, it will be wrapped in a `MetadataValue`
     /// when returned from this function.
@@ -1418,6 +1940,613 @@ impl Drop for Module {
     }
 }
 
+// Walks everything reachable from the given functions, returning the set of live global values and
+// the subset of live functions whose bodies are needed. Aliases are always live, and their aliasees
+// must stay definitions.
+fn find_live_globals<I: Iterator<Item=LLVMValueRef>>(module: &Module, roots: I) -> (HashSet<LLVMValueRef>, HashSet<LLVMValueRef>) {
+    let mut live = HashSet::new();
+    let mut defined = HashSet::new();
+    let mut visited_constants = HashSet::new();
+    let mut worklist = Vec::new();
+
+    for root in roots {
+        live.insert(root);
+        defined.insert(root);
+        worklist.push(root);
+    }
+
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
+    {
+        for alias in module.get_aliases() {
+            let mut aliased = Vec::new();
+
+            live.insert(alias.as_value_ref());
+            find_referenced_globals(alias.get_aliasee().as_value_ref(), &mut HashSet::new(), &mut aliased);
+
+            for value in aliased {
+                if unsafe { !LLVMIsAFunction(value).is_null() } && defined.insert(value) {
+                    live.insert(value);
+                    worklist.push(value);
+                }
+            }
+        }
+    }
+    #[cfg(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0"))]
+    let _ = module;
+
+    while let Some(value) = worklist.pop() {
+        let mut referenced = Vec::new();
+
+        if unsafe { !LLVMIsAFunction(value).is_null() } {
+            if !defined.contains(&value) {
+                continue;
+            }
+
+            let function = FunctionValue::new(value).expect("Function should not be null");
+
+            #[cfg(not(feature = "llvm3-6"))]
+            {
+                if unsafe { LLVMHasPersonalityFn(value) } == 1 {
+                    find_referenced_globals(unsafe { LLVMGetPersonalityFn(value) }, &mut visited_constants, &mut referenced);
+                }
+            }
+
+            for basic_block in function.get_basic_blocks_iter() {
+                for instruction in basic_block.get_instructions() {
+                    for index in 0..unsafe { LLVMGetNumOperands(instruction.as_value_ref()) } {
+                        let operand = unsafe { LLVMGetOperand(instruction.as_value_ref(), index as u32) };
+
+                        find_referenced_globals(operand, &mut visited_constants, &mut referenced);
+                    }
+                }
+            }
+        } else if unsafe { !LLVMIsAGlobalVariable(value).is_null() } {
+            let initializer = unsafe { LLVMGetInitializer(value) };
+
+            find_referenced_globals(initializer, &mut visited_constants, &mut referenced);
+        }
+
+        for global in referenced {
+            if live.insert(global) {
+                worklist.push(global);
+            }
+        }
+    }
+
+    (live, defined)
+}
+
+// Collects the global values used by a constant, looking through constant expressions and aggregates.
+// Anything which isn't a constant (ie an instruction, metadata or null) is ignored.
+fn find_referenced_globals(value: LLVMValueRef, visited: &mut HashSet<LLVMValueRef>, globals: &mut Vec<LLVMValueRef>) {
+    if value.is_null() || unsafe { LLVMIsAConstant(value).is_null() } || !visited.insert(value) {
+        return;
+    }
+
+    if unsafe { !LLVMIsAGlobalValue(value).is_null() } {
+        globals.push(value);
+
+        return;
+    }
+
+    for index in 0..unsafe { LLVMGetNumOperands(value) } {
+        let operand = unsafe { LLVMGetOperand(value, index as u32) };
+
+        find_referenced_globals(operand, visited, globals);
+    }
+}
+
+// Removes a function or global variable, replacing any remaining uses (ie by other dead code) with undef
+fn delete_global(value: LLVMValueRef) {
+    unsafe {
+        if !LLVMGetFirstUse(value).is_null() {
+            LLVMReplaceAllUsesWith(value, LLVMGetUndef(LLVMTypeOf(value)));
+        }
+
+        if LLVMIsAFunction(value).is_null() {
+            LLVMDeleteGlobal(value);
+        } else {
+            LLVMDeleteFunction(value);
+        }
+    }
+}
+
+// Swaps a function definition for a declaration with the same name, type and attributes. This is
+// simpler than stripping its body in place, which would also require removing its personality,
+// comdat and metadata.
+fn replace_with_declaration(module: &Module, function: FunctionValue) -> FunctionValue {
+    let name = function.get_name().to_owned();
+    let declaration = module.add_function("", function.get_type(), None);
+
+    declaration.set_call_conventions(function.get_call_conventions());
+
+    unsafe {
+        let gc = LLVMGetGC(function.as_value_ref());
+
+        if !gc.is_null() {
+            LLVMSetGC(declaration.as_value_ref(), gc);
+        }
+    }
+
+    declaration.as_global_value().set_visibility(function.as_global_value().get_visibility());
+    declaration.as_global_value().set_dll_storage_class(function.as_global_value().get_dll_storage_class());
+
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8")))]
+    {
+        use llvm_sys::LLVMAttributeFunctionIndex;
+        use llvm_sys::core::{LLVMGetAttributesAtIndex, LLVMAddAttributeAtIndex};
+
+        let param_indices = 0..=function.count_params();
+
+        for index in param_indices.chain(Some(LLVMAttributeFunctionIndex)) {
+            let mut attributes = vec![ptr::null_mut(); function.count_attributes(index) as usize];
+
+            unsafe {
+                LLVMGetAttributesAtIndex(function.as_value_ref(), index, attributes.as_mut_ptr());
+
+                for attribute in attributes {
+                    LLVMAddAttributeAtIndex(declaration.as_value_ref(), index, attribute);
+                }
+            }
+        }
+    }
+
+    unsafe {
+        LLVMReplaceAllUsesWith(function.as_value_ref(), declaration.as_value_ref());
+        LLVMDeleteFunction(function.as_value_ref());
+    }
+
+    set_raw_name(declaration.as_value_ref(), &name);
+
+    declaration
+}
+
+// Names needn't be valid UTF-8, so a replacement's name is set from the bytes of the original's
+#[allow(deprecated)]
+fn set_raw_name(value: LLVMValueRef, name: &CStr) {
+    use llvm_sys::core::LLVMSetValueName;
+
+    unsafe {
+        LLVMSetValueName(value, name.as_ptr())
+    }
+}
+
+// Swaps a global variable definition for a declaration with the same name, type and properties
+// needed to access it correctly, such as its address space and thread local mode.
+fn replace_global_with_declaration(module: &Module, global: GlobalValue) -> GlobalValue {
+    let pointer = global.as_pointer_value();
+    let name = pointer.get_name().to_owned();
+    let address_space = unsafe { LLVMGetPointerAddressSpace(LLVMTypeOf(pointer.as_value_ref())) };
+    let declaration = unsafe {
+        GlobalValue::new(LLVMAddGlobalInAddressSpace(module.module.get(), LLVMGetElementType(LLVMTypeOf(pointer.as_value_ref())), b"\0".as_ptr() as *const _, address_space))
+    };
+
+    declaration.set_constant(global.is_constant());
+    declaration.set_thread_local_mode(global.get_thread_local_mode());
+    declaration.set_visibility(global.get_visibility());
+    declaration.set_dll_storage_class(global.get_dll_storage_class());
+    declaration.set_alignment(global.get_alignment());
+
+    unsafe {
+        LLVMReplaceAllUsesWith(pointer.as_value_ref(), declaration.as_pointer_value().as_value_ref());
+        LLVMDeleteGlobal(pointer.as_value_ref());
+    }
+
+    set_raw_name(declaration.as_value_ref(), &name);
+
+    declaration
+}
+
+// Gives internal and private globals hidden, external linkage so that they can be referenced from
+// other partitions, as LLVM's SplitModule does.
+fn externalize_local_globals(module: &Module) {
+    let mut values: Vec<LLVMValueRef> = module.get_functions().map(|function| function.as_value_ref()).collect();
+
+    values.extend(module.get_globals().map(|global| global.as_pointer_value().as_value_ref()));
+
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
+    values.extend(module.get_aliases().map(|alias| alias.as_value_ref()));
+
+    for value in values {
+        match unsafe { LLVMGetLinkage(value) } {
+            LLVMLinkage::LLVMInternalLinkage | LLVMLinkage::LLVMPrivateLinkage => {},
+            _ => continue,
+        }
+
+        let global = GlobalValue::new(value);
+
+        unsafe {
+            LLVMSetLinkage(value, LLVMLinkage::LLVMExternalLinkage);
+        }
+
+        global.set_visibility(GlobalVisibility::Hidden);
+
+        if global.as_pointer_value().get_name().to_bytes().is_empty() {
+            global.as_pointer_value().set_name("__llvmsplit_unnamed");
+        }
+    }
+}
+
+// Assigns each function and global variable definition to a partition, returning the partitions of
+// the functions and globals in module order (None for declarations). Members of a comdat are kept
+// together, while everything aliases refer to and appending globals (ie llvm.global_ctors) are
+// placed in the first partition. Otherwise, the largest definitions are placed first, each into
+// whichever partition has the fewest instructions so far.
+fn assign_partitions(module: &Module, partitions: u32) -> (Vec<Option<u32>>, Vec<Option<u32>>) {
+    let functions: Vec<FunctionValue> = module.get_functions().collect();
+    let globals: Vec<GlobalValue> = module.get_globals().collect();
+    let mut pinned: HashSet<LLVMValueRef> = HashSet::new();
+
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
+    {
+        let mut aliased = Vec::new();
+
+        for alias in module.get_aliases() {
+            find_referenced_globals(alias.get_aliasee().as_value_ref(), &mut HashSet::new(), &mut aliased);
+        }
+
+        pinned.extend(aliased);
+    }
+
+    let values = functions.iter().map(|function| {
+        let size = function.get_basic_blocks_iter().map(|block| block.get_instructions().count()).sum();
+
+        (function.as_global_value(), size)
+    });
+    let values = values.chain(globals.iter().map(|&global| (global, 1)));
+
+    // Each group holds its total size, whether it is pinned to the first partition and its members
+    let mut groups: Vec<(usize, bool, Vec<usize>)> = Vec::new();
+    #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
+    let mut comdat_groups = ::std::collections::HashMap::new();
+    let mut owners = vec![None; functions.len() + globals.len()];
+
+    for (index, (global, size)) in values.enumerate() {
+        if global.is_declaration() {
+            continue;
+        }
+
+        let value = global.as_pointer_value().as_value_ref();
+        let is_pinned = pinned.contains(&value) || unsafe { LLVMGetLinkage(value) } == LLVMLinkage::LLVMAppendingLinkage;
+        #[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
+        let group = global.get_comdat().map(|comdat| *comdat_groups.entry(comdat.0).or_insert_with(|| {
+            groups.push((0, false, Vec::new()));
+
+            groups.len() - 1
+        }));
+        #[cfg(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0"))]
+        let group = None;
+        let group = group.unwrap_or_else(|| {
+            groups.push((0, false, Vec::new()));
+
+            groups.len() - 1
+        });
+
+        groups[group].0 += size;
+        groups[group].1 |= is_pinned;
+        groups[group].2.push(index);
+    }
+
+    // Pinned groups go first so that they are always placed in the first partition
+    groups.sort_by(|a, b| b.1.cmp(&a.1).then(b.0.cmp(&a.0)));
+
+    let mut sizes = vec![0; partitions as usize];
+
+    for (size, is_pinned, members) in groups {
+        let partition = if is_pinned {
+            0
+        } else {
+            (0..partitions as usize).min_by_key(|&partition| sizes[partition]).expect("There should be at least one partition")
+        };
+
+        sizes[partition] += size;
+
+        for member in members {
+            owners[member] = Some(partition as u32);
+        }
+    }
+
+    let global_owners = owners.split_off(functions.len());
+
+    (owners, global_owners)
+}
+
+// Turns an alias into a declaration of the symbol it names, for partitions which don't define it.
+// As the C API can't remove aliases, the original is renamed and made private. It must still
+// point to a definition, so it is redirected to a placeholder global.
+#[llvm_versions(7.0 => latest)]
+fn replace_alias_with_declaration(module: &Module, alias: GlobalAliasValue, placeholder: GlobalValue) -> GlobalValue {
+    use llvm_sys::LLVMTypeKind;
+    use llvm_sys::core::{LLVMConstPointerCast, LLVMGetTypeKind};
+
+    let name = alias.get_name().to_owned();
+    let alias_type = unsafe { LLVMTypeOf(alias.as_value_ref()) };
+    let value_type = unsafe { LLVMGetElementType(alias_type) };
+    let declaration = unsafe {
+        if LLVMGetTypeKind(value_type) == LLVMTypeKind::LLVMFunctionTypeKind {
+            LLVMAddFunction(module.module.get(), b"\0".as_ptr() as *const _, value_type)
+        } else {
+            LLVMAddGlobalInAddressSpace(module.module.get(), value_type, b"\0".as_ptr() as *const _, LLVMGetPointerAddressSpace(alias_type))
+        }
+    };
+    let declaration = GlobalValue::new(declaration);
+
+    declaration.set_visibility(alias.get_visibility());
+    declaration.set_dll_storage_class(alias.as_global_value().get_dll_storage_class());
+
+    unsafe {
+        LLVMReplaceAllUsesWith(alias.as_value_ref(), declaration.as_pointer_value().as_value_ref());
+    }
+
+    alias.as_pointer_value().set_name("");
+    alias.set_linkage(Linkage::Private);
+    alias.set_visibility(GlobalVisibility::Default);
+
+    let aliasee = unsafe { LLVMConstPointerCast(placeholder.as_pointer_value().as_value_ref(), alias_type) };
+
+    alias.set_aliasee(PointerValue::new(aliasee));
+    set_raw_name(declaration.as_value_ref(), &name);
+
+    declaration
+}
+
+// Whether a value is used by anything other than constants which are themselves unused. Those can
+// outlive the globals which used them, ie when an initializer is dropped along with its global.
+fn is_used(value: LLVMValueRef) -> bool {
+    let mut use_ = unsafe { LLVMGetFirstUse(value) };
+
+    while !use_.is_null() {
+        let user = unsafe { LLVMGetUser(use_) };
+        let is_constant = unsafe { !LLVMIsAConstant(user).is_null() && LLVMIsAGlobalValue(user).is_null() };
+
+        if !is_constant || is_used(user) {
+            return true;
+        }
+
+        use_ = unsafe { LLVMGetNextUse(use_) };
+    }
+
+    false
+}
+
+/// A `Module` which is the sole owner of its `Context`, so that it can be moved to another
+/// thread, ie to be compiled on a worker pool. This is the case for each of the partitions
+/// returned by `Module::split_into`. On the receiving thread, `into_inner` gives the `Module` back.
+///
+/// As with `ThreadSafeContext`, types, values and builders don't hold on to the `Module`'s
+/// `Context`, which is why `ThreadSafeModule::new` is unsafe.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::context::Context;
+/// use inkwell::module::ThreadSafeModule;
+///
+/// let context = Context::create();
+/// let module = context.create_module("my_module");
+///
+/// drop(context);
+///
+/// let thread_safe_module = unsafe { ThreadSafeModule::new(module).unwrap() };
+///
+/// let handle = std::thread::spawn(move || {
+///     let module = thread_safe_module.into_inner();
+///
+///     module.get_name().to_str().unwrap().to_owned()
+/// });
+///
+/// assert_eq!(handle.join().unwrap(), "my_module");
+/// ```
+#[derive(Debug)]
+pub struct ThreadSafeModule {
+    module: Module,
+}
+
+// See ThreadSafeContext, of which this is the single Module case without a separate Context handle
+unsafe impl Send for ThreadSafeModule {}
+
+impl ThreadSafeModule {
+    /// Wraps a `Module` which holds the only remaining reference to its `Context`. Otherwise,
+    /// ie if the `Context` is still alive elsewhere, is shared with other `Module`s, is the
+    /// global `Context` or the `Module` is owned by an `ExecutionEngine`, it is returned as is.
+    ///
+    /// # Safety
+    ///
+    /// As with `ThreadSafeContext::new`, no types, values, basic blocks, `Builder`s or
+    /// `ContextRef`s from the `Module`'s `Context` may be used on the current thread afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::module::ThreadSafeModule;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    ///
+    /// // The context is still alive
+    /// let module = unsafe { ThreadSafeModule::new(module).unwrap_err() };
+    ///
+    /// drop(context);
+    ///
+    /// assert!(unsafe { ThreadSafeModule::new(module) }.is_ok());
+    /// ```
+    pub unsafe fn new(module: Module) -> Result<Self, Module> {
+        let is_only_owner = match module.non_global_context {
+            Some(ref context) => context.is_only_owned_by(slice::from_ref(&module), 0),
+            None => false,
+        };
+
+        if !is_only_owner {
+            return Err(module);
+        }
+
+        Ok(ThreadSafeModule {
+            module,
+        })
+    }
+
+    /// Gets back the `Module`, ie once it has been moved to another thread.
+    pub fn into_inner(self) -> Module {
+        self.module
+    }
+}
+
+/// An error produced while parsing textual LLVM IR, ie by `Module::parse_ir_from_path`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct IRParseError {
+    diagnostic: LLVMString,
+    line: Option<u32>,
+    column: Option<u32>,
+}
+
+impl IRParseError {
+    // LLVM prints its SMDiagnostic as "<buffer name>:<line>:<column>: error: <message>", followed
+    // by the offending source line and a caret when a location is known
+    fn new(diagnostic: LLVMString) -> Self {
+        let location = {
+            let diagnostic = diagnostic.to_string_lossy();
+            let first_line = diagnostic.lines().next().unwrap_or("");
+
+            first_line.find(": error: ").and_then(|index| {
+                let mut parts = first_line[..index].rsplitn(3, ':');
+                let column = parts.next()?.parse().ok()?;
+                let line = parts.next()?.parse().ok()?;
+
+                Some((line, column))
+            })
+        };
+
+        IRParseError {
+            diagnostic,
+            line: location.map(|(line, _)| line),
+            column: location.map(|(_, column)| column),
+        }
+    }
+
+    fn from_file_error(diagnostic: LLVMString) -> Self {
+        IRParseError {
+            diagnostic,
+            line: None,
+            column: None,
+        }
+    }
+
+    /// Gets the line, starting at 1, the error was found on. This is `None` if
+    /// there is no location, ie when the file could not be read.
+    pub fn get_line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// Gets the column, starting at 1, the error was found at. This is `None` if
+    /// there is no location, ie when the file could not be read.
+    pub fn get_column(&self) -> Option<u32> {
+        self.column
+    }
+
+    /// Gets the error message without its location prefix or the source line it refers to.
+    pub fn get_message(&self) -> &str {
+        let diagnostic = self.diagnostic.to_str().unwrap_or("IRParseError with invalid unicode");
+        let first_line = diagnostic.lines().next().unwrap_or("");
+
+        match first_line.find(": error: ") {
+            Some(index) => &first_line[index + ": error: ".len()..],
+            None => first_line,
+        }
+    }
+
+    /// Gets the full diagnostic as printed by LLVM, including its location
+    /// and the source line it refers to.
+    pub fn get_diagnostic(&self) -> &LLVMString {
+        &self.diagnostic
+    }
+}
+
+impl Error for IRParseError {
+    fn description(&self) -> &str {
+        self.get_message()
+    }
+
+    fn cause(&self) -> Option<&dyn Error> {
+        None
+    }
+}
+
+impl Display for IRParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "IRParseError({}:{}: {})", line, column, self.get_message()),
+            _ => write!(f, "IRParseError({})", self.get_message()),
+        }
+    }
+}
+
+/// An iterator over the `FunctionValue`s of a `Module`, created by `Module::get_functions`.
+#[derive(Debug)]
+pub struct FunctionIter {
+    front: LLVMValueRef,
+    back: LLVMValueRef,
+}
+
+impl Iterator for FunctionIter {
+    type Item = FunctionValue;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_in_list(&mut self.front, &mut self.back, LLVMGetNextFunction).and_then(FunctionValue::new)
+    }
+}
+
+impl DoubleEndedIterator for FunctionIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        next_in_list(&mut self.back, &mut self.front, LLVMGetPreviousFunction).and_then(FunctionValue::new)
+    }
+}
+
+/// An iterator over the `GlobalValue`s of a `Module`, created by `Module::get_globals`.
+#[derive(Debug)]
+pub struct GlobalIter {
+    front: LLVMValueRef,
+    back: LLVMValueRef,
+}
+
+impl Iterator for GlobalIter {
+    type Item = GlobalValue;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_in_list(&mut self.front, &mut self.back, LLVMGetNextGlobal).map(GlobalValue::new)
+    }
+}
+
+impl DoubleEndedIterator for GlobalIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        next_in_list(&mut self.back, &mut self.front, LLVMGetPreviousGlobal).map(GlobalValue::new)
+    }
+}
+
+/// An iterator over the global aliases of a `Module`, created by `Module::get_aliases`.
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
+#[derive(Debug)]
+pub struct GlobalAliasIter {
+    front: LLVMValueRef,
+    back: LLVMValueRef,
+}
+
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
+impl Iterator for GlobalAliasIter {
+    type Item = GlobalAliasValue;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_in_list(&mut self.front, &mut self.back, LLVMGetNextGlobalAlias).map(GlobalAliasValue::new)
+    }
+}
+
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
+impl DoubleEndedIterator for GlobalAliasIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        next_in_list(&mut self.back, &mut self.front, LLVMGetPreviousGlobalAlias).map(GlobalAliasValue::new)
+    }
+}
+
 #[llvm_versions(7.0 => latest)]
 enum_rename!{
     /// Defines the operational behavior for a module wide flag. This documenation comes directly
@@ -1,105 +1,219 @@
-use llvm_sys::object::{LLVMDisposeObjectFile, LLVMObjectFileRef, LLVMSectionIteratorRef, LLVMGetSections, LLVMDisposeSectionIterator, LLVMSymbolIteratorRef, LLVMIsSectionIteratorAtEnd, LLVMGetSectionName, LLVMDisposeRelocationIterator, LLVMRelocationIteratorRef, LLVMDisposeSymbolIterator, LLVMGetSectionContents, LLVMGetSectionSize, LLVMMoveToNextSection, LLVMGetSectionAddress, LLVMGetSymbolName, LLVMGetSymbolSize, LLVMGetRelocations, LLVMGetSymbolAddress, LLVMGetRelocationOffset, LLVMGetRelocationSymbol, LLVMGetRelocationType, LLVMGetRelocationTypeName, LLVMGetRelocationValueString, LLVMMoveToNextSymbol, LLVMMoveToNextRelocation, LLVMIsSymbolIteratorAtEnd, LLVMIsRelocationIteratorAtEnd, LLVMGetSymbols};
+use llvm_sys::object::{LLVMDisposeObjectFile, LLVMObjectFileRef, LLVMSectionIteratorRef, LLVMGetSections, LLVMDisposeSectionIterator, LLVMSymbolIteratorRef, LLVMIsSectionIteratorAtEnd, LLVMGetSectionName, LLVMDisposeRelocationIterator, LLVMRelocationIteratorRef, LLVMDisposeSymbolIterator, LLVMGetSectionContents, LLVMGetSectionSize, LLVMMoveToNextSection, LLVMGetSectionAddress, LLVMGetSymbolName, LLVMGetSymbolSize, LLVMGetRelocations, LLVMGetSymbolAddress, LLVMGetRelocationOffset, LLVMGetRelocationSymbol, LLVMGetRelocationType, LLVMGetRelocationTypeName, LLVMGetRelocationValueString, LLVMMoveToNextSymbol, LLVMMoveToNextRelocation, LLVMIsSymbolIteratorAtEnd, LLVMIsRelocationIteratorAtEnd, LLVMGetSymbols, LLVMMoveToContainingSection};
+
+use libc::c_char;
+
+use memory_buffer::MemoryBuffer;
+use support::LLVMString;
 
 use std::ffi::CStr;
+use std::path::Path;
+use std::slice;
 
-// REVIEW: Make sure SectionIterator's object_file ptr doesn't outlive ObjectFile
-// REVIEW: This module is very untested
-// TODO: More references to account for lifetimes
-#[derive(Debug)]
-pub struct ObjectFile {
-    object_file: LLVMObjectFileRef
+// Some versions of LLVM give null rather than an empty string for nameless sections and symbols
+fn name_to_c_str<'a>(name: *const c_char) -> &'a CStr {
+    if name.is_null() {
+        return Default::default();
+    }
+
+    unsafe {
+        CStr::from_ptr(name)
+    }
 }
 
-impl ObjectFile {
-    pub(crate) fn new(object_file: LLVMObjectFileRef) -> Self {
-        assert!(!object_file.is_null());
+// The C API has no way of copying an iterator, so each `Section`, `Symbol` and `Relocation` gets
+// its own, moved along to the same position as the one it was found with.
 
-        ObjectFile {
-            object_file
+fn section_at(object_file: &ObjectFile, index: usize) -> Option<LLVMSectionIteratorRef> {
+    let section_iterator = unsafe {
+        LLVMGetSections(object_file.object_file)
+    };
+
+    for _ in 0..index {
+        if object_file.is_section_at_end(section_iterator) {
+            break;
+        }
+
+        unsafe {
+            LLVMMoveToNextSection(section_iterator)
         }
     }
 
-    pub fn get_sections(&self) -> SectionIterator {
-        let section_iterator = unsafe {
-            LLVMGetSections(self.object_file)
-        };
+    if object_file.is_section_at_end(section_iterator) {
+        unsafe {
+            LLVMDisposeSectionIterator(section_iterator)
+        }
 
-        SectionIterator::new(section_iterator, self.object_file)
+        return None;
     }
 
-    pub fn get_symbols(&self) -> SymbolIterator {
-        let symbol_iterator = unsafe {
-            LLVMGetSymbols(self.object_file)
-        };
+    Some(section_iterator)
+}
+
+fn symbol_at(object_file: &ObjectFile, index: usize) -> Option<LLVMSymbolIteratorRef> {
+    let symbol_iterator = unsafe {
+        LLVMGetSymbols(object_file.object_file)
+    };
+
+    for _ in 0..index {
+        if object_file.is_symbol_at_end(symbol_iterator) {
+            break;
+        }
 
-        SymbolIterator::new(symbol_iterator, self.object_file)
+        unsafe {
+            LLVMMoveToNextSymbol(symbol_iterator)
+        }
     }
+
+    if object_file.is_symbol_at_end(symbol_iterator) {
+        unsafe {
+            LLVMDisposeSymbolIterator(symbol_iterator)
+        }
+
+        return None;
+    }
+
+    Some(symbol_iterator)
 }
 
-impl Drop for ObjectFile {
-    fn drop(&mut self) {
+fn relocation_at(section: &Section, index: usize) -> Option<LLVMRelocationIteratorRef> {
+    let relocation_iterator = unsafe {
+        LLVMGetRelocations(section.section)
+    };
+
+    for _ in 0..index {
+        if section.is_relocation_at_end(relocation_iterator) {
+            break;
+        }
+
         unsafe {
-            LLVMDisposeObjectFile(self.object_file)
+            LLVMMoveToNextRelocation(relocation_iterator)
+        }
+    }
+
+    if section.is_relocation_at_end(relocation_iterator) {
+        unsafe {
+            LLVMDisposeRelocationIterator(relocation_iterator)
         }
+
+        return None;
     }
+
+    Some(relocation_iterator)
 }
 
 #[derive(Debug)]
-pub struct SectionIterator {
-    section_iterator: LLVMSectionIteratorRef,
+pub struct ObjectFile {
     object_file: LLVMObjectFileRef,
+    // The contents of the buffer the object file was created from, which is owned by the object file
+    start: *const u8,
+    size: usize,
 }
 
-impl SectionIterator {
-    fn new(section_iterator: LLVMSectionIteratorRef, object_file: LLVMObjectFileRef) -> Self {
-        assert!(!section_iterator.is_null());
+impl ObjectFile {
+    pub(crate) fn new(object_file: LLVMObjectFileRef, start: *const u8, size: usize) -> Self {
+        assert!(!object_file.is_null());
 
-        SectionIterator {
-            section_iterator,
-            object_file
+        ObjectFile {
+            object_file,
+            start,
+            size,
         }
     }
-}
-
-impl Iterator for SectionIterator {
-    type Item = Section;
 
-    fn next(&mut self) -> Option<Self::Item> {
-        // REVIEW: Should it compare against 1? End checking order might also be off
-        let at_end = unsafe {
-            LLVMIsSectionIteratorAtEnd(self.object_file, self.section_iterator) == 1
-        };
+    /// Reads an `ObjectFile` from a file, ie one written by `TargetMachine::write_to_file`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::object_file::ObjectFile;
+    ///
+    /// use std::path::Path;
+    ///
+    /// let object_file = ObjectFile::create_from_file(Path::new("my_module.o")).unwrap();
+    ///
+    /// for symbol in object_file.get_symbols() {
+    ///     println!("{:?}: {:?}", symbol.get_name(), symbol.get_section().map(|section| section.get_name().to_owned()));
+    /// }
+    /// ```
+    pub fn create_from_file(path: &Path) -> Result<Self, LLVMString> {
+        let memory_buffer = MemoryBuffer::create_from_file(path)?;
+
+        memory_buffer.create_object_file().ok_or_else(|| LLVMString::create(b"Not a valid object file\0".as_ptr() as *const _))
+    }
 
-        if at_end {
-            return None;
+    fn as_slice(&self) -> &[u8] {
+        unsafe {
+            slice::from_raw_parts(self.start, self.size)
         }
+    }
 
-        let section = Section::new(self.section_iterator, self.object_file);
+    fn is_section_at_end(&self, section_iterator: LLVMSectionIteratorRef) -> bool {
+        // REVIEW: Should it compare against 1? End checking order might also be off
+        unsafe {
+            LLVMIsSectionIteratorAtEnd(self.object_file, section_iterator) == 1
+        }
+    }
 
+    fn is_symbol_at_end(&self, symbol_iterator: LLVMSymbolIteratorRef) -> bool {
+        // REVIEW: Should it compare against 1? End checking order might also be off
         unsafe {
-            LLVMMoveToNextSection(self.section_iterator)
+            LLVMIsSymbolIteratorAtEnd(self.object_file, symbol_iterator) == 1
         }
+    }
 
-        Some(section)
+    pub fn get_sections(&self) -> SectionIterator<'_> {
+        SectionIterator::new(self)
+    }
+
+    pub fn get_symbols(&self) -> SymbolIterator<'_> {
+        SymbolIterator::new(self)
     }
 }
 
-impl Drop for SectionIterator {
+impl Drop for ObjectFile {
     fn drop(&mut self) {
         unsafe {
-            LLVMDisposeSectionIterator(self.section_iterator)
+            LLVMDisposeObjectFile(self.object_file)
+        }
+    }
+}
+
+/// Iterates over the sections of an `ObjectFile`.
+#[derive(Debug)]
+pub struct SectionIterator<'a> {
+    object_file: &'a ObjectFile,
+    index: usize,
+}
+
+impl<'a> SectionIterator<'a> {
+    fn new(object_file: &'a ObjectFile) -> Self {
+        SectionIterator {
+            object_file,
+            index: 0,
         }
     }
 }
 
+impl<'a> Iterator for SectionIterator<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let section = section_at(self.object_file, self.index)?;
+
+        self.index += 1;
+
+        Some(Section::new(section, self.object_file))
+    }
+}
+
 #[derive(Debug)]
-pub struct Section {
+pub struct Section<'a> {
     section: LLVMSectionIteratorRef,
-    object_file: LLVMObjectFileRef,
+    object_file: &'a ObjectFile,
 }
 
-impl Section {
-    fn new(section: LLVMSectionIteratorRef, object_file: LLVMObjectFileRef) -> Self {
+impl<'a> Section<'a> {
+    fn new(section: LLVMSectionIteratorRef, object_file: &'a ObjectFile) -> Self {
         assert!(!section.is_null());
 
         Section {
@@ -108,21 +222,47 @@ impl Section {
         }
     }
 
-    pub fn get_name(&self) -> &CStr {
+    fn is_relocation_at_end(&self, relocation_iterator: LLVMRelocationIteratorRef) -> bool {
+        // REVIEW: Should it compare against 1? End checking order might also be off
         unsafe {
-            CStr::from_ptr(LLVMGetSectionName(self.section))
+            LLVMIsRelocationIteratorAtEnd(self.section, relocation_iterator) == 1
         }
     }
 
+    pub fn get_name(&self) -> &CStr {
+        let name = unsafe {
+            LLVMGetSectionName(self.section)
+        };
+
+        name_to_c_str(name)
+    }
+
     pub fn size(&self) -> u64 {
         unsafe {
             LLVMGetSectionSize(self.section)
         }
     }
 
-    pub fn get_contents(&self) -> &CStr {
+    /// Gets the contents of this `Section`. Sections which take up no space in the file, such as
+    /// `.bss`, have no meaningful contents: depending on the version of LLVM they are either empty
+    /// or whatever bytes follow their offset in the file.
+    pub fn get_contents(&self) -> &[u8] {
+        let start = unsafe {
+            LLVMGetSectionContents(self.section)
+        } as usize;
+        let size = self.size() as usize;
+        let file = self.object_file.as_slice();
+        let file_start = file.as_ptr() as usize;
+        let is_in_file = start >= file_start && start.checked_add(size).map(|end| end <= file_start + file.len()).unwrap_or(false);
+
+        // Every object file format starts with its header, so no section starts there. Some
+        // versions of LLVM point sections without any contents at the start of the file instead
+        if start == 0 || start == file_start || !is_in_file {
+            return &[];
+        }
+
         unsafe {
-            CStr::from_ptr(LLVMGetSectionContents(self.section))
+            slice::from_raw_parts(start as *const u8, size)
         }
     }
 
@@ -132,73 +272,55 @@ impl Section {
         }
     }
 
-    pub fn get_relocations(&self) -> RelocationIterator {
-        let relocation_iterator = unsafe {
-            LLVMGetRelocations(self.section)
-        };
+    pub fn get_relocations(&self) -> RelocationIterator<'_> {
+        RelocationIterator::new(self)
+    }
+}
 
-        RelocationIterator::new(relocation_iterator, self.section, self.object_file)
+impl<'a> Drop for Section<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            LLVMDisposeSectionIterator(self.section)
+        }
     }
 }
 
+/// Iterates over the relocations of a `Section`.
 #[derive(Debug)]
-pub struct RelocationIterator {
-    relocation_iterator: LLVMRelocationIteratorRef,
-    section_iterator: LLVMSectionIteratorRef,
-    object_file: LLVMObjectFileRef,
+pub struct RelocationIterator<'a> {
+    section: &'a Section<'a>,
+    index: usize,
 }
 
-impl RelocationIterator {
-    fn new(relocation_iterator: LLVMRelocationIteratorRef, section_iterator: LLVMSectionIteratorRef, object_file: LLVMObjectFileRef) -> Self {
-        assert!(!relocation_iterator.is_null());
-
+impl<'a> RelocationIterator<'a> {
+    fn new(section: &'a Section<'a>) -> Self {
         RelocationIterator {
-            relocation_iterator,
-            section_iterator,
-            object_file,
+            section,
+            index: 0,
         }
     }
 }
 
-impl Iterator for RelocationIterator {
-    type Item = Relocation;
+impl<'a> Iterator for RelocationIterator<'a> {
+    type Item = Relocation<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        // REVIEW: Should it compare against 1? End checking order might also be off
-        let at_end = unsafe {
-            LLVMIsRelocationIteratorAtEnd(self.section_iterator, self.relocation_iterator) == 1
-        };
-
-        if at_end {
-            return None;
-        }
-
-        let relocation = Relocation::new(self.relocation_iterator, self.object_file);
+        let relocation = relocation_at(self.section, self.index)?;
 
-        unsafe {
-            LLVMMoveToNextRelocation(self.relocation_iterator)
-        }
-
-        Some(relocation)
-    }
-}
+        self.index += 1;
 
-impl Drop for RelocationIterator {
-    fn drop(&mut self) {
-        unsafe {
-            LLVMDisposeRelocationIterator(self.relocation_iterator)
-        }
+        Some(Relocation::new(relocation, self.section.object_file))
     }
 }
 
 #[derive(Debug)]
-pub struct Relocation {
+pub struct Relocation<'a> {
     relocation: LLVMRelocationIteratorRef,
-    object_file: LLVMObjectFileRef,
+    object_file: &'a ObjectFile,
 }
 
-impl Relocation {
-    fn new(relocation: LLVMRelocationIteratorRef, object_file: LLVMObjectFileRef) -> Self {
+impl<'a> Relocation<'a> {
+    fn new(relocation: LLVMRelocationIteratorRef, object_file: &'a ObjectFile) -> Self {
         assert!(!relocation.is_null());
 
         Relocation {
@@ -213,13 +335,21 @@ impl Relocation {
         }
     }
 
-    pub fn get_symbols(&self) -> SymbolIterator {
-        let symbol_iterator = unsafe {
-            // REVIEW: Is this just returning a single Symbol (given the name) and not a full iterator?
+    /// Gets the `Symbol` this `Relocation` refers to, if any.
+    pub fn get_symbol(&self) -> Option<Symbol<'a>> {
+        let symbol = unsafe {
             LLVMGetRelocationSymbol(self.relocation)
         };
 
-        SymbolIterator::new(symbol_iterator, self.object_file)
+        if self.object_file.is_symbol_at_end(symbol) {
+            unsafe {
+                LLVMDisposeSymbolIterator(symbol)
+            }
+
+            return None;
+        }
+
+        Some(Symbol::new(symbol, self.object_file))
     }
 
     pub fn get_type(&self) -> (u64, &CStr) {
@@ -240,72 +370,64 @@ impl Relocation {
     }
 }
 
-#[derive(Debug)]
-pub struct SymbolIterator {
-    symbol_iterator: LLVMSymbolIteratorRef,
-    object_file: LLVMObjectFileRef,
+impl<'a> Drop for Relocation<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            LLVMDisposeRelocationIterator(self.relocation)
+        }
+    }
 }
 
-impl SymbolIterator {
-    fn new(symbol_iterator: LLVMSymbolIteratorRef, object_file: LLVMObjectFileRef) -> Self {
-        assert!(!symbol_iterator.is_null());
+/// Iterates over the symbols of an `ObjectFile`.
+#[derive(Debug)]
+pub struct SymbolIterator<'a> {
+    object_file: &'a ObjectFile,
+    index: usize,
+}
 
+impl<'a> SymbolIterator<'a> {
+    fn new(object_file: &'a ObjectFile) -> Self {
         SymbolIterator {
-            symbol_iterator,
             object_file,
+            index: 0,
         }
     }
 }
 
-impl Iterator for SymbolIterator {
-    type Item = Symbol;
+impl<'a> Iterator for SymbolIterator<'a> {
+    type Item = Symbol<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        // REVIEW: Should it compare against 1? End checking order might also be off
-        let at_end = unsafe {
-            LLVMIsSymbolIteratorAtEnd(self.object_file, self.symbol_iterator) == 1
-        };
-
-        if at_end {
-            return None;
-        }
+        let symbol = symbol_at(self.object_file, self.index)?;
 
-        let symbol = Symbol::new(self.symbol_iterator);
+        self.index += 1;
 
-        unsafe {
-            LLVMMoveToNextSymbol(self.symbol_iterator)
-        }
-
-        Some(symbol)
-    }
-}
-
-impl Drop for SymbolIterator {
-    fn drop(&mut self) {
-        unsafe {
-            LLVMDisposeSymbolIterator(self.symbol_iterator)
-        }
+        Some(Symbol::new(symbol, self.object_file))
     }
 }
 
 #[derive(Debug)]
-pub struct Symbol {
+pub struct Symbol<'a> {
     symbol: LLVMSymbolIteratorRef,
+    object_file: &'a ObjectFile,
 }
 
-impl Symbol {
-    fn new(symbol: LLVMSymbolIteratorRef) -> Self {
+impl<'a> Symbol<'a> {
+    fn new(symbol: LLVMSymbolIteratorRef, object_file: &'a ObjectFile) -> Self {
         assert!(!symbol.is_null());
 
         Symbol {
             symbol,
+            object_file,
         }
     }
 
     pub fn get_name(&self) -> &CStr {
-        unsafe {
-            CStr::from_ptr(LLVMGetSymbolName(self.symbol))
-        }
+        let name = unsafe {
+            LLVMGetSymbolName(self.symbol)
+        };
+
+        name_to_c_str(name)
     }
 
     pub fn size(&self) -> u64 {
@@ -319,4 +441,34 @@ impl Symbol {
             LLVMGetSymbolAddress(self.symbol)
         }
     }
+
+    /// Gets the `Section` this `Symbol` is defined in, which is `None` for undefined
+    /// symbols as well as those with an absolute address.
+    pub fn get_section(&self) -> Option<Section<'a>> {
+        let section = unsafe {
+            let section = LLVMGetSections(self.object_file.object_file);
+
+            LLVMMoveToContainingSection(section, self.symbol);
+
+            section
+        };
+
+        if self.object_file.is_section_at_end(section) {
+            unsafe {
+                LLVMDisposeSectionIterator(section)
+            }
+
+            return None;
+        }
+
+        Some(Section::new(section, self.object_file))
+    }
+}
+
+impl<'a> Drop for Symbol<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            LLVMDisposeSymbolIterator(self.symbol)
+        }
+    }
 }
@@ -0,0 +1,371 @@
+//! An ORC based JIT, which unlike the `ExecutionEngine` can have modules added
+//! and removed incrementally and can compile them one function at a time.
+
+use libc::{c_char, c_void};
+use llvm_sys::orc::{LLVMOrcJITStackRef, LLVMOrcModuleHandle, LLVMOrcTargetAddress, LLVMOrcErrorCode, LLVMOrcSymbolResolverFn, LLVMOrcCreateInstance, LLVMOrcDisposeInstance, LLVMOrcGetErrorMsg, LLVMOrcGetMangledSymbol, LLVMOrcDisposeMangledSymbol, LLVMOrcAddEagerlyCompiledIR, LLVMOrcAddLazilyCompiledIR, LLVMOrcAddObjectFile, LLVMOrcRemoveModule, LLVMOrcGetSymbolAddress, LLVMOrcGetSymbolAddressIn, LLVMOrcCreateIndirectStub, LLVMOrcSetIndirectStubPointer};
+use llvm_sys::prelude::LLVMModuleRef;
+use llvm_sys::support::{LLVMLoadLibraryPermanently, LLVMSearchForAddressOfSymbol};
+
+use context::Context;
+use execution_engine::{FunctionLookupError, JitFunction, UnsafeFunctionPointer};
+use memory_buffer::MemoryBuffer;
+use module::Module;
+use support::{LLVMString, abort_on_panic};
+use targets::TargetMachine;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
+use std::fmt::{self, Debug, Formatter};
+use std::mem::{forget, size_of, transmute_copy};
+use std::ptr;
+use std::rc::Rc;
+
+/// A handle to a module or object file which has been added to an `OrcEngine`,
+/// which can be used to look up its symbols or remove it again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ModuleHandle(LLVMOrcModuleHandle);
+
+type SymbolResolver = Box<dyn Fn(&str) -> Option<u64>>;
+
+/// A reference-counted wrapper around an ORC JIT stack.
+///
+/// Symbols which a module references but does not define are first looked up
+/// among everything else added to the engine, then passed to the symbol resolver
+/// set by `set_symbol_resolver` (if any), and finally looked up in the current process.
+#[derive(Clone)]
+pub struct OrcEngine {
+    orc_engine: Rc<OrcEngineInner>,
+}
+
+impl OrcEngine {
+    /// Creates an `OrcEngine` which takes ownership of, and generates code with, `target_machine`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::OptimizationLevel;
+    /// use inkwell::orc::OrcEngine;
+    /// use inkwell::targets::{CodeModel, InitializationConfig, RelocMode, Target, TargetMachine};
+    ///
+    /// Target::initialize_native(&InitializationConfig::default()).unwrap();
+    ///
+    /// let triple = TargetMachine::get_default_triple();
+    /// let target = Target::from_triple(triple.to_str().unwrap()).unwrap();
+    /// let target_machine = target.create_target_machine(triple.to_str().unwrap(), "", "", OptimizationLevel::None, RelocMode::Default, CodeModel::JITDefault).unwrap();
+    /// let orc_engine = OrcEngine::create(target_machine);
+    /// ```
+    pub fn create(target_machine: TargetMachine) -> Self {
+        let jit_stack = unsafe {
+            LLVMOrcCreateInstance(target_machine.target_machine)
+        };
+
+        // The JIT stack now owns the target machine
+        forget(target_machine);
+
+        assert!(!jit_stack.is_null());
+
+        // Makes the symbols of the current process available to the fallback lookup
+        unsafe {
+            LLVMLoadLibraryPermanently(ptr::null());
+        }
+
+        OrcEngine {
+            orc_engine: Rc::new(OrcEngineInner {
+                jit_stack,
+                symbol_resolver: RefCell::new(None),
+                module_contexts: RefCell::new(HashMap::new()),
+            }),
+        }
+    }
+
+    /// Sets a callback which is asked for the address of any symbol that is not
+    /// defined by a module or object file in this `OrcEngine`. Returning `None`
+    /// falls back to looking the symbol up in the current process.
+    ///
+    /// The callback receives mangled names, as produced by `get_mangled_symbol`. It is
+    /// called from within LLVM, so the process is aborted should it panic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within the symbol resolver itself.
+    pub fn set_symbol_resolver<F>(&self, resolver: F)
+    where
+        F: Fn(&str) -> Option<u64> + 'static,
+    {
+        *self.orc_engine.symbol_resolver.borrow_mut() = Some(Box::new(resolver));
+    }
+
+    /// Removes the symbol resolver callback, if one was set.
+    pub fn clear_symbol_resolver(&self) {
+        *self.orc_engine.symbol_resolver.borrow_mut() = None;
+    }
+
+    /// Adds a `Module` to this `OrcEngine`, compiling all of it right away.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::OptimizationLevel;
+    /// use inkwell::context::Context;
+    /// use inkwell::orc::OrcEngine;
+    /// use inkwell::targets::{CodeModel, InitializationConfig, RelocMode, Target, TargetMachine};
+    ///
+    /// Target::initialize_native(&InitializationConfig::default()).unwrap();
+    ///
+    /// let triple = TargetMachine::get_default_triple();
+    /// let target = Target::from_triple(triple.to_str().unwrap()).unwrap();
+    /// let target_machine = target.create_target_machine(triple.to_str().unwrap(), "", "", OptimizationLevel::None, RelocMode::Default, CodeModel::JITDefault).unwrap();
+    /// let orc_engine = OrcEngine::create(target_machine);
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_value = module.add_function("forty_two", i32_type.fn_type(&[], false), None);
+    /// let entry = fn_value.append_basic_block("entry");
+    ///
+    /// builder.position_at_end(&entry);
+    /// builder.build_return(Some(&i32_type.const_int(42, false)));
+    ///
+    /// let handle = orc_engine.add_module(module).unwrap();
+    ///
+    /// unsafe {
+    ///     let forty_two = orc_engine.get_function::<unsafe extern "C" fn() -> i32>("forty_two").unwrap();
+    ///
+    ///     assert_eq!(forty_two.call(), 42);
+    /// }
+    ///
+    /// orc_engine.remove_module(handle).unwrap();
+    /// ```
+    pub fn add_module(&self, module: Module) -> Result<ModuleHandle, LLVMString> {
+        self.add_ir(module, LLVMOrcAddEagerlyCompiledIR)
+    }
+
+    /// Adds a `Module` to this `OrcEngine` without compiling it. Each function
+    /// is instead compiled the first time it is called.
+    pub fn add_lazy_module(&self, module: Module) -> Result<ModuleHandle, LLVMString> {
+        self.add_ir(module, LLVMOrcAddLazilyCompiledIR)
+    }
+
+    fn add_ir(
+        &self,
+        module: Module,
+        add_ir: unsafe extern "C" fn(LLVMOrcJITStackRef, *mut LLVMOrcModuleHandle, LLVMModuleRef, LLVMOrcSymbolResolverFn, *mut c_void) -> LLVMOrcErrorCode,
+    ) -> Result<ModuleHandle, LLVMString> {
+        assert!(module.owned_by_ee.borrow().is_none(), "Module is already owned by an ExecutionEngine");
+
+        let (module, context) = module.into_raw();
+        let mut handle = 0;
+
+        // The JIT stack takes ownership of the module, even on failure
+        let code = unsafe {
+            add_ir(self.orc_engine.jit_stack, &mut handle, module, Some(resolve_symbol), self.resolver_ctx())
+        };
+
+        if code != LLVMOrcErrorCode::LLVMOrcErrSuccess {
+            return Err(self.get_error_message());
+        }
+
+        // The module's IR lives in its context, so it must outlive the module
+        self.orc_engine.module_contexts.borrow_mut().insert(handle, context);
+
+        Ok(ModuleHandle(handle))
+    }
+
+    /// Adds an already compiled object file to this `OrcEngine`.
+    pub fn add_object_file(&self, memory_buffer: MemoryBuffer) -> Result<ModuleHandle, LLVMString> {
+        let mut handle = 0;
+
+        let code = unsafe {
+            LLVMOrcAddObjectFile(self.orc_engine.jit_stack, &mut handle, memory_buffer.memory_buffer, Some(resolve_symbol), self.resolver_ctx())
+        };
+
+        // The JIT stack takes ownership of the memory buffer
+        forget(memory_buffer);
+
+        if code != LLVMOrcErrorCode::LLVMOrcErrSuccess {
+            return Err(self.get_error_message());
+        }
+
+        self.orc_engine.module_contexts.borrow_mut().insert(handle, None);
+
+        Ok(ModuleHandle(handle))
+    }
+
+    /// Removes a module or object file from this `OrcEngine`, freeing its code.
+    ///
+    /// Any `JitFunction`s obtained from it must no longer be called afterwards.
+    pub fn remove_module(&self, handle: ModuleHandle) -> Result<(), LLVMString> {
+        let code = unsafe {
+            LLVMOrcRemoveModule(self.orc_engine.jit_stack, handle.0)
+        };
+
+        if code != LLVMOrcErrorCode::LLVMOrcErrSuccess {
+            return Err(self.get_error_message());
+        }
+
+        self.orc_engine.module_contexts.borrow_mut().remove(&handle.0);
+
+        Ok(())
+    }
+
+    /// Mangles a symbol name the way the target does, ie prefixing an underscore on MachO.
+    pub fn get_mangled_symbol(&self, symbol: &str) -> String {
+        let c_string = CString::new(symbol).expect("Conversion to CString failed unexpectedly");
+        let mut mangled_symbol = ptr::null_mut();
+
+        unsafe {
+            LLVMOrcGetMangledSymbol(self.orc_engine.jit_stack, &mut mangled_symbol, c_string.as_ptr());
+
+            let string = CStr::from_ptr(mangled_symbol).to_string_lossy().into_owned();
+
+            LLVMOrcDisposeMangledSymbol(mangled_symbol);
+
+            string
+        }
+    }
+
+    /// Gets the address of a symbol defined in this `OrcEngine`, compiling it
+    /// first if it is part of a lazy module. The name is mangled before lookup.
+    pub fn get_symbol_address(&self, symbol: &str) -> Result<Option<u64>, LLVMString> {
+        let c_string = CString::new(self.get_mangled_symbol(symbol)).expect("Conversion to CString failed unexpectedly");
+        let mut address = 0;
+
+        let code = unsafe {
+            LLVMOrcGetSymbolAddress(self.orc_engine.jit_stack, &mut address, c_string.as_ptr())
+        };
+
+        self.to_address(code, address)
+    }
+
+    /// Gets the address of a symbol, only searching the module or object file identified by `handle`.
+    pub fn get_symbol_address_in(&self, handle: ModuleHandle, symbol: &str) -> Result<Option<u64>, LLVMString> {
+        let c_string = CString::new(self.get_mangled_symbol(symbol)).expect("Conversion to CString failed unexpectedly");
+        let mut address = 0;
+
+        let code = unsafe {
+            LLVMOrcGetSymbolAddressIn(self.orc_engine.jit_stack, &mut address, handle.0, c_string.as_ptr())
+        };
+
+        self.to_address(code, address)
+    }
+
+    fn to_address(&self, code: LLVMOrcErrorCode, address: LLVMOrcTargetAddress) -> Result<Option<u64>, LLVMString> {
+        if code != LLVMOrcErrorCode::LLVMOrcErrSuccess {
+            return Err(self.get_error_message());
+        }
+
+        if address == 0 {
+            return Ok(None);
+        }
+
+        Ok(Some(address))
+    }
+
+    /// Try to load a function from this `OrcEngine`, compiling it first if it
+    /// is part of a lazy module.
+    ///
+    /// # Safety
+    ///
+    /// It is the caller's responsibility to ensure they call the function with
+    /// the correct signature and calling convention, and that the module which
+    /// defines it has not been removed.
+    pub unsafe fn get_function<F>(&self, fn_name: &str) -> Result<JitFunction<F>, FunctionLookupError>
+    where
+        F: UnsafeFunctionPointer,
+    {
+        let address = match self.get_symbol_address(fn_name) {
+            Ok(Some(address)) => address,
+            _ => return Err(FunctionLookupError::FunctionNotFound),
+        };
+
+        assert_eq!(size_of::<F>(), size_of::<usize>(),
+            "The type `F` must have the same size as a function pointer");
+
+        Ok(JitFunction::from_orc(self.orc_engine.clone(), transmute_copy(&(address as usize))))
+    }
+
+    /// Creates an indirect call stub named `stub_name` which jumps to `address`.
+    /// Calls made through the stub can later be redirected with `set_indirect_stub_pointer`,
+    /// which allows redefining a function without recompiling its callers.
+    pub fn create_indirect_stub(&self, stub_name: &str, address: u64) -> Result<(), LLVMString> {
+        let c_string = CString::new(self.get_mangled_symbol(stub_name)).expect("Conversion to CString failed unexpectedly");
+
+        let code = unsafe {
+            LLVMOrcCreateIndirectStub(self.orc_engine.jit_stack, c_string.as_ptr(), address)
+        };
+
+        if code != LLVMOrcErrorCode::LLVMOrcErrSuccess {
+            return Err(self.get_error_message());
+        }
+
+        Ok(())
+    }
+
+    /// Points an indirect call stub created by `create_indirect_stub` at a new address.
+    pub fn set_indirect_stub_pointer(&self, stub_name: &str, address: u64) -> Result<(), LLVMString> {
+        let c_string = CString::new(self.get_mangled_symbol(stub_name)).expect("Conversion to CString failed unexpectedly");
+
+        let code = unsafe {
+            LLVMOrcSetIndirectStubPointer(self.orc_engine.jit_stack, c_string.as_ptr(), address)
+        };
+
+        if code != LLVMOrcErrorCode::LLVMOrcErrSuccess {
+            return Err(self.get_error_message());
+        }
+
+        Ok(())
+    }
+
+    fn get_error_message(&self) -> LLVMString {
+        // The message is owned by the JIT stack and may be overwritten, so copy it
+        unsafe {
+            LLVMString::create(LLVMOrcGetErrorMsg(self.orc_engine.jit_stack))
+        }
+    }
+
+    fn resolver_ctx(&self) -> *mut c_void {
+        &*self.orc_engine as *const OrcEngineInner as *mut c_void
+    }
+}
+
+impl Debug for OrcEngine {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("OrcEngine")
+            .field("jit_stack", &self.orc_engine.jit_stack)
+            .finish()
+    }
+}
+
+extern "C" fn resolve_symbol(name: *const c_char, ctx: *mut c_void) -> u64 {
+    let orc_engine = unsafe { &*(ctx as *const OrcEngineInner) };
+    let name_str = unsafe { CStr::from_ptr(name) };
+
+    // A reentrant or non-utf8 lookup simply falls through to the process' symbols
+    if let (Ok(resolver), Ok(name_str)) = (orc_engine.symbol_resolver.try_borrow(), name_str.to_str()) {
+        if let Some(address) = resolver.as_ref().and_then(|resolver| abort_on_panic(|| resolver(name_str))) {
+            return address;
+        }
+    }
+
+    unsafe {
+        LLVMSearchForAddressOfSymbol(name) as u64
+    }
+}
+
+/// Owns the JIT stack along with everything which must outlive it.
+pub(crate) struct OrcEngineInner {
+    jit_stack: LLVMOrcJITStackRef,
+    symbol_resolver: RefCell<Option<SymbolResolver>>,
+    module_contexts: RefCell<HashMap<LLVMOrcModuleHandle, Option<Context>>>,
+}
+
+impl Drop for OrcEngineInner {
+    fn drop(&mut self) {
+        // Disposing the stack frees all of its modules before their contexts are dropped
+        unsafe {
+            LLVMOrcDisposeInstance(self.jit_stack);
+        }
+    }
+}
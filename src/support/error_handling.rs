@@ -9,8 +9,14 @@ use llvm_sys::prelude::LLVMDiagnosticInfoRef;
 use llvm_sys::LLVMDiagnosticSeverity;
 use libc::c_void;
 
-// REVIEW: Maybe it's possible to have a safe wrapper? If we can
-// wrap the provided function input ptr into a &CStr somehow
+use support::{LLVMString, abort_on_panic};
+
+use std::cell::Cell;
+use std::ffi::CStr;
+use std::mem::forget;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 // TODOC: Can be used like this:
 // extern "C" fn print_before_exit(msg: *const i8) {
 //    let c_str = unsafe { ::std::ffi::CStr::from_ptr(msg) };
@@ -21,7 +27,8 @@ use libc::c_void;
 //     install_fatal_error_handler(print_before_exit);
 // }
 // and will be called before LLVM calls C exit()
-/// Installs an error handler to be called before LLVM exits.
+/// Installs an error handler to be called before LLVM exits. See `set_fatal_error_handler`
+/// for a safe alternative.
 pub unsafe fn install_fatal_error_handler(handler: extern "C" fn(*const i8)) {
     LLVMInstallFatalErrorHandler(Some(handler))
 }
@@ -33,7 +40,121 @@ pub fn reset_fatal_error_handler() {
     }
 }
 
-pub(crate) struct DiagnosticInfo {
+type FatalErrorHandler = Arc<dyn Fn(&str) + Send + Sync>;
+
+static NEXT_FATAL_ERROR_HANDLER_ID: AtomicUsize = AtomicUsize::new(0);
+
+// Handlers set via set_fatal_error_handler, most recent last. LLVM's handler is process wide,
+// so the same is true of this stack. Each handler is paired with the id of its guard.
+// A handler panicking doesn't leave the stack itself in an inconsistent state.
+global_mutex! {
+    fn lock_fatal_error_handlers() -> Vec<(usize, FatalErrorHandler)> = Vec::new();
+}
+
+extern "C" fn call_fatal_error_handler(reason: *const i8) {
+    // The lock must not be held while calling the handler in case it sets another one
+    let handler = match lock_fatal_error_handlers().last() {
+        Some((_, handler)) => handler.clone(),
+        None => return,
+    };
+    let reason = unsafe {
+        CStr::from_ptr(reason)
+    };
+
+    abort_on_panic(|| handler(&reason.to_string_lossy()))
+}
+
+/// Sets a closure to be called with the reason LLVM gives when it encounters an unrecoverable
+/// error, such as being unable to select an instruction during codegen. LLVM will exit
+/// the process once the closure returns, and the process is aborted if it panics.
+///
+/// The closure is active until the returned guard is dropped, at which point the handler which
+/// was active before it is restored. Guards may be dropped in any order. In order to keep
+/// a handler for the remainder of the process, `std::mem::forget` its guard.
+///
+/// As with LLVM's own handler, this applies to the whole process rather than a single `Context`,
+/// and the closure may be called from any thread. It should not be mixed with `install_fatal_error_handler`.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::support::error_handling::set_fatal_error_handler;
+///
+/// let module_name = String::from("my_module");
+/// let _guard = set_fatal_error_handler(move |reason| {
+///     eprintln!("LLVM fatally errored while compiling {}: {}", module_name, reason);
+/// });
+///
+/// // Compile my_module...
+/// ```
+pub fn set_fatal_error_handler<F: Fn(&str) + Send + Sync + 'static>(handler: F) -> FatalErrorHandlerGuard {
+    let id = NEXT_FATAL_ERROR_HANDLER_ID.fetch_add(1, Ordering::SeqCst);
+    let mut handlers = lock_fatal_error_handlers();
+
+    if handlers.is_empty() {
+        unsafe {
+            LLVMInstallFatalErrorHandler(Some(call_fatal_error_handler))
+        }
+    }
+
+    handlers.push((id, Arc::new(handler)));
+
+    FatalErrorHandlerGuard {
+        id,
+    }
+}
+
+/// Keeps a fatal error handler set by `set_fatal_error_handler` active. Dropping it removes
+/// that handler, restoring the previously set one or LLVM's default.
+#[derive(Debug)]
+#[must_use = "the fatal error handler is removed as soon as the guard is dropped"]
+pub struct FatalErrorHandlerGuard {
+    id: usize,
+}
+
+impl Drop for FatalErrorHandlerGuard {
+    fn drop(&mut self) {
+        let mut handlers = lock_fatal_error_handlers();
+
+        handlers.retain(|&(id, _)| id != self.id);
+
+        if handlers.is_empty() {
+            unsafe {
+                LLVMResetFatalErrorHandler()
+            }
+        }
+    }
+}
+
+// Not an enum_rename since diagnostic severities are only ever read from LLVM
+/// The severity of a `DiagnosticInfo`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum DiagnosticSeverity {
+    /// An error, after which the operation that produced it has failed.
+    Error,
+    /// A warning, ie about a stack frame being larger than requested.
+    Warning,
+    /// An optimization remark, only produced when remarks have been enabled.
+    Remark,
+    /// Additional information attached to a previous diagnostic.
+    Note,
+}
+
+impl DiagnosticSeverity {
+    pub(crate) fn new(severity: LLVMDiagnosticSeverity) -> Self {
+        match severity {
+            LLVMDiagnosticSeverity::LLVMDSError => DiagnosticSeverity::Error,
+            LLVMDiagnosticSeverity::LLVMDSWarning => DiagnosticSeverity::Warning,
+            LLVMDiagnosticSeverity::LLVMDSRemark => DiagnosticSeverity::Remark,
+            LLVMDiagnosticSeverity::LLVMDSNote => DiagnosticSeverity::Note,
+        }
+    }
+}
+
+/// A diagnostic reported by LLVM, ie by a pass, the linker or during codegen.
+/// It is only valid for the duration of a diagnostic handler call.
+#[derive(Debug)]
+pub struct DiagnosticInfo {
     diagnostic_info: LLVMDiagnosticInfoRef,
 }
 
@@ -44,18 +165,71 @@ impl DiagnosticInfo {
         }
     }
 
-    pub(crate) fn get_description(&self) -> *mut i8 {
-        unsafe {
+    /// Gets a description of this diagnostic, without any severity prefix.
+    pub fn get_description(&self) -> LLVMString {
+        let description = unsafe {
             LLVMGetDiagInfoDescription(self.diagnostic_info)
+        };
+
+        LLVMString::new(description)
+    }
+
+    /// Gets the `DiagnosticSeverity` of this diagnostic.
+    pub fn get_severity(&self) -> DiagnosticSeverity {
+        let severity = unsafe {
+            LLVMGetDiagInfoSeverity(self.diagnostic_info)
+        };
+
+        DiagnosticSeverity::new(severity)
+    }
+}
+
+// The closure set by Context::set_diagnostic_handler. As the closure may clear or replace
+// itself while it is being called, it is only freed once no call is in progress.
+pub(crate) struct DiagnosticHandler {
+    handler: Box<dyn Fn(&DiagnosticInfo)>,
+    calls_in_progress: Cell<u32>,
+    is_released: Cell<bool>,
+}
+
+impl DiagnosticHandler {
+    pub(crate) fn into_raw<F: Fn(&DiagnosticInfo) + 'static>(handler: F) -> *mut c_void {
+        let handler = Box::new(DiagnosticHandler {
+            handler: Box::new(handler),
+            calls_in_progress: Cell::new(0),
+            is_released: Cell::new(false),
+        });
+
+        Box::into_raw(handler) as *mut c_void
+    }
+
+    // Assumes the void_ptr was produced by into_raw and has not already been released
+    pub(crate) unsafe fn release(void_ptr: *mut c_void) {
+        let handler = void_ptr as *mut DiagnosticHandler;
+
+        if (*handler).calls_in_progress.get() > 0 {
+            (*handler).is_released.set(true);
+        } else {
+            drop(Box::from_raw(handler));
         }
     }
+}
 
-    pub(crate) fn severity_is_error(&self) -> bool {
-        unsafe {
-            match LLVMGetDiagInfoSeverity(self.diagnostic_info) {
-                LLVMDiagnosticSeverity::LLVMDSError => true,
-                _ => false,
-            }
+// Assumptions this handler makes:
+// * The void_ptr was produced by DiagnosticHandler::into_raw (via context.set_diagnostic_handler)
+//   and is still alive, since it is only released once the handler is replaced or the context is disposed
+pub(crate) extern "C" fn closure_diagnostic_handler(diagnostic_info: LLVMDiagnosticInfoRef, void_ptr: *mut c_void) {
+    let handler = void_ptr as *mut DiagnosticHandler;
+
+    unsafe {
+        let calls_in_progress = (*handler).calls_in_progress.get();
+
+        (*handler).calls_in_progress.set(calls_in_progress + 1);
+        abort_on_panic(|| ((*handler).handler)(&DiagnosticInfo::new(diagnostic_info)));
+        (*handler).calls_in_progress.set(calls_in_progress);
+
+        if calls_in_progress == 0 && (*handler).is_released.get() {
+            drop(Box::from_raw(handler));
         }
     }
 }
@@ -68,11 +242,15 @@ impl DiagnosticInfo {
 pub(crate) extern "C" fn get_error_str_diagnostic_handler(diagnostic_info: LLVMDiagnosticInfoRef, void_ptr: *mut c_void) {
     let diagnostic_info = DiagnosticInfo::new(diagnostic_info);
 
-    if diagnostic_info.severity_is_error() {
+    if diagnostic_info.get_severity() == DiagnosticSeverity::Error {
         let i8_ptr_ptr = void_ptr as *mut *mut c_void as *mut *mut i8;
+        let description = diagnostic_info.get_description();
 
         unsafe {
-            *i8_ptr_ptr = diagnostic_info.get_description();
+            *i8_ptr_ptr = description.ptr as *mut i8;
         }
+
+        // Ownership of the description is handed to whoever installed this handler
+        forget(description);
     }
 }
@@ -1,6 +1,3 @@
-#[deny(missing_docs)]
-pub mod error_handling;
-
 use libc::c_char;
 use llvm_sys::core::{LLVMCreateMessage, LLVMDisposeMessage};
 use llvm_sys::support::LLVMLoadLibraryPermanently;
@@ -9,6 +6,9 @@ use std::error::Error;
 use std::fmt::{self, Debug, Display, Formatter};
 use std::ffi::{CString, CStr};
 use std::ops::Deref;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::process::abort;
+use std::ptr;
 
 /// An owned LLVM String. Also known as a LLVM Message
 #[derive(Eq)]
@@ -117,6 +117,35 @@ impl PartialEq for LLVMStringOrRaw {
     }
 }
 
+/// Takes the value at `front` from a double-ended iterator over an intrusive LLVM list (functions,
+/// globals, basic blocks, instructions...) and moves `front` along using `advance`. Reverse iteration
+/// swaps `front` and `back` and passes the corresponding "previous" function. The list is exhausted
+/// once both ends have met.
+pub(crate) fn next_in_list<T>(front: &mut *mut T, back: &mut *mut T, advance: unsafe extern "C" fn(*mut T) -> *mut T) -> Option<*mut T> {
+    let value = *front;
+
+    if value.is_null() {
+        return None;
+    }
+
+    if value == *back {
+        *front = ptr::null_mut();
+        *back = ptr::null_mut();
+    } else {
+        *front = unsafe {
+            advance(value)
+        };
+    }
+
+    Some(value)
+}
+
+/// Calls a user supplied closure from a function LLVM calls back into, aborting the process if
+/// it panics, since unwinding through LLVM's frames is undefined behavior.
+pub(crate) fn abort_on_panic<F: FnOnce() -> R, R>(f: F) -> R {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| abort())
+}
+
 /// This function is very unsafe. Any reference to LLVM data after this function is called will likey segfault.
 /// Probably only ever useful to call before your program ends. Might not even be absolutely necessary.
 pub unsafe fn shutdown_llvm() {
@@ -187,3 +216,30 @@ macro_rules! enum_rename {
         }
     );
 }
+
+// Declares a function which locks a process wide Mutex, which is created with `$init` the first time
+// it is locked. Mutex::new isn't a const fn, so the Mutex is boxed and leaked rather than being the static.
+// Poisoning is ignored, so the data must not be left in an inconsistent state by a panic while it is locked.
+macro_rules! global_mutex {
+    ($(#[$attrs:meta])* fn $lock:ident() -> $type:ty = $init:expr;) => (
+        $(#[$attrs])*
+        fn $lock() -> ::std::sync::MutexGuard<'static, $type> {
+            #[allow(deprecated)]
+            static INIT: ::std::sync::Once = ::std::sync::ONCE_INIT;
+            static mut MUTEX: *const ::std::sync::Mutex<$type> = ::std::ptr::null();
+
+            let mutex = unsafe {
+                INIT.call_once(|| {
+                    MUTEX = Box::into_raw(Box::new(::std::sync::Mutex::new($init)));
+                });
+
+                &*MUTEX
+            };
+
+            mutex.lock().unwrap_or_else(::std::sync::PoisonError::into_inner)
+        }
+    );
+}
+
+#[deny(missing_docs)]
+pub mod error_handling;
@@ -19,7 +19,7 @@ use std::mem::zeroed;
 use std::path::Path;
 use std::ptr;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum CodeModel {
     Default,
     JITDefault,
@@ -29,7 +29,7 @@ pub enum CodeModel {
     Large,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum RelocMode {
     Default,
     Static,
@@ -37,6 +37,114 @@ pub enum RelocMode {
     DynamicNoPic,
 }
 
+/// The configuration of a `TargetMachine`, built up by chaining setters starting from the
+/// defaults of `TargetMachineOptions::new` and passed to `Target::create_target_machine_from_options`.
+///
+/// These are the options which the C API of LLVM 7 can set. The rest of LLVM's `TargetOptions`,
+/// such as the float ABI, emulated TLS, function and data sections or the stack protector guard,
+/// can only be set through the C++ API and so aren't available.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::OptimizationLevel;
+/// use inkwell::targets::{InitializationConfig, RelocMode, Target, TargetMachineOptions};
+///
+/// Target::initialize_arm(&InitializationConfig::default());
+///
+/// let target = Target::from_name("thumb").unwrap();
+/// let options = TargetMachineOptions::new()
+///     .set_cpu("cortex-m4")
+///     .set_level(OptimizationLevel::Less)
+///     .set_reloc_mode(RelocMode::Static);
+/// let target_machine = target.create_target_machine_from_options("thumbv7em-none-eabihf", options).unwrap();
+///
+/// assert_eq!(target_machine.get_options().get_cpu(), "cortex-m4");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TargetMachineOptions {
+    cpu: String,
+    features: String,
+    level: OptimizationLevel,
+    reloc_mode: RelocMode,
+    code_model: CodeModel,
+}
+
+impl TargetMachineOptions {
+    /// Creates `TargetMachineOptions` for the target's generic CPU without any extra features,
+    /// with every other option set to its `Default` variant.
+    pub fn new() -> Self {
+        TargetMachineOptions {
+            cpu: String::new(),
+            features: String::new(),
+            level: OptimizationLevel::default(),
+            reloc_mode: RelocMode::Default,
+            code_model: CodeModel::Default,
+        }
+    }
+
+    /// Sets the CPU to generate code for, ie "x86-64" or "cortex-m4".
+    pub fn set_cpu(mut self, cpu: &str) -> Self {
+        self.cpu = cpu.to_owned();
+        self
+    }
+
+    /// Gets the CPU to generate code for.
+    pub fn get_cpu(&self) -> &str {
+        &self.cpu
+    }
+
+    /// Sets the comma separated list of features to enable or disable, ie "+avx2,-sse4a".
+    pub fn set_features(mut self, features: &str) -> Self {
+        self.features = features.to_owned();
+        self
+    }
+
+    /// Gets the comma separated list of features to enable or disable.
+    pub fn get_features(&self) -> &str {
+        &self.features
+    }
+
+    /// Sets the `OptimizationLevel` of code generation.
+    pub fn set_level(mut self, level: OptimizationLevel) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Gets the `OptimizationLevel` of code generation.
+    pub fn get_level(&self) -> OptimizationLevel {
+        self.level
+    }
+
+    /// Sets the `RelocMode`.
+    pub fn set_reloc_mode(mut self, reloc_mode: RelocMode) -> Self {
+        self.reloc_mode = reloc_mode;
+        self
+    }
+
+    /// Gets the `RelocMode`.
+    pub fn get_reloc_mode(&self) -> RelocMode {
+        self.reloc_mode
+    }
+
+    /// Sets the `CodeModel`.
+    pub fn set_code_model(mut self, code_model: CodeModel) -> Self {
+        self.code_model = code_model;
+        self
+    }
+
+    /// Gets the `CodeModel`.
+    pub fn get_code_model(&self) -> CodeModel {
+        self.code_model
+    }
+}
+
+impl Default for TargetMachineOptions {
+    /// Returns the same options as `TargetMachineOptions::new`.
+    fn default() -> Self {
+        TargetMachineOptions::new()
+    }
+}
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum FileType {
@@ -665,16 +773,45 @@ impl Target {
     }
 
     pub fn create_target_machine(&self, triple: &str, cpu: &str, features: &str, level: OptimizationLevel, reloc_mode: RelocMode, code_model: CodeModel) -> Option<TargetMachine> {
+        let options = TargetMachineOptions::new()
+            .set_cpu(cpu)
+            .set_features(features)
+            .set_level(level)
+            .set_reloc_mode(reloc_mode)
+            .set_code_model(code_model);
+
+        self.create_target_machine_from_options(triple, options)
+    }
+
+    /// Creates a `TargetMachine` configured by `TargetMachineOptions`, which it keeps so
+    /// that they can be read back with `TargetMachine::get_options`.
+    ///
+    /// Returns `None` if LLVM fails to create the `TargetMachine`, ie due to an unknown CPU.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::targets::{CodeModel, InitializationConfig, Target, TargetMachineOptions};
+    ///
+    /// Target::initialize_x86(&InitializationConfig::default());
+    ///
+    /// let target = Target::from_name("x86-64").unwrap();
+    /// let options = TargetMachineOptions::new().set_cpu("x86-64").set_code_model(CodeModel::Small);
+    /// let target_machine = target.create_target_machine_from_options("x86_64-pc-linux-gnu", options).unwrap();
+    ///
+    /// assert_eq!(target_machine.get_options().get_code_model(), CodeModel::Small);
+    /// ```
+    pub fn create_target_machine_from_options(&self, triple: &str, options: TargetMachineOptions) -> Option<TargetMachine> {
         let triple = CString::new(triple).expect("Conversion to CString failed unexpectedly");
-        let cpu = CString::new(cpu).expect("Conversion to CString failed unexpectedly");
-        let features = CString::new(features).expect("Conversion to CString failed unexpectedly");
-        let level = match level {
+        let cpu = CString::new(options.cpu.as_str()).expect("Conversion to CString failed unexpectedly");
+        let features = CString::new(options.features.as_str()).expect("Conversion to CString failed unexpectedly");
+        let level = match options.level {
             OptimizationLevel::None => LLVMCodeGenOptLevel::LLVMCodeGenLevelNone,
             OptimizationLevel::Less => LLVMCodeGenOptLevel::LLVMCodeGenLevelLess,
             OptimizationLevel::Default => LLVMCodeGenOptLevel::LLVMCodeGenLevelDefault,
             OptimizationLevel::Aggressive => LLVMCodeGenOptLevel::LLVMCodeGenLevelAggressive,
         };
-        let code_model = match code_model {
+        let code_model = match options.code_model {
             CodeModel::Default => LLVMCodeModel::LLVMCodeModelDefault,
             CodeModel::JITDefault => LLVMCodeModel::LLVMCodeModelJITDefault,
             CodeModel::Small => LLVMCodeModel::LLVMCodeModelSmall,
@@ -682,7 +819,7 @@ impl Target {
             CodeModel::Medium => LLVMCodeModel::LLVMCodeModelMedium,
             CodeModel::Large => LLVMCodeModel::LLVMCodeModelLarge,
         };
-        let reloc_mode = match reloc_mode {
+        let reloc_mode = match options.reloc_mode {
             RelocMode::Default => LLVMRelocMode::LLVMRelocDefault,
             RelocMode::Static => LLVMRelocMode::LLVMRelocStatic,
             RelocMode::PIC => LLVMRelocMode::LLVMRelocPIC,
@@ -696,7 +833,7 @@ impl Target {
             return None;
         }
 
-        Some(TargetMachine::new(target_machine))
+        Some(TargetMachine::new(target_machine, options))
     }
 
     pub fn get_first() -> Option<Self> {
@@ -790,18 +927,25 @@ impl Target {
 
 #[derive(Debug)]
 pub struct TargetMachine {
-    target_machine: LLVMTargetMachineRef,
+    pub(crate) target_machine: LLVMTargetMachineRef,
+    options: TargetMachineOptions,
 }
 
 impl TargetMachine {
-    fn new(target_machine: LLVMTargetMachineRef) -> Self {
+    fn new(target_machine: LLVMTargetMachineRef, options: TargetMachineOptions) -> Self {
         assert!(!target_machine.is_null());
 
         TargetMachine {
             target_machine,
+            options,
         }
     }
 
+    /// Gets the `TargetMachineOptions` this `TargetMachine` was created with.
+    pub fn get_options(&self) -> &TargetMachineOptions {
+        &self.options
+    }
+
     pub fn get_target(&self) -> Target {
         let target = unsafe {
             LLVMGetTargetMachineTarget(self.target_machine)
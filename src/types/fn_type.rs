@@ -1,5 +1,6 @@
-use llvm_sys::core::{LLVMGetParamTypes, LLVMIsFunctionVarArg, LLVMCountParamTypes};
+use llvm_sys::core::{LLVMGetParamTypes, LLVMIsFunctionVarArg, LLVMCountParamTypes, LLVMGetReturnType, LLVMGetTypeKind};
 use llvm_sys::prelude::LLVMTypeRef;
+use llvm_sys::LLVMTypeKind;
 
 use std::fmt;
 use std::mem::forget;
@@ -10,7 +11,6 @@ use support::LLVMString;
 use types::traits::AsTypeRef;
 use types::{PointerType, Type, BasicTypeEnum};
 
-// REVIEW: Add a get_return_type() -> Option<BasicTypeEnum>?
 /// A `FunctionType` is the type of a function variable.
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub struct FunctionType {
@@ -95,6 +95,32 @@ impl FunctionType {
         raw_vec.iter().map(|val| BasicTypeEnum::new(*val)).collect()
     }
 
+    /// Gets the return type of this `FunctionType`, or `None` if it returns void.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f32_type = context.f32_type();
+    /// let fn_type = f32_type.fn_type(&[], false);
+    /// let void_fn_type = context.void_type().fn_type(&[], false);
+    ///
+    /// assert_eq!(fn_type.get_return_type().unwrap().into_float_type(), f32_type);
+    /// assert!(void_fn_type.get_return_type().is_none());
+    /// ```
+    pub fn get_return_type(&self) -> Option<BasicTypeEnum> {
+        let type_ = unsafe {
+            LLVMGetReturnType(self.as_type_ref())
+        };
+
+        match unsafe { LLVMGetTypeKind(type_) } {
+            LLVMTypeKind::LLVMVoidTypeKind => None,
+            _ => Some(BasicTypeEnum::new(type_)),
+        }
+    }
+
     /// Counts the number of param types this `FunctionType` has.
     ///
     /// # Example
@@ -256,6 +256,48 @@ impl IntType {
         IntValue::new(value)
     }
 
+    /// Creates a constant `IntValue` from a `u128`, which is truncated or zero extended to this `IntType`'s width.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i128_type = context.i128_type();
+    /// let i128_val = i128_type.const_u128(u128::max_value());
+    ///
+    /// assert_eq!(i128_val.get_u128_constant(), Some(u128::max_value()));
+    /// ```
+    pub fn const_u128(&self, value: u128) -> IntValue {
+        self.const_int_arbitrary_precision(&[value as u64, (value >> 64) as u64])
+    }
+
+    /// Creates a constant `IntValue` from an `i128`, which is truncated or sign extended to this `IntType`'s width.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i256_type = context.custom_width_int_type(256);
+    /// let i256_val = i256_type.const_i128(-1);
+    ///
+    /// assert_eq!(i256_val.get_constant_words(), Some(vec![u64::max_value(); 4]));
+    /// ```
+    pub fn const_i128(&self, value: i128) -> IntValue {
+        let mut words = vec![value as u64, (value >> 64) as u64];
+
+        // Words beyond those given are zero, so they must be filled in for negative values
+        let word_count = (self.get_bit_width() as usize - 1) / 64 + 1;
+        let extension = (value >> 127) as u64;
+
+        words.resize(word_count.max(2), extension);
+
+        self.const_int_arbitrary_precision(&words)
+    }
+
     /// Creates an `IntValue` representing a constant value of all one bits of this `IntType`. It will be automatically assigned this `IntType`'s `Context`.
     ///
     /// # Example
@@ -12,6 +12,8 @@ mod int_type;
 #[deny(missing_docs)]
 mod ptr_type;
 #[deny(missing_docs)]
+mod rust_type;
+#[deny(missing_docs)]
 mod struct_type;
 #[deny(missing_docs)]
 mod traits;
@@ -26,7 +28,10 @@ pub use types::float_type::FloatType;
 pub use types::fn_type::FunctionType;
 pub use types::int_type::IntType;
 pub use types::ptr_type::PointerType;
-pub use types::struct_type::StructType;
+pub use types::rust_type::{RustStruct, RustType};
+pub use inkwell_derive::RustStruct;
+pub use types::struct_type::{FieldNameError, StructType};
+pub(crate) use types::struct_type::remove_field_names;
 pub use types::traits::{AnyType, BasicType, IntMathType, FloatMathType, PointerMathType};
 pub use types::vec_type::VectorType;
 pub use types::void_type::VoidType;
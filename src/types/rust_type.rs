@@ -0,0 +1,187 @@
+use std::mem::size_of;
+use std::os::raw::c_void;
+
+use AddressSpace;
+use context::Context;
+use targets::TargetData;
+use types::{AsTypeRef, BasicType, BasicTypeEnum, StructType, Type};
+
+/// A Rust type with an equivalent LLVM type, such that values may be shared
+/// between Rust and JIT compiled code.
+///
+/// It is implemented for primitives, pointers, references and arrays of up to 32
+/// elements, as well as for any struct deriving `RustStruct`.
+///
+/// # Safety
+///
+/// `basic_type` must return a type with the same size and layout as `Self`, as
+/// `ExecutionEngine::get_checked_function` relies on it to call JIT compiled code.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::context::Context;
+/// use inkwell::types::{BasicType, RustType};
+///
+/// let context = Context::create();
+///
+/// assert_eq!(u16::basic_type(&context), context.i16_type().as_basic_type_enum());
+/// assert_eq!(<*const f32>::basic_type(&context), context.f32_type().ptr_type(inkwell::AddressSpace::Generic).as_basic_type_enum());
+/// ```
+pub unsafe trait RustType {
+    /// Gets the LLVM type equivalent to this Rust type.
+    fn basic_type(context: &Context) -> BasicTypeEnum;
+}
+
+macro_rules! impl_rust_type {
+    ($type_method:ident: $($rust_type:ty),*) => (
+        $(
+            unsafe impl RustType for $rust_type {
+                fn basic_type(context: &Context) -> BasicTypeEnum {
+                    context.$type_method().as_basic_type_enum()
+                }
+            }
+        )*
+    );
+}
+
+// bool is a byte in memory, just as clang represents it
+impl_rust_type! {i8_type: bool, i8, u8}
+impl_rust_type! {i16_type: i16, u16}
+impl_rust_type! {i32_type: i32, u32, char}
+impl_rust_type! {i64_type: i64, u64}
+impl_rust_type! {i128_type: i128, u128}
+impl_rust_type! {f32_type: f32}
+impl_rust_type! {f64_type: f64}
+
+unsafe impl RustType for isize {
+    fn basic_type(context: &Context) -> BasicTypeEnum {
+        context.custom_width_int_type(size_of::<isize>() as u32 * 8).as_basic_type_enum()
+    }
+}
+
+unsafe impl RustType for usize {
+    fn basic_type(context: &Context) -> BasicTypeEnum {
+        context.custom_width_int_type(size_of::<usize>() as u32 * 8).as_basic_type_enum()
+    }
+}
+
+/// LLVM has no void pointers, so `c_void` is an `i8` in order for
+/// pointers to it to become `i8*`, just as clang does.
+unsafe impl RustType for c_void {
+    fn basic_type(context: &Context) -> BasicTypeEnum {
+        context.i8_type().as_basic_type_enum()
+    }
+}
+
+macro_rules! impl_rust_type_for_pointers {
+    ($($pointer_type:ty),*) => (
+        $(
+            unsafe impl<'a, T: RustType> RustType for $pointer_type {
+                fn basic_type(context: &Context) -> BasicTypeEnum {
+                    let pointee = T::basic_type(context);
+
+                    Type::new(pointee.as_type_ref()).ptr_type(AddressSpace::Generic).as_basic_type_enum()
+                }
+            }
+        )*
+    );
+}
+
+impl_rust_type_for_pointers! {*const T, *mut T, &'a T, &'a mut T}
+
+macro_rules! impl_rust_type_for_arrays {
+    ($($size:expr),*) => (
+        $(
+            unsafe impl<T: RustType> RustType for [T; $size] {
+                fn basic_type(context: &Context) -> BasicTypeEnum {
+                    let element = T::basic_type(context);
+
+                    Type::new(element.as_type_ref()).array_type($size).as_basic_type_enum()
+                }
+            }
+        )*
+    );
+}
+
+impl_rust_type_for_arrays! {0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32}
+
+/// A `#[repr(C)]` Rust struct with an equivalent LLVM `StructType`. It should be
+/// implemented with `#[derive(RustStruct)]`, which requires every field to be a `RustType`.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::context::Context;
+/// use inkwell::types::RustStruct;
+///
+/// #[derive(RustStruct)]
+/// #[repr(C)]
+/// struct Point {
+///     x: f64,
+///     y: f64,
+///     tag: u8,
+/// }
+///
+/// let context = Context::create();
+/// let f64_type = context.f64_type().into();
+/// let point_type = Point::struct_type(&context);
+///
+/// assert_eq!(point_type, context.struct_type(&[f64_type, f64_type, context.i8_type().into()], false));
+/// assert_eq!(Point::field_index("tag"), Some(2));
+/// ```
+pub trait RustStruct: Sized {
+    /// The names of this struct's fields in order, which are their indices for tuple structs.
+    const FIELD_NAMES: &'static [&'static str];
+
+    /// Gets the LLVM `StructType` equivalent to this struct.
+    fn struct_type(context: &Context) -> StructType;
+
+    /// Gets the offsets of this struct's fields in bytes, as laid out by Rust.
+    fn field_offsets() -> Vec<u64>;
+
+    /// Gets the index of a field of this struct in its `StructType`, ie for use with `Builder::build_struct_gep`.
+    fn field_index(name: &str) -> Option<u32> {
+        Self::FIELD_NAMES.iter().position(|&field_name| field_name == name).map(|index| index as u32)
+    }
+
+    /// Panics unless every field of this struct's `StructType` is at the same offset
+    /// according to `TargetData` as it is in Rust, and the struct has the same size.
+    /// This is intended to be used in tests, with the `TargetData` of the JIT compiled code.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::OptimizationLevel;
+    /// use inkwell::context::Context;
+    /// use inkwell::targets::{InitializationConfig, Target};
+    /// use inkwell::types::RustStruct;
+    ///
+    /// #[derive(RustStruct)]
+    /// #[repr(C)]
+    /// struct Header {
+    ///     tag: u8,
+    ///     len: u32,
+    ///     data: *const u8,
+    /// }
+    ///
+    /// Target::initialize_native(&InitializationConfig::default()).unwrap();
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_module");
+    /// let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    ///
+    /// Header::assert_layout_matches(&context, execution_engine.get_target_data());
+    /// ```
+    fn assert_layout_matches(context: &Context, target_data: &TargetData) {
+        let struct_type = Self::struct_type(context);
+
+        for (index, rust_offset) in Self::field_offsets().into_iter().enumerate() {
+            let llvm_offset = target_data.offset_of_element(&struct_type, index as u32);
+
+            assert_eq!(llvm_offset, Some(rust_offset), "Field {} is at a different offset in LLVM than in Rust", Self::FIELD_NAMES[index]);
+        }
+
+        assert_eq!(target_data.get_abi_size(&struct_type), size_of::<Self>() as u64, "Struct has a different size in LLVM than in Rust");
+    }
+}
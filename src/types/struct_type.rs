@@ -1,9 +1,13 @@
 use llvm_sys::core::{LLVMConstNamedStruct, LLVMConstStruct, LLVMStructType, LLVMCountStructElementTypes, LLVMGetStructElementTypes, LLVMGetStructName, LLVMIsPackedStruct, LLVMIsOpaqueStruct, LLVMStructSetBody, LLVMConstArray};
 #[llvm_versions(3.7 => latest)]
 use llvm_sys::core::LLVMStructGetTypeAtIndex;
-use llvm_sys::prelude::{LLVMTypeRef, LLVMValueRef};
+use llvm_sys::core::LLVMGetTypeContext;
+use llvm_sys::prelude::{LLVMContextRef, LLVMTypeRef, LLVMValueRef};
 
+use std::collections::BTreeMap;
+use std::error::Error;
 use std::ffi::CStr;
+use std::fmt::{self, Display, Formatter};
 use std::mem::forget;
 
 use AddressSpace;
@@ -13,6 +17,71 @@ use types::traits::AsTypeRef;
 use types::{Type, BasicTypeEnum, ArrayType, PointerType, FunctionType, VectorType};
 use values::{ArrayValue, BasicValueEnum, StructValue, IntValue, AsValueRef};
 
+// Field names of named struct types, keyed by the addresses of their context and then the type.
+// Names are removed once their context is disposed, since its types' addresses may then be reused.
+// Panicking while holding the lock doesn't leave the table in an inconsistent state.
+global_mutex! {
+    fn lock_field_names() -> BTreeMap<(usize, usize), Vec<String>> = BTreeMap::new();
+}
+
+// Removes the field names of every struct type in a context which is being disposed
+pub(crate) fn remove_field_names(context: LLVMContextRef) {
+    lock_field_names().retain(|&(struct_context, _), _| struct_context != context as usize);
+}
+
+/// Errors that can occur when looking up a field of a `StructType` by name.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum FieldNameError {
+    /// The type being indexed into is not a struct.
+    NonStructType,
+    /// The struct has not had any field names set via `StructType::set_field_names`.
+    NoFieldNames {
+        /// The name of the struct, or how it is printed if it is unnamed.
+        struct_name: String,
+    },
+    /// The struct has no field with the requested name.
+    UnknownField {
+        /// The name of the struct.
+        struct_name: String,
+        /// The requested field name.
+        field_name: String,
+        /// The names the struct's fields do have.
+        field_names: Vec<String>,
+    },
+}
+
+impl FieldNameError {
+    fn as_str(&self) -> &str {
+        match self {
+            FieldNameError::NonStructType => "The type is not a struct",
+            FieldNameError::NoFieldNames { .. } => "The struct has no field names",
+            FieldNameError::UnknownField { .. } => "The struct has no field with this name",
+        }
+    }
+}
+
+impl Error for FieldNameError {
+    fn description(&self) -> &str {
+        self.as_str()
+    }
+
+    fn cause(&self) -> Option<&dyn Error> {
+        None
+    }
+}
+
+impl Display for FieldNameError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            FieldNameError::NonStructType => write!(f, "FieldNameError({})", self.as_str()),
+            FieldNameError::NoFieldNames { ref struct_name } => write!(f, "FieldNameError({}: {})", self.as_str(), struct_name),
+            FieldNameError::UnknownField { ref struct_name, ref field_name, ref field_names } => {
+                write!(f, "FieldNameError({}: {} has no field {:?}, only {:?})", self.as_str(), struct_name, field_name, field_names)
+            },
+        }
+    }
+}
+
 /// A `StructType` is the type of a heterogeneous container of types.
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct StructType {
@@ -486,6 +555,111 @@ impl StructType {
         is_opaque
     }
 
+    fn field_names_key(&self) -> (usize, usize) {
+        let context = unsafe {
+            LLVMGetTypeContext(self.as_type_ref())
+        };
+
+        (context as usize, self.as_type_ref() as usize)
+    }
+
+    /// Associates a name with each field of a named `StructType`, which can then be used
+    /// in place of field indices, ie by `Builder::build_struct_gep_by_name`. The names only
+    /// exist on the Rust side, and so aren't kept when the struct is printed or written out.
+    ///
+    /// Returns `false` without setting any names if this struct is unnamed or opaque, or if
+    /// the names aren't unique or there isn't one for each field.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let f32_type = context.f32_type();
+    /// let struct_type = context.opaque_struct_type("point");
+    ///
+    /// struct_type.set_body(&[f32_type.into(), f32_type.into()], false);
+    ///
+    /// assert!(struct_type.set_field_names(&["x", "y"]));
+    /// assert_eq!(struct_type.get_field_index("y"), Ok(1));
+    /// ```
+    pub fn set_field_names(&self, field_names: &[&str]) -> bool {
+        let are_unique = field_names.iter().enumerate().all(|(index, name)| !field_names[..index].contains(name));
+
+        if self.get_name().is_none() || self.is_opaque() || field_names.len() != self.count_fields() as usize || !are_unique {
+            return false;
+        }
+
+        let field_names = field_names.iter().map(|&name| name.to_owned()).collect();
+
+        lock_field_names().insert(self.field_names_key(), field_names);
+
+        true
+    }
+
+    /// Gets the field names set via `set_field_names`, if any.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let struct_type = context.opaque_struct_type("wrapper");
+    ///
+    /// assert_eq!(struct_type.get_field_names(), None);
+    ///
+    /// struct_type.set_body(&[context.i64_type().into()], false);
+    /// struct_type.set_field_names(&["inner"]);
+    ///
+    /// assert_eq!(struct_type.get_field_names(), Some(vec![String::from("inner")]));
+    /// ```
+    pub fn get_field_names(&self) -> Option<Vec<String>> {
+        lock_field_names().get(&self.field_names_key()).cloned()
+    }
+
+    /// Gets the index of the field with the given name, as set via `set_field_names`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::types::FieldNameError;
+    ///
+    /// let context = Context::create();
+    /// let f32_type = context.f32_type();
+    /// let struct_type = context.opaque_struct_type("point");
+    ///
+    /// struct_type.set_body(&[f32_type.into(), f32_type.into()], false);
+    ///
+    /// assert_eq!(struct_type.get_field_index("x"), Err(FieldNameError::NoFieldNames { struct_name: String::from("point") }));
+    ///
+    /// struct_type.set_field_names(&["x", "y"]);
+    ///
+    /// assert_eq!(struct_type.get_field_index("x"), Ok(0));
+    /// assert!(struct_type.get_field_index("z").is_err());
+    /// ```
+    pub fn get_field_index(&self, field_name: &str) -> Result<u32, FieldNameError> {
+        let struct_name = || match self.get_name() {
+            Some(name) => name.to_string_lossy().into_owned(),
+            None => self.print_to_string().to_string(),
+        };
+        let field_names = match self.get_field_names() {
+            Some(field_names) => field_names,
+            None => return Err(FieldNameError::NoFieldNames { struct_name: struct_name() }),
+        };
+
+        match field_names.iter().position(|name| name == field_name) {
+            Some(index) => Ok(index as u32),
+            None => Err(FieldNameError::UnknownField {
+                struct_name: struct_name(),
+                field_name: field_name.to_owned(),
+                field_names,
+            }),
+        }
+    }
+
     /// Creates a `VectorType` with this `StructType` for its element type.
     ///
     /// # Example
@@ -1,13 +1,14 @@
-use llvm_sys::core::{LLVMIsAConstantArray, LLVMIsAConstantDataArray};
+use llvm_sys::core::{LLVMIsAConstantArray, LLVMIsAConstantDataArray, LLVMIsAConstantDataSequential, LLVMIsConstantString, LLVMGetAsString};
 use llvm_sys::prelude::LLVMValueRef;
 
 use std::ffi::CStr;
 use std::fmt;
+use std::slice;
 
 use support::LLVMString;
 use types::ArrayType;
 use values::traits::AsValueRef;
-use values::{Value, InstructionValue, MetadataValue};
+use values::{get_constant_element, BasicValueEnum, Value, InstructionValue, MetadataValue};
 
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub struct ArrayValue {
@@ -88,6 +89,72 @@ impl ArrayValue {
     pub fn is_const(&self) -> bool {
         self.array_value.is_const()
     }
+
+    /// Gets the element of a constant `ArrayValue` at an index. Returns `None` if
+    /// the array isn't a constant, ie is a constant expression, or the index is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i64_type = context.i64_type();
+    /// let i64_val = i64_type.const_int(23, false);
+    /// let array_val = i64_type.const_array(&[i64_val]);
+    ///
+    /// assert_eq!(array_val.get_element_as_constant(0), Some(i64_val.into()));
+    /// assert_eq!(array_val.get_element_as_constant(1), None);
+    /// ```
+    pub fn get_element_as_constant(&self, index: u32) -> Option<BasicValueEnum> {
+        let array_type = self.get_type();
+
+        if !self.is_const() || index >= array_type.len() {
+            return None;
+        }
+
+        get_constant_element(self.as_value_ref(), index, &array_type.get_element_type())
+    }
+
+    /// Determines whether or not an `ArrayValue` is a constant array of `i8`s, such as
+    /// one created by `Context::const_value` from a `str`. Zero initialized arrays are not strings.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let string = context.const_value("my_string").into_array_value();
+    ///
+    /// assert!(string.is_const_string());
+    /// assert_eq!(string.get_string_constant(), Some(&b"my_string"[..]));
+    /// ```
+    // SubTypes: Impl only for ArrayValue<IntValue<i8>>
+    pub fn is_const_string(&self) -> bool {
+        unsafe {
+            // LLVMIsConstantString assumes its argument is a ConstantDataSequential
+            !LLVMIsAConstantDataSequential(self.as_value_ref()).is_null() && LLVMIsConstantString(self.as_value_ref()) == 1
+        }
+    }
+
+    /// Gets the bytes of a constant string, including its null terminator if it has one.
+    /// Returns `None` unless `is_const_string` is true.
+    // SubTypes: Impl only for ArrayValue<IntValue<i8>>
+    pub fn get_string_constant(&self) -> Option<&[u8]> {
+        if !self.is_const_string() {
+            return None;
+        }
+
+        let mut len = 0;
+        let ptr = unsafe {
+            LLVMGetAsString(self.as_value_ref(), &mut len)
+        };
+
+        unsafe {
+            Some(slice::from_raw_parts(ptr as *const u8, len))
+        }
+    }
 }
 
 impl AsValueRef for ArrayValue {
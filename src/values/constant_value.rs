@@ -0,0 +1,152 @@
+use llvm_sys::core::{LLVMConstNull, LLVMGetElementAsConstant, LLVMGetIntTypeWidth, LLVMGetOperand, LLVMGetUndef, LLVMIsAConstantAggregateZero, LLVMIsAConstantArray, LLVMIsAConstantDataSequential, LLVMIsAConstantFP, LLVMIsAConstantInt, LLVMIsAConstantPointerNull, LLVMIsAConstantStruct, LLVMIsAConstantVector, LLVMIsAUndefValue, LLVMIsConstant, LLVMTypeOf};
+use llvm_sys::prelude::LLVMValueRef;
+
+use types::{AsTypeRef, BasicTypeEnum};
+use values::{AsValueRef, BasicValueEnum};
+
+/// A constant read back into a Rust value, ie for asserting against the
+/// initializer of a `GlobalValue`. See `BasicValueEnum::get_constant_value`.
+///
+/// Aggregates are read element by element, so a zero initialized array becomes
+/// an `Array` of zeros rather than being represented any differently.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::context::Context;
+/// use inkwell::values::ConstantValue;
+///
+/// let context = Context::create();
+/// let value = context.const_value(&(1u8, [2.5f64])).get_constant_value();
+///
+/// assert_eq!(value, Some(ConstantValue::Struct(vec![
+///     ConstantValue::Int { bit_width: 8, value: 1 },
+///     ConstantValue::Array(vec![ConstantValue::Float(2.5)]),
+/// ])));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstantValue {
+    /// An integer of up to 64 bits, zero extended.
+    Int {
+        /// The width of the integer's type in bits.
+        bit_width: u32,
+        /// The integer's zero extended value.
+        value: u64,
+    },
+    /// A floating point number, which may have lost precision if its type is wider than a double.
+    Float(f64),
+    /// A null pointer.
+    NullPointer,
+    /// An undefined value of any type.
+    Undef,
+    /// The elements of a constant array.
+    Array(Vec<ConstantValue>),
+    /// The fields of a constant struct.
+    Struct(Vec<ConstantValue>),
+    /// The elements of a constant vector.
+    Vector(Vec<ConstantValue>),
+    /// A constant which can't be read into a Rust value, such as the address of a global,
+    /// a constant expression or an integer wider than 64 bits.
+    Other(BasicValueEnum),
+}
+
+impl ConstantValue {
+    pub(crate) fn new(value: BasicValueEnum) -> Option<Self> {
+        let value_ref = value.as_value_ref();
+
+        unsafe {
+            if LLVMIsConstant(value_ref) == 0 {
+                return None;
+            }
+
+            if !LLVMIsAUndefValue(value_ref).is_null() {
+                return Some(ConstantValue::Undef);
+            }
+        }
+
+        let constant = match value {
+            BasicValueEnum::IntValue(int_value) => {
+                let bit_width = unsafe {
+                    LLVMGetIntTypeWidth(LLVMTypeOf(value_ref))
+                };
+                let is_const_int = unsafe {
+                    !LLVMIsAConstantInt(value_ref).is_null()
+                };
+
+                // Constant expressions and wider integers can't be read without LLVM asserting
+                if !is_const_int || bit_width > 64 {
+                    return Some(ConstantValue::Other(value));
+                }
+
+                ConstantValue::Int { bit_width, value: int_value.get_zero_extended_constant()? }
+            },
+            BasicValueEnum::FloatValue(float_value) => {
+                let is_const_fp = unsafe {
+                    !LLVMIsAConstantFP(value_ref).is_null()
+                };
+
+                if !is_const_fp {
+                    return Some(ConstantValue::Other(value));
+                }
+
+                ConstantValue::Float(float_value.get_constant()?.0)
+            },
+            BasicValueEnum::PointerValue(_) => {
+                let is_null = unsafe {
+                    !LLVMIsAConstantPointerNull(value_ref).is_null()
+                };
+
+                if is_null { ConstantValue::NullPointer } else { ConstantValue::Other(value) }
+            },
+            BasicValueEnum::ArrayValue(array_value) => {
+                let len = array_value.get_type().len();
+
+                ConstantValue::from_elements(len, |index| array_value.get_element_as_constant(index))
+                    .map_or(ConstantValue::Other(value), ConstantValue::Array)
+            },
+            BasicValueEnum::StructValue(struct_value) => {
+                let count = struct_value.get_type().count_fields();
+
+                ConstantValue::from_elements(count, |index| struct_value.get_element_as_constant(index))
+                    .map_or(ConstantValue::Other(value), ConstantValue::Struct)
+            },
+            BasicValueEnum::VectorValue(vector_value) => {
+                let vector_type = vector_value.get_type();
+                let element_type = vector_type.get_element_type();
+
+                ConstantValue::from_elements(vector_type.get_size(), |index| get_constant_element(value_ref, index, &element_type))
+                    .map_or(ConstantValue::Other(value), ConstantValue::Vector)
+            },
+        };
+
+        Some(constant)
+    }
+
+    // None if any element can't be read, so that the whole aggregate is Other
+    fn from_elements<F>(len: u32, get_element: F) -> Option<Vec<ConstantValue>>
+    where
+        F: Fn(u32) -> Option<BasicValueEnum>,
+    {
+        (0..len).map(|index| get_element(index).and_then(ConstantValue::new)).collect()
+    }
+}
+
+/// Gets the element of a constant array, struct or vector at an index which must be in
+/// bounds, or `None` if it's something else such as a constant expression.
+pub(crate) fn get_constant_element(value: LLVMValueRef, index: u32, element_type: &BasicTypeEnum) -> Option<BasicValueEnum> {
+    let element = unsafe {
+        if !LLVMIsAConstantDataSequential(value).is_null() {
+            LLVMGetElementAsConstant(value, index)
+        } else if !LLVMIsAConstantArray(value).is_null() || !LLVMIsAConstantStruct(value).is_null() || !LLVMIsAConstantVector(value).is_null() {
+            LLVMGetOperand(value, index)
+        } else if !LLVMIsAConstantAggregateZero(value).is_null() {
+            LLVMConstNull(element_type.as_type_ref())
+        } else if !LLVMIsAUndefValue(value).is_null() {
+            LLVMGetUndef(element_type.as_type_ref())
+        } else {
+            return None;
+        }
+    };
+
+    Some(BasicValueEnum::new(element))
+}
@@ -4,7 +4,7 @@ use llvm_sys::prelude::LLVMValueRef;
 
 use types::{AnyTypeEnum, BasicTypeEnum};
 use values::traits::AsValueRef;
-use values::{IntValue, FunctionValue, PointerValue, VectorValue, ArrayValue, StructValue, FloatValue, PhiValue, InstructionValue, MetadataValue};
+use values::{ConstantValue, IntValue, FunctionValue, PointerValue, VectorValue, ArrayValue, StructValue, FloatValue, PhiValue, InstructionValue, MetadataValue};
 
 macro_rules! enum_value_set {
     ($enum_name:ident: $($args:ident),*) => (
@@ -115,6 +115,33 @@ impl BasicValueEnum {
 
         BasicTypeEnum::new(type_)
     }
+
+    /// Reads a constant into a `ConstantValue`, recursing into the elements of
+    /// arrays, structs and vectors. Returns `None` if the value isn't a constant.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::values::ConstantValue;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let i32_type = context.i32_type();
+    /// let global = module.add_global(i32_type.array_type(2), None, "table");
+    ///
+    /// global.set_initializer(&context.const_value(&[3i32, 4]));
+    ///
+    /// let initializer = global.get_initializer().unwrap().get_constant_value();
+    ///
+    /// assert_eq!(initializer, Some(ConstantValue::Array(vec![
+    ///     ConstantValue::Int { bit_width: 32, value: 3 },
+    ///     ConstantValue::Int { bit_width: 32, value: 4 },
+    /// ])));
+    /// ```
+    pub fn get_constant_value(&self) -> Option<ConstantValue> {
+        ConstantValue::new(*self)
+    }
 }
 
 impl AggregateValueEnum {
@@ -1,5 +1,5 @@
 use llvm_sys::analysis::{LLVMVerifierFailureAction, LLVMVerifyFunction, LLVMViewFunctionCFG, LLVMViewFunctionCFGOnly};
-use llvm_sys::core::{LLVMIsAFunction, LLVMIsConstant, LLVMGetLinkage, LLVMTypeOf, LLVMGetPreviousFunction, LLVMGetNextFunction, LLVMGetParam, LLVMCountParams, LLVMGetLastParam, LLVMCountBasicBlocks, LLVMGetFirstParam, LLVMGetNextParam, LLVMGetBasicBlocks, LLVMGetReturnType, LLVMAppendBasicBlock, LLVMDeleteFunction, LLVMGetElementType, LLVMGetLastBasicBlock, LLVMGetFirstBasicBlock, LLVMGetEntryBasicBlock, LLVMGetIntrinsicID, LLVMGetFunctionCallConv, LLVMSetFunctionCallConv, LLVMGetGC, LLVMSetGC, LLVMSetLinkage, LLVMSetParamAlignment, LLVMGetParams};
+use llvm_sys::core::{LLVMIsAFunction, LLVMIsConstant, LLVMGetLinkage, LLVMTypeOf, LLVMGetPreviousFunction, LLVMGetNextFunction, LLVMGetParam, LLVMCountParams, LLVMGetLastParam, LLVMCountBasicBlocks, LLVMGetFirstParam, LLVMGetNextParam, LLVMGetBasicBlocks, LLVMGetReturnType, LLVMAppendBasicBlock, LLVMDeleteFunction, LLVMGetElementType, LLVMGetLastBasicBlock, LLVMGetFirstBasicBlock, LLVMGetEntryBasicBlock, LLVMGetIntrinsicID, LLVMGetFunctionCallConv, LLVMSetFunctionCallConv, LLVMGetGC, LLVMSetGC, LLVMSetLinkage, LLVMSetParamAlignment, LLVMGetParams, LLVMGetNextBasicBlock, LLVMGetPreviousBasicBlock};
 #[llvm_versions(3.7 => latest)]
 use llvm_sys::core::{LLVMGetPersonalityFn, LLVMSetPersonalityFn};
 #[llvm_versions(3.9 => latest)]
@@ -13,8 +13,10 @@ use std::fmt;
 #[llvm_versions(3.9 => latest)]
 use attributes::Attribute;
 use basic_block::BasicBlock;
+#[llvm_versions(7.0 => latest)]
+use debug_info::DISubprogram;
 use module::Linkage;
-use support::LLVMString;
+use support::{LLVMString, next_in_list};
 use types::{BasicTypeEnum, FunctionType};
 use values::traits::AsValueRef;
 use values::{BasicValueEnum, GlobalValue, Value, MetadataValue};
@@ -82,7 +84,6 @@ impl FunctionValue {
         code != 1
     }
 
-    // REVIEW: If there's a demand, could easily create a module.get_functions() -> Iterator
     pub fn get_next_function(&self) -> Option<Self> {
         let function = unsafe {
             LLVMGetNextFunction(self.as_value_ref())
@@ -201,6 +202,36 @@ impl FunctionValue {
         raw_vec.iter().map(|val| BasicBlock::new(*val).unwrap()).collect()
     }
 
+    /// Gets an iterator over the `BasicBlock`s of this function, in order. Unlike
+    /// `get_basic_blocks`, this does not collect them into a `Vec` up front and
+    /// may also be walked from the last block.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let fn_type = context.void_type().fn_type(&[], false);
+    /// let fn_value = module.add_function("my_fn", fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    /// let exit = fn_value.append_basic_block("exit");
+    ///
+    /// assert_eq!(fn_value.get_basic_blocks_iter().collect::<Vec<_>>(), vec![entry, exit]);
+    /// assert_eq!(fn_value.get_basic_blocks_iter().rev().next().unwrap(), fn_value.get_last_basic_block().unwrap());
+    /// ```
+    pub fn get_basic_blocks_iter(&self) -> BasicBlockIter {
+        let (front, back) = unsafe {
+            (LLVMGetFirstBasicBlock(self.as_value_ref()), LLVMGetLastBasicBlock(self.as_value_ref()))
+        };
+
+        BasicBlockIter {
+            front,
+            back,
+        }
+    }
+
     pub fn get_return_type(&self) -> BasicTypeEnum {
         let type_ = unsafe {
             LLVMGetReturnType(LLVMGetElementType(LLVMTypeOf(self.fn_value.value)))
@@ -262,7 +293,12 @@ impl FunctionValue {
     }
 
     pub fn get_type(&self) -> FunctionType {
-        FunctionType::new(self.fn_value.get_type())
+        // A function is a global, so its value's type is a pointer to its FunctionType
+        let fn_type = unsafe {
+            LLVMGetElementType(LLVMTypeOf(self.fn_value.value))
+        };
+
+        FunctionType::new(fn_type)
     }
 
     pub fn has_metadata(&self) -> bool {
@@ -326,6 +362,34 @@ impl FunctionValue {
         }
     }
 
+    /// Attaches the debug info of this function.
+    #[llvm_versions(7.0 => latest)]
+    pub fn set_subprogram(&self, subprogram: DISubprogram) {
+        use llvm_sys::debuginfo::LLVMSetSubprogram;
+
+        unsafe {
+            LLVMSetSubprogram(self.as_value_ref(), subprogram.metadata_ref)
+        }
+    }
+
+    /// Gets the debug info attached to this function, if any.
+    #[llvm_versions(7.0 => latest)]
+    pub fn get_subprogram(&self) -> Option<DISubprogram<'_>> {
+        use llvm_sys::debuginfo::LLVMGetSubprogram;
+
+        let metadata_ref = unsafe {
+            LLVMGetSubprogram(self.as_value_ref())
+        };
+
+        if metadata_ref.is_null() {
+            return None;
+        }
+
+        Some(DISubprogram::new(metadata_ref))
+    }
+
+    /// Gets the ID of the intrinsic this function is a declaration of, or 0 if it
+    /// isn't one. Intrinsics can be declared with [`Intrinsic::get_declaration`](../intrinsics/struct.Intrinsic.html#method.get_declaration).
     pub fn get_intrinsic_id(&self) -> u32 {
         unsafe {
             LLVMGetIntrinsicID(self.as_value_ref())
@@ -614,3 +678,24 @@ impl Iterator for ParamValueIter {
         Some(Self::Item::new(next_value))
     }
 }
+
+/// An iterator over the `BasicBlock`s of a `FunctionValue`, created by `FunctionValue::get_basic_blocks_iter`.
+#[derive(Debug)]
+pub struct BasicBlockIter {
+    front: LLVMBasicBlockRef,
+    back: LLVMBasicBlockRef,
+}
+
+impl Iterator for BasicBlockIter {
+    type Item = BasicBlock;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        next_in_list(&mut self.front, &mut self.back, LLVMGetNextBasicBlock).and_then(BasicBlock::new)
+    }
+}
+
+impl DoubleEndedIterator for BasicBlockIter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        next_in_list(&mut self.back, &mut self.front, LLVMGetPreviousBasicBlock).and_then(BasicBlock::new)
+    }
+}
@@ -0,0 +1,194 @@
+use llvm_sys::core::{LLVMAliasGetAliasee, LLVMAliasSetAliasee, LLVMGetLinkage, LLVMSetLinkage, LLVMGetVisibility, LLVMSetVisibility};
+use llvm_sys::prelude::LLVMValueRef;
+
+use std::ffi::CStr;
+
+use GlobalVisibility;
+use module::Linkage;
+use support::LLVMString;
+use values::{AsValueRef, GlobalValue, PointerValue, Value};
+
+/// A `GlobalAliasValue` is a global symbol which introduces a second name for
+/// another global value (its aliasee), such as a function or global variable.
+/// It does not have any storage of its own.
+///
+/// Ifuncs, which are similar but name a resolver function which picks the aliasee
+/// when the program is loaded, aren't supported as llvm-sys 70 doesn't bind
+/// `LLVMAddGlobalIFunc` or any of the C API for them.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct GlobalAliasValue {
+    global_alias_value: Value,
+}
+
+impl GlobalAliasValue {
+    pub(crate) fn new(value: LLVMValueRef) -> Self {
+        assert!(!value.is_null());
+
+        GlobalAliasValue {
+            global_alias_value: Value::new(value),
+        }
+    }
+
+    /// Gets the name of this `GlobalAliasValue`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let global = module.add_global(context.i32_type(), None, "my_global");
+    /// let alias = module.add_global_alias(global.as_pointer_value(), "my_alias").unwrap();
+    ///
+    /// assert_eq!(alias.get_name().to_str(), Ok("my_alias"));
+    /// ```
+    pub fn get_name(&self) -> &CStr {
+        self.global_alias_value.get_name()
+    }
+
+    /// Gets the value this `GlobalAliasValue` is an alias of, which is either a global
+    /// or a constant expression derived from one.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let global = module.add_global(context.i32_type(), None, "my_global");
+    /// let alias = module.add_global_alias(global.as_pointer_value(), "my_alias").unwrap();
+    ///
+    /// assert_eq!(alias.get_aliasee(), global.as_pointer_value());
+    /// ```
+    pub fn get_aliasee(&self) -> PointerValue {
+        let value = unsafe {
+            LLVMAliasGetAliasee(self.as_value_ref())
+        };
+
+        PointerValue::new(value)
+    }
+
+    /// Makes this `GlobalAliasValue` an alias of another value. The aliasee should have
+    /// the same type as the alias for the module to remain valid. Returns false without
+    /// changing the aliasee if `aliasee` isn't a constant, as an alias can't refer to
+    /// instructions or arguments.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let i32_type = context.i32_type();
+    /// let global = module.add_global(i32_type, None, "my_global");
+    /// let other_global = module.add_global(i32_type, None, "my_other_global");
+    /// let alias = module.add_global_alias(global.as_pointer_value(), "my_alias").unwrap();
+    ///
+    /// assert!(alias.set_aliasee(other_global.as_pointer_value()));
+    ///
+    /// assert_eq!(alias.get_aliasee(), other_global.as_pointer_value());
+    /// ```
+    pub fn set_aliasee(&self, aliasee: PointerValue) -> bool {
+        if !aliasee.is_const() {
+            return false;
+        }
+
+        unsafe {
+            LLVMAliasSetAliasee(self.as_value_ref(), aliasee.as_value_ref())
+        }
+
+        true
+    }
+
+    /// Gets the `Linkage` of this `GlobalAliasValue`.
+    pub fn get_linkage(&self) -> Linkage {
+        let linkage = unsafe {
+            LLVMGetLinkage(self.as_value_ref())
+        };
+
+        Linkage::new(linkage)
+    }
+
+    /// Sets the `Linkage` of this `GlobalAliasValue`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    /// use inkwell::module::Linkage;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let global = module.add_global(context.i32_type(), None, "my_global");
+    /// let alias = module.add_global_alias(global.as_pointer_value(), "my_alias").unwrap();
+    ///
+    /// assert_eq!(alias.get_linkage(), Linkage::External);
+    ///
+    /// alias.set_linkage(Linkage::Internal);
+    ///
+    /// assert_eq!(alias.get_linkage(), Linkage::Internal);
+    /// ```
+    pub fn set_linkage(&self, linkage: Linkage) {
+        unsafe {
+            LLVMSetLinkage(self.as_value_ref(), linkage.as_llvm_enum())
+        }
+    }
+
+    /// Gets the `GlobalVisibility` of this `GlobalAliasValue`.
+    pub fn get_visibility(&self) -> GlobalVisibility {
+        let visibility = unsafe {
+            LLVMGetVisibility(self.as_value_ref())
+        };
+
+        GlobalVisibility::new(visibility)
+    }
+
+    /// Sets the `GlobalVisibility` of this `GlobalAliasValue`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::GlobalVisibility;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let global = module.add_global(context.i32_type(), None, "my_global");
+    /// let alias = module.add_global_alias(global.as_pointer_value(), "my_alias").unwrap();
+    ///
+    /// alias.set_visibility(GlobalVisibility::Hidden);
+    ///
+    /// assert_eq!(alias.get_visibility(), GlobalVisibility::Hidden);
+    /// ```
+    pub fn set_visibility(&self, visibility: GlobalVisibility) {
+        unsafe {
+            LLVMSetVisibility(self.as_value_ref(), visibility.as_llvm_enum())
+        }
+    }
+
+    /// Gets this `GlobalAliasValue` as a `PointerValue`, so that it may be used
+    /// in place of its aliasee, ie as an instruction operand or another alias' aliasee.
+    pub fn as_pointer_value(&self) -> PointerValue {
+        PointerValue::new(self.as_value_ref())
+    }
+
+    /// Gets this `GlobalAliasValue` as a `GlobalValue`, in order to access
+    /// the properties it shares with other globals (ie DLL storage class).
+    pub fn as_global_value(&self) -> GlobalValue {
+        GlobalValue::new(self.as_value_ref())
+    }
+
+    /// Prints this `GlobalAliasValue` to an `LLVMString`.
+    pub fn print_to_string(&self) -> LLVMString {
+        self.global_alias_value.print_to_string()
+    }
+}
+
+impl AsValueRef for GlobalAliasValue {
+    fn as_value_ref(&self) -> LLVMValueRef {
+        self.global_alias_value.value
+    }
+}
@@ -1,10 +1,11 @@
 use either::{Either, Either::{Left, Right}};
-use llvm_sys::core::{LLVMGetInstructionOpcode, LLVMIsTailCall, LLVMGetPreviousInstruction, LLVMGetNextInstruction, LLVMGetInstructionParent, LLVMInstructionEraseFromParent, LLVMInstructionClone, LLVMSetVolatile, LLVMGetVolatile, LLVMGetNumOperands, LLVMGetOperand, LLVMGetOperandUse, LLVMSetOperand, LLVMValueAsBasicBlock, LLVMIsABasicBlock};
+use llvm_sys::core::{LLVMGetInstructionOpcode, LLVMIsTailCall, LLVMGetPreviousInstruction, LLVMGetNextInstruction, LLVMGetInstructionParent, LLVMInstructionEraseFromParent, LLVMInstructionClone, LLVMSetVolatile, LLVMGetVolatile, LLVMGetNumOperands, LLVMGetOperand, LLVMGetOperandUse, LLVMSetOperand, LLVMValueAsBasicBlock, LLVMIsABasicBlock, LLVMGetAlignment, LLVMSetAlignment, LLVMGetOrdering, LLVMSetOrdering};
 #[llvm_versions(3.9 => latest)]
 use llvm_sys::core::LLVMInstructionRemoveFromParent;
 use llvm_sys::LLVMOpcode;
 use llvm_sys::prelude::LLVMValueRef;
 
+use AtomicOrdering;
 use basic_block::BasicBlock;
 use values::traits::AsValueRef;
 use values::{BasicValue, BasicValueEnum, BasicValueUse, Value};
@@ -342,6 +343,142 @@ impl InstructionValue {
         }
     }
 
+    // SubTypes: Only apply to memory access and alloca instructions
+    /// Returns the alignment of an alloca, load or store instruction, where 0 means
+    /// the ABI alignment of the type is used.
+    pub fn get_alignment(&self) -> Result<u32, &'static str> {
+        match self.get_opcode() {
+            InstructionOpcode::Alloca | InstructionOpcode::Load | InstructionOpcode::Store => {},
+            _ => return Err("Only alloca, load and store instructions have an alignment"),
+        }
+
+        Ok(unsafe {
+            LLVMGetAlignment(self.as_value_ref())
+        })
+    }
+
+    // SubTypes: Only apply to memory access and alloca instructions
+    /// Sets the alignment of an alloca, load or store instruction. The alignment
+    /// must be 0 or a power of two.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::{AddressSpace, AtomicOrdering};
+    /// use inkwell::context::Context;
+    /// use inkwell::values::BasicValue;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i32_type = context.i32_type();
+    /// let fn_type = i32_type.fn_type(&[i32_type.ptr_type(AddressSpace::Generic).into()], false);
+    /// let fn_value = module.add_function("my_fn", fn_type, None);
+    /// let entry = fn_value.append_basic_block("entry");
+    ///
+    /// builder.position_at_end(&entry);
+    ///
+    /// let ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    /// let load = builder.build_load(ptr, "load");
+    /// let load_instruction = load.as_instruction_value().unwrap();
+    ///
+    /// load_instruction.set_alignment(4).unwrap();
+    /// load_instruction.set_atomic_ordering(AtomicOrdering::Acquire).unwrap();
+    ///
+    /// assert_eq!(load_instruction.get_alignment(), Ok(4));
+    /// assert_eq!(load_instruction.get_atomic_ordering(), Ok(AtomicOrdering::Acquire));
+    /// ```
+    pub fn set_alignment(&self, alignment: u32) -> Result<(), &'static str> {
+        match self.get_opcode() {
+            InstructionOpcode::Alloca | InstructionOpcode::Load | InstructionOpcode::Store => {},
+            _ => return Err("Only alloca, load and store instructions have an alignment"),
+        }
+
+        if !alignment.is_power_of_two() && alignment != 0 {
+            return Err("Alignment must be 0 or a power of two");
+        }
+
+        unsafe {
+            LLVMSetAlignment(self.as_value_ref(), alignment)
+        }
+
+        Ok(())
+    }
+
+    // SubTypes: Only apply to load and store instructions
+    /// Returns the atomic ordering of a load or store instruction.
+    pub fn get_atomic_ordering(&self) -> Result<AtomicOrdering, &'static str> {
+        match self.get_opcode() {
+            InstructionOpcode::Load | InstructionOpcode::Store => {},
+            _ => return Err("Only load and store instructions have an atomic ordering"),
+        }
+
+        let ordering = unsafe {
+            LLVMGetOrdering(self.as_value_ref())
+        };
+
+        Ok(AtomicOrdering::new(ordering))
+    }
+
+    // SubTypes: Only apply to load and store instructions
+    /// Sets the atomic ordering of a load or store instruction. Loads may not be
+    /// `Release` or `AcquireRelease`, and stores may not be `Acquire` or `AcquireRelease`.
+    /// Note that atomic loads and stores must also be given an explicit alignment.
+    pub fn set_atomic_ordering(&self, ordering: AtomicOrdering) -> Result<(), &'static str> {
+        match (self.get_opcode(), ordering) {
+            (InstructionOpcode::Load, AtomicOrdering::Release) |
+            (InstructionOpcode::Load, AtomicOrdering::AcquireRelease) => return Err("Loads may not have release or acquire-release ordering"),
+            (InstructionOpcode::Store, AtomicOrdering::Acquire) |
+            (InstructionOpcode::Store, AtomicOrdering::AcquireRelease) => return Err("Stores may not have acquire or acquire-release ordering"),
+            (InstructionOpcode::Load, _) | (InstructionOpcode::Store, _) => {},
+            _ => return Err("Only load and store instructions have an atomic ordering"),
+        }
+
+        unsafe {
+            LLVMSetOrdering(self.as_value_ref(), ordering.as_llvm_enum())
+        }
+
+        Ok(())
+    }
+
+    // REVIEW: LLVM's C API only exposes the single thread sync scope on these instructions
+    /// Returns whether an atomicrmw or cmpxchg instruction is only synchronized with the
+    /// current thread (the "singlethread" sync scope), rather than with all threads.
+    #[llvm_versions(3.9 => latest)]
+    pub fn is_atomic_single_thread(&self) -> Result<bool, &'static str> {
+        // llvm-sys binds this as LLVMIsAtomicSinglethread, which is not the exported symbol
+        extern "C" {
+            fn LLVMIsAtomicSingleThread(atomic_inst: LLVMValueRef) -> i32;
+        }
+
+        match self.get_opcode() {
+            InstructionOpcode::AtomicRMW | InstructionOpcode::AtomicCmpXchg => {},
+            _ => return Err("Only atomicrmw and cmpxchg instructions have a sync scope"),
+        }
+
+        Ok(unsafe {
+            LLVMIsAtomicSingleThread(self.as_value_ref()) == 1
+        })
+    }
+
+    /// Sets whether an atomicrmw or cmpxchg instruction is only synchronized with the
+    /// current thread (the "singlethread" sync scope), rather than with all threads.
+    #[llvm_versions(3.9 => latest)]
+    pub fn set_atomic_single_thread(&self, single_thread: bool) -> Result<(), &'static str> {
+        use llvm_sys::core::LLVMSetAtomicSingleThread;
+
+        match self.get_opcode() {
+            InstructionOpcode::AtomicRMW | InstructionOpcode::AtomicCmpXchg => {},
+            _ => return Err("Only atomicrmw and cmpxchg instructions have a sync scope"),
+        }
+
+        unsafe {
+            LLVMSetAtomicSingleThread(self.as_value_ref(), single_thread as i32)
+        }
+
+        Ok(())
+    }
+
     /// Obtains the number of operands an `InstructionValue` has.
     /// An operand is a `BasicValue` used in an IR instruction.
     ///
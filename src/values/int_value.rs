@@ -1,4 +1,4 @@
-use llvm_sys::core::{LLVMConstNot, LLVMConstNeg, LLVMConstNSWNeg, LLVMConstNUWNeg, LLVMConstAdd, LLVMConstNSWAdd, LLVMConstNUWAdd, LLVMConstSub, LLVMConstNSWSub, LLVMConstNUWSub, LLVMConstMul, LLVMConstNSWMul, LLVMConstNUWMul, LLVMConstUDiv, LLVMConstSDiv, LLVMConstSRem, LLVMConstURem, LLVMConstIntCast, LLVMConstXor, LLVMConstOr, LLVMConstAnd, LLVMConstExactSDiv, LLVMConstShl, LLVMConstLShr, LLVMConstAShr, LLVMConstUIToFP, LLVMConstSIToFP, LLVMConstIntToPtr, LLVMConstTrunc, LLVMConstSExt, LLVMConstZExt, LLVMConstTruncOrBitCast, LLVMConstSExtOrBitCast, LLVMConstZExtOrBitCast, LLVMConstBitCast, LLVMConstICmp, LLVMConstIntGetZExtValue, LLVMConstIntGetSExtValue, LLVMConstSelect};
+use llvm_sys::core::{LLVMConstNot, LLVMConstNeg, LLVMConstNSWNeg, LLVMConstNUWNeg, LLVMConstAdd, LLVMConstNSWAdd, LLVMConstNUWAdd, LLVMConstSub, LLVMConstNSWSub, LLVMConstNUWSub, LLVMConstMul, LLVMConstNSWMul, LLVMConstNUWMul, LLVMConstUDiv, LLVMConstSDiv, LLVMConstSRem, LLVMConstURem, LLVMConstIntCast, LLVMConstXor, LLVMConstOr, LLVMConstAnd, LLVMConstExactSDiv, LLVMConstShl, LLVMConstLShr, LLVMConstAShr, LLVMConstUIToFP, LLVMConstSIToFP, LLVMConstIntToPtr, LLVMConstTrunc, LLVMConstSExt, LLVMConstZExt, LLVMConstTruncOrBitCast, LLVMConstSExtOrBitCast, LLVMConstZExtOrBitCast, LLVMConstBitCast, LLVMConstICmp, LLVMConstIntGetZExtValue, LLVMConstIntGetSExtValue, LLVMConstSelect, LLVMIsAConstantInt};
 #[llvm_versions(4.0 => latest)]
 use llvm_sys::core::LLVMConstExactUDiv;
 use llvm_sys::prelude::LLVMValueRef;
@@ -402,7 +402,8 @@ impl IntValue {
         self.int_value.is_const()
     }
 
-    /// Obtains a constant `IntValue`'s zero extended value.
+    /// Obtains a constant `IntValue`'s zero extended value. Values wider than 64 bits
+    /// may be read with `get_constant_words` instead.
     ///
     /// # Example
     ///
@@ -450,6 +451,101 @@ impl IntValue {
         }
     }
 
+    /// Obtains a constant `IntValue`'s value as 64 bit words, least significant first,
+    /// in the same form as `IntType::const_int_arbitrary_precision` takes. Returns `None`
+    /// if the value isn't a constant integer, ie is a constant expression.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i128_type = context.i128_type();
+    /// let i128_val = i128_type.const_int_arbitrary_precision(&[1, 2]);
+    ///
+    /// assert_eq!(i128_val.get_constant_words(), Some(vec![1, 2]));
+    /// ```
+    pub fn get_constant_words(&self) -> Option<Vec<u64>> {
+        // Garbage values are produced, or LLVM asserts, on anything else
+        let is_const_int = unsafe {
+            !LLVMIsAConstantInt(self.as_value_ref()).is_null()
+        };
+
+        if !is_const_int {
+            return None;
+        }
+
+        let int_type = self.get_type();
+        let bit_width = int_type.get_bit_width();
+
+        if bit_width <= 64 {
+            return self.get_zero_extended_constant().map(|word| vec![word]);
+        }
+
+        // LLVM can only hand out 64 bits at a time, so each word is shifted down and truncated
+        // by constant folding, which always produces a constant integer here
+        let i64_type = int_type.get_context().i64_type();
+        let words = (0..=(bit_width - 1) / 64).map(|index| {
+            let shift = int_type.const_int(u64::from(index) * 64, false);
+            let word = unsafe {
+                LLVMConstTrunc(LLVMConstLShr(self.as_value_ref(), shift.as_value_ref()), i64_type.as_type_ref())
+            };
+
+            unsafe {
+                LLVMConstIntGetZExtValue(word)
+            }
+        });
+
+        Some(words.collect())
+    }
+
+    /// Obtains a constant `IntValue`'s zero extended value as a `u128`. Returns `None` if
+    /// the value isn't a constant integer or its type is wider than 128 bits.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i8_type = context.i8_type();
+    /// let i8_all_ones = i8_type.const_all_ones();
+    ///
+    /// assert_eq!(i8_all_ones.get_u128_constant(), Some(255));
+    /// ```
+    pub fn get_u128_constant(&self) -> Option<u128> {
+        if self.get_type().get_bit_width() > 128 {
+            return None;
+        }
+
+        let words = self.get_constant_words()?;
+
+        Some(words.iter().rev().fold(0, |value, &word| value << 64 | u128::from(word)))
+    }
+
+    /// Obtains a constant `IntValue`'s sign extended value as an `i128`. Returns `None` if
+    /// the value isn't a constant integer or its type is wider than 128 bits.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i8_type = context.i8_type();
+    /// let i8_all_ones = i8_type.const_all_ones();
+    ///
+    /// assert_eq!(i8_all_ones.get_i128_constant(), Some(-1));
+    /// ```
+    pub fn get_i128_constant(&self) -> Option<i128> {
+        let value = self.get_u128_constant()? as i128;
+        let unused_bits = 128 - self.get_type().get_bit_width();
+
+        // Shifting the sign bit to the top and back sign extends it
+        Some(value << unused_bits >> unused_bits)
+    }
+
     pub fn replace_all_uses_with(&self, other: IntValue) {
         self.int_value.replace_all_uses_with(other.as_value_ref())
     }
@@ -0,0 +1,183 @@
+use llvm_sys::core::{LLVMConstArray, LLVMConstStringInContext};
+use llvm_sys::prelude::LLVMValueRef;
+
+use std::ffi::CStr;
+
+use context::Context;
+use types::{AsTypeRef, RustType};
+use values::{AsValueRef, BasicValue, BasicValueEnum};
+
+/// A Rust value which can be turned into an equivalent LLVM constant, ie
+/// for emitting lookup tables. See `Context::const_value`.
+///
+/// Constants have the same types as `RustType` gives, so a `bool` is an `i8`
+/// as it is in memory. Slices and arrays become constant arrays, tuples become
+/// constant (unpacked) structs, and strings become `i8` arrays, which are only
+/// null terminated when coming from a `CStr`.
+///
+/// # Example
+///
+/// ```no_run
+/// use inkwell::context::Context;
+/// use inkwell::values::IntoConst;
+///
+/// let context = Context::create();
+/// let value = (1u8, 2.5f32).const_value(&context).into_struct_value();
+///
+/// assert_eq!(value.get_type(), context.struct_type(&[context.i8_type().into(), context.f32_type().into()], false));
+/// ```
+pub trait IntoConst {
+    /// Creates the LLVM constant equivalent to this value.
+    fn const_value(&self, context: &Context) -> BasicValueEnum;
+}
+
+macro_rules! impl_into_const_for_ints {
+    ($($int_type:ty),*) => (
+        $(
+            impl IntoConst for $int_type {
+                fn const_value(&self, context: &Context) -> BasicValueEnum {
+                    let int_type = <$int_type as RustType>::basic_type(context).into_int_type();
+
+                    // Sign extending first means negative values are correctly represented at any width
+                    int_type.const_int(*self as i64 as u64, false).as_basic_value_enum()
+                }
+            }
+        )*
+    );
+}
+
+impl_into_const_for_ints! {i8, u8, i16, u16, i32, u32, i64, u64, isize, usize}
+
+impl IntoConst for i128 {
+    fn const_value(&self, context: &Context) -> BasicValueEnum {
+        context.i128_type().const_i128(*self).as_basic_value_enum()
+    }
+}
+
+impl IntoConst for u128 {
+    fn const_value(&self, context: &Context) -> BasicValueEnum {
+        context.i128_type().const_u128(*self).as_basic_value_enum()
+    }
+}
+
+impl IntoConst for bool {
+    fn const_value(&self, context: &Context) -> BasicValueEnum {
+        context.i8_type().const_int(*self as u64, false).as_basic_value_enum()
+    }
+}
+
+impl IntoConst for char {
+    fn const_value(&self, context: &Context) -> BasicValueEnum {
+        context.i32_type().const_int(*self as u64, false).as_basic_value_enum()
+    }
+}
+
+impl IntoConst for f32 {
+    fn const_value(&self, context: &Context) -> BasicValueEnum {
+        context.f32_type().const_float(f64::from(*self)).as_basic_value_enum()
+    }
+}
+
+impl IntoConst for f64 {
+    fn const_value(&self, context: &Context) -> BasicValueEnum {
+        context.f64_type().const_float(*self).as_basic_value_enum()
+    }
+}
+
+fn const_bytes(context: &Context, bytes: &[u8]) -> BasicValueEnum {
+    // Null termination is up to the caller, as the bytes may already include it
+    let value = unsafe {
+        LLVMConstStringInContext(*context.context, bytes.as_ptr() as *const i8, bytes.len() as u32, true as i32)
+    };
+
+    BasicValueEnum::new(value)
+}
+
+impl IntoConst for str {
+    fn const_value(&self, context: &Context) -> BasicValueEnum {
+        const_bytes(context, self.as_bytes())
+    }
+}
+
+impl IntoConst for String {
+    fn const_value(&self, context: &Context) -> BasicValueEnum {
+        self.as_str().const_value(context)
+    }
+}
+
+impl IntoConst for CStr {
+    fn const_value(&self, context: &Context) -> BasicValueEnum {
+        const_bytes(context, self.to_bytes_with_nul())
+    }
+}
+
+// The element type comes from RustType rather than the first element so that empty slices work
+impl<T: IntoConst + RustType> IntoConst for [T] {
+    fn const_value(&self, context: &Context) -> BasicValueEnum {
+        let element_type = T::basic_type(context);
+        let mut values: Vec<LLVMValueRef> = self.iter()
+                                                .map(|value| value.const_value(context).as_value_ref())
+                                                .collect();
+        let value = unsafe {
+            LLVMConstArray(element_type.as_type_ref(), values.as_mut_ptr(), values.len() as u32)
+        };
+
+        BasicValueEnum::new(value)
+    }
+}
+
+impl<T: IntoConst + RustType> IntoConst for Vec<T> {
+    fn const_value(&self, context: &Context) -> BasicValueEnum {
+        self.as_slice().const_value(context)
+    }
+}
+
+macro_rules! impl_into_const_for_arrays {
+    ($($size:expr),*) => (
+        $(
+            impl<T: IntoConst + RustType> IntoConst for [T; $size] {
+                fn const_value(&self, context: &Context) -> BasicValueEnum {
+                    self[..].const_value(context)
+                }
+            }
+        )*
+    );
+}
+
+impl_into_const_for_arrays! {0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32}
+
+macro_rules! impl_into_const_for_tuples {
+    ($(($($name:ident),+)),*) => (
+        $(
+            impl<$($name: IntoConst),+> IntoConst for ($($name,)+) {
+                #[allow(non_snake_case)]
+                fn const_value(&self, context: &Context) -> BasicValueEnum {
+                    let ($(ref $name,)+) = *self;
+
+                    context.const_struct(&[$($name.const_value(context)),+], false).as_basic_value_enum()
+                }
+            }
+        )*
+    );
+}
+
+impl_into_const_for_tuples! {
+    (A),
+    (A, B),
+    (A, B, C),
+    (A, B, C, D),
+    (A, B, C, D, E),
+    (A, B, C, D, E, F),
+    (A, B, C, D, E, F, G),
+    (A, B, C, D, E, F, G, H),
+    (A, B, C, D, E, F, G, H, I),
+    (A, B, C, D, E, F, G, H, I, J),
+    (A, B, C, D, E, F, G, H, I, J, K),
+    (A, B, C, D, E, F, G, H, I, J, K, L)
+}
+
+impl<'a, T: IntoConst + ?Sized> IntoConst for &'a T {
+    fn const_value(&self, context: &Context) -> BasicValueEnum {
+        (**self).const_value(context)
+    }
+}
@@ -0,0 +1,142 @@
+use llvm_sys::core::{LLVMAddClause, LLVMIsAConstant, LLVMSetCleanup};
+use llvm_sys::prelude::LLVMValueRef;
+
+use std::ffi::CStr;
+
+use support::LLVMString;
+use values::traits::AsValueRef;
+use values::{BasicValue, BasicValueEnum, InstructionValue, Value};
+
+/// A landing pad instruction marks a basic block as the place where an invoke
+/// unwinds to, and produces the exception value selected by its personality function.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct LandingPadValue {
+    landing_pad_value: Value
+}
+
+impl LandingPadValue {
+    pub(crate) fn new(value: LLVMValueRef) -> Self {
+        assert!(!value.is_null());
+
+        LandingPadValue {
+            landing_pad_value: Value::new(value),
+        }
+    }
+
+    /// Adds a clause to this landing pad. A constant pointer to a type info is
+    /// treated as a catch clause, and a constant array of them as a filter clause.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the clause is not a constant.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let builder = context.create_builder();
+    /// let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    /// let i32_type = context.i32_type();
+    /// let exception_type = context.struct_type(&[i8_ptr_type.into(), i32_type.into()], false);
+    /// let personality = module.add_function("__gxx_personality_v0", i32_type.fn_type(&[], true), None);
+    /// let fn_value = module.add_function("my_fn", context.void_type().fn_type(&[], false), None);
+    /// let entry = fn_value.append_basic_block("entry");
+    ///
+    /// fn_value.set_personality_function(personality);
+    /// builder.position_at_end(&entry);
+    ///
+    /// let landing_pad = builder.build_landing_pad(exception_type, personality, "lpad");
+    ///
+    /// landing_pad.add_clause(i8_ptr_type.const_null());
+    /// ```
+    pub fn add_clause<BV: BasicValue>(&self, clause: BV) {
+        let is_const = unsafe {
+            !LLVMIsAConstant(clause.as_value_ref()).is_null()
+        };
+
+        assert!(is_const, "Landing pad clauses must be constants");
+
+        unsafe {
+            LLVMAddClause(self.as_value_ref(), clause.as_value_ref())
+        }
+    }
+
+    /// Gets the number of clauses this landing pad has.
+    #[llvm_versions(6.0 => latest)]
+    pub fn count_clauses(&self) -> u32 {
+        use llvm_sys::core::LLVMGetNumClauses;
+
+        unsafe {
+            LLVMGetNumClauses(self.as_value_ref())
+        }
+    }
+
+    /// Gets the clause at `index`, if it exists.
+    #[llvm_versions(6.0 => latest)]
+    pub fn get_clause(&self, index: u32) -> Option<BasicValueEnum> {
+        use llvm_sys::core::LLVMGetClause;
+
+        if index >= self.count_clauses() {
+            return None;
+        }
+
+        let value = unsafe {
+            LLVMGetClause(self.as_value_ref(), index)
+        };
+
+        Some(BasicValueEnum::new(value))
+    }
+
+    /// Determines whether this landing pad is entered even when no clause matches,
+    /// so that cleanup code may run.
+    #[llvm_versions(6.0 => latest)]
+    pub fn is_cleanup(&self) -> bool {
+        use llvm_sys::core::LLVMIsCleanup;
+
+        unsafe {
+            LLVMIsCleanup(self.as_value_ref()) == 1
+        }
+    }
+
+    /// Sets whether this landing pad is entered even when no clause matches.
+    pub fn set_cleanup(&self, is_cleanup: bool) {
+        unsafe {
+            LLVMSetCleanup(self.as_value_ref(), is_cleanup as i32)
+        }
+    }
+
+    pub fn get_name(&self) -> &CStr {
+        self.landing_pad_value.get_name()
+    }
+
+    pub fn set_name(&self, name: &str) {
+        self.landing_pad_value.set_name(name);
+    }
+
+    pub fn print_to_string(&self) -> LLVMString {
+        self.landing_pad_value.print_to_string()
+    }
+
+    pub fn print_to_stderr(&self) {
+        self.landing_pad_value.print_to_stderr()
+    }
+
+    // SubType: -> InstructionValue<LandingPad>
+    pub fn as_instruction(&self) -> InstructionValue {
+        self.landing_pad_value.as_instruction().expect("LandingPadValue should always be a LandingPad InstructionValue")
+    }
+
+    pub fn as_basic_value(&self) -> BasicValueEnum {
+        BasicValueEnum::new(self.as_value_ref())
+    }
+}
+
+impl AsValueRef for LandingPadValue {
+    fn as_value_ref(&self) -> LLVMValueRef {
+        self.landing_pad_value.value
+    }
+}
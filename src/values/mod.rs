@@ -5,13 +5,21 @@ mod array_value;
 mod basic_value_use;
 #[deny(missing_docs)]
 mod call_site_value;
+#[deny(missing_docs)]
+mod constant_value;
 mod enums;
 mod float_value;
 mod fn_value;
 mod generic_value;
+#[cfg(not(any(feature = "llvm3-6", feature = "llvm3-7", feature = "llvm3-8", feature = "llvm3-9", feature = "llvm4-0", feature = "llvm5-0", feature = "llvm6-0")))]
+#[deny(missing_docs)]
+mod global_alias_value;
 mod global_value;
 mod instruction_value;
 mod int_value;
+#[deny(missing_docs)]
+mod into_const;
+mod landing_pad_value;
 mod metadata_value;
 mod phi_value;
 mod ptr_value;
@@ -22,18 +30,25 @@ mod vec_value;
 pub use values::array_value::ArrayValue;
 pub use values::basic_value_use::BasicValueUse;
 pub use values::call_site_value::CallSiteValue;
+pub use values::constant_value::ConstantValue;
+pub(crate) use values::constant_value::get_constant_element;
 pub use values::enums::{AnyValueEnum, AggregateValueEnum, BasicValueEnum, BasicMetadataValueEnum};
 pub use values::float_value::FloatValue;
-pub use values::fn_value::FunctionValue;
+pub use values::fn_value::{FunctionValue, BasicBlockIter};
 pub use values::generic_value::GenericValue;
+#[llvm_versions(7.0 => latest)]
+pub use values::global_alias_value::GlobalAliasValue;
 pub use values::global_value::GlobalValue;
 #[llvm_versions(7.0 => latest)]
 pub use values::global_value::UnnamedAddress;
 pub use values::instruction_value::{InstructionValue, InstructionOpcode};
 pub use values::int_value::IntValue;
+pub use values::into_const::IntoConst;
+pub use values::landing_pad_value::LandingPadValue;
 pub use values::metadata_value::{MetadataValue, FIRST_CUSTOM_METADATA_KIND_ID};
 pub use values::phi_value::PhiValue;
-pub use values::ptr_value::PointerValue;
+pub use values::ptr_value::{PointerValue, GEPError};
+pub(crate) use values::ptr_value::get_gep_element_type;
 pub use values::struct_value::StructValue;
 pub use values::traits::{AnyValue, AggregateValue, BasicValue, IntMathValue, FloatMathValue, PointerMathValue};
 pub use values::vec_value::VectorValue;
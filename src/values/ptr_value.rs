@@ -1,12 +1,199 @@
-use llvm_sys::core::{LLVMConstGEP, LLVMConstInBoundsGEP, LLVMConstPtrToInt, LLVMConstPointerCast, LLVMConstAddrSpaceCast};
+use llvm_sys::core::{LLVMConstGEP, LLVMConstInBoundsGEP, LLVMConstPtrToInt, LLVMConstPointerCast, LLVMConstAddrSpaceCast, LLVMIsAConstantInt, LLVMTypeIsSized};
 use llvm_sys::prelude::LLVMValueRef;
 
+use std::error::Error;
 use std::ffi::CStr;
+use std::fmt::{self, Display, Formatter};
 
 use support::LLVMString;
-use types::{AsTypeRef, IntType, PointerType};
+use types::{AnyType, AnyTypeEnum, AsTypeRef, IntType, PointerType};
 use values::{AsValueRef, InstructionValue, IntValue, Value, MetadataValue};
 
+/// Errors that can occur when validating the indices of a GEP.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum GEPError {
+    /// The pointer's element type is unsized and so cannot be offset into.
+    UnsizedPointee,
+    /// A constant GEP was requested on a pointer which is not a constant.
+    NonConstantPointer,
+    /// The pointer's element type is not a struct, as required by a struct GEP.
+    NonStructPointee,
+    /// The index at `position` must be a constant but is not.
+    NonConstantIndex {
+        position: usize,
+    },
+    /// The index at `position` tries to index into a type which is not an aggregate.
+    NonAggregateType {
+        position: usize,
+    },
+    /// The index at `position` tries to index into an opaque struct, which has no known fields.
+    OpaqueStruct {
+        position: usize,
+    },
+    /// The index at `position` indexes into a struct but is not an `i32`.
+    InvalidStructIndexType {
+        position: usize,
+        bit_width: u32,
+    },
+    /// The index at `position` is not a valid field of the struct being indexed into.
+    StructIndexOutOfBounds {
+        position: usize,
+        index: u64,
+        field_count: u32,
+    },
+    /// The index at `position` is outside of the array being indexed into.
+    ArrayIndexOutOfBounds {
+        position: usize,
+        index: i64,
+        len: u32,
+    },
+    /// The index at `position` is outside of the vector being indexed into.
+    VectorIndexOutOfBounds {
+        position: usize,
+        index: i64,
+        len: u32,
+    },
+}
+
+impl GEPError {
+    fn as_str(&self) -> &str {
+        match self {
+            GEPError::UnsizedPointee => "The pointer's element type is unsized",
+            GEPError::NonConstantPointer => "The pointer is not a constant",
+            GEPError::NonStructPointee => "The pointer's element type is not a struct",
+            GEPError::NonConstantIndex { .. } => "An index is not a constant",
+            GEPError::NonAggregateType { .. } => "An index is used on a type which is not an aggregate",
+            GEPError::OpaqueStruct { .. } => "An index is used on an opaque struct",
+            GEPError::InvalidStructIndexType { .. } => "A struct index is not an i32",
+            GEPError::StructIndexOutOfBounds { .. } => "A struct index is out of bounds",
+            GEPError::ArrayIndexOutOfBounds { .. } => "An array index is out of bounds",
+            GEPError::VectorIndexOutOfBounds { .. } => "A vector index is out of bounds",
+        }
+    }
+}
+
+impl Error for GEPError {
+    fn description(&self) -> &str {
+        self.as_str()
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        None
+    }
+}
+
+impl Display for GEPError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            GEPError::NonConstantIndex { position } |
+            GEPError::NonAggregateType { position } |
+            GEPError::OpaqueStruct { position } => write!(f, "GEPError({} at index {})", self.as_str(), position),
+            GEPError::InvalidStructIndexType { position, bit_width } => {
+                write!(f, "GEPError({} at index {}: found i{})", self.as_str(), position, bit_width)
+            },
+            GEPError::StructIndexOutOfBounds { position, index, field_count } => {
+                write!(f, "GEPError({} at index {}: field {} of {})", self.as_str(), position, index, field_count)
+            },
+            GEPError::ArrayIndexOutOfBounds { position, index, len } |
+            GEPError::VectorIndexOutOfBounds { position, index, len } => {
+                write!(f, "GEPError({} at index {}: element {} of {})", self.as_str(), position, index, len)
+            },
+            _ => write!(f, "GEPError({})", self.as_str()),
+        }
+    }
+}
+
+/// Returns the value of a constant integer index, if it is one which fits in 64 bits.
+fn get_constant_index(index: &IntValue) -> Option<i64> {
+    let is_const_int = unsafe {
+        !LLVMIsAConstantInt(index.as_value_ref()).is_null()
+    };
+
+    if !is_const_int || index.get_type().get_bit_width() > 64 {
+        return None;
+    }
+
+    index.get_sign_extended_constant()
+}
+
+/// Walks the type of `ptr_type` along `ordered_indexes` as a GEP would, returning the
+/// type of the element the resulting pointer points to. A constant array or vector index
+/// may only equal the length of the aggregate (a one-past-the-end pointer) if it is the
+/// final index.
+pub(crate) fn get_gep_element_type(ptr_type: PointerType, ordered_indexes: &[IntValue]) -> Result<AnyTypeEnum, GEPError> {
+    let mut element_type = ptr_type.get_element_type();
+
+    if ordered_indexes.is_empty() {
+        return Ok(element_type);
+    }
+
+    // The first index offsets the pointer itself, which requires a sized element type
+    let is_sized = unsafe {
+        LLVMTypeIsSized(element_type.as_type_ref()) == 1
+    };
+
+    if !is_sized {
+        return Err(GEPError::UnsizedPointee);
+    }
+
+    let last_position = ordered_indexes.len() - 1;
+    let check_bounds = |position: usize, index: &IntValue, len: u32| -> Result<(), i64> {
+        match get_constant_index(index) {
+            Some(i) if i < 0 || i > len as i64 => Err(i),
+            Some(i) if i == len as i64 && position != last_position => Err(i),
+            _ => Ok(()),
+        }
+    };
+
+    for (position, index) in ordered_indexes.iter().enumerate().skip(1) {
+        element_type = match element_type {
+            AnyTypeEnum::StructType(struct_type) => {
+                if struct_type.is_opaque() {
+                    return Err(GEPError::OpaqueStruct { position });
+                }
+
+                let bit_width = index.get_type().get_bit_width();
+
+                if bit_width != 32 {
+                    return Err(GEPError::InvalidStructIndexType { position, bit_width });
+                }
+
+                let field_index = match get_constant_index(index) {
+                    Some(i) => i as u32 as u64,
+                    None => return Err(GEPError::NonConstantIndex { position }),
+                };
+                let field_count = struct_type.count_fields();
+
+                if field_index >= field_count as u64 {
+                    return Err(GEPError::StructIndexOutOfBounds { position, index: field_index, field_count });
+                }
+
+                struct_type.get_field_types()[field_index as usize].as_any_type_enum()
+            },
+            AnyTypeEnum::ArrayType(array_type) => {
+                let len = array_type.len();
+
+                // Zero length arrays are commonly used as trailing variable length members
+                if len != 0 {
+                    check_bounds(position, index, len).map_err(|index| GEPError::ArrayIndexOutOfBounds { position, index, len })?;
+                }
+
+                array_type.get_element_type().as_any_type_enum()
+            },
+            AnyTypeEnum::VectorType(vector_type) => {
+                let len = vector_type.get_size();
+
+                check_bounds(position, index, len).map_err(|index| GEPError::VectorIndexOutOfBounds { position, index, len })?;
+
+                vector_type.get_element_type().as_any_type_enum()
+            },
+            _ => return Err(GEPError::NonAggregateType { position }),
+        };
+    }
+
+    Ok(element_type)
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct PointerValue {
     ptr_value: Value,
@@ -84,28 +271,66 @@ impl PointerValue {
     }
 
     // REVIEW: Should this be on array value too?
-    /// GEP is very likely to segfault if indexes are used incorrectly, and is therefore an unsafe function. Maybe we can change this in the future.
-    pub unsafe fn const_gep(&self, ordered_indexes: &[IntValue]) -> PointerValue {
+    /// Creates a constant GEP from this pointer. The pointer and every index must be
+    /// constants, and the indices are checked against the pointer's element type
+    /// before being handed to LLVM.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::AddressSpace;
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let module = context.create_module("my_mod");
+    /// let i32_type = context.i32_type();
+    /// let array_type = i32_type.array_type(4);
+    /// let global = module.add_global(array_type, Some(AddressSpace::Generic), "my_global");
+    /// let ptr = global.as_pointer_value();
+    /// let zero = i32_type.const_zero();
+    ///
+    /// let elem_ptr = ptr.const_gep(&[zero, i32_type.const_int(3, false)]).unwrap();
+    ///
+    /// assert_eq!(elem_ptr.get_type(), i32_type.ptr_type(AddressSpace::Generic));
+    /// assert!(ptr.const_gep(&[zero, i32_type.const_int(5, false)]).is_err());
+    /// ```
+    pub fn const_gep(&self, ordered_indexes: &[IntValue]) -> Result<PointerValue, GEPError> {
+        self.check_const_gep(ordered_indexes)?;
+
         let mut index_values: Vec<LLVMValueRef> = ordered_indexes.iter()
                                                                  .map(|val| val.as_value_ref())
                                                                  .collect();
-        let value = {
+        let value = unsafe {
             LLVMConstGEP(self.as_value_ref(), index_values.as_mut_ptr(), index_values.len() as u32)
         };
 
-        PointerValue::new(value)
+        Ok(PointerValue::new(value))
     }
 
-    /// GEP is very likely to segfault if indexes are used incorrectly, and is therefore an unsafe function. Maybe we can change this in the future.
-    pub unsafe fn const_in_bounds_gep(&self, ordered_indexes: &[IntValue]) -> PointerValue {
+    /// Creates a constant in bounds GEP from this pointer. The same checks as `const_gep` apply.
+    pub fn const_in_bounds_gep(&self, ordered_indexes: &[IntValue]) -> Result<PointerValue, GEPError> {
+        self.check_const_gep(ordered_indexes)?;
+
         let mut index_values: Vec<LLVMValueRef> = ordered_indexes.iter()
                                                                  .map(|val| val.as_value_ref())
                                                                  .collect();
-        let value = {
+        let value = unsafe {
             LLVMConstInBoundsGEP(self.as_value_ref(), index_values.as_mut_ptr(), index_values.len() as u32)
         };
 
-        PointerValue::new(value)
+        Ok(PointerValue::new(value))
+    }
+
+    fn check_const_gep(&self, ordered_indexes: &[IntValue]) -> Result<(), GEPError> {
+        if !self.is_const() {
+            return Err(GEPError::NonConstantPointer);
+        }
+
+        if let Some(position) = ordered_indexes.iter().position(|index| !index.is_const()) {
+            return Err(GEPError::NonConstantIndex { position });
+        }
+
+        get_gep_element_type(self.get_type(), ordered_indexes).map(|_| ())
     }
 
     pub fn const_to_int(&self, int_type: IntType) -> IntValue {
@@ -5,7 +5,7 @@ use std::ffi::CStr;
 use support::LLVMString;
 use types::StructType;
 use values::traits::AsValueRef;
-use values::{InstructionValue, Value, MetadataValue};
+use values::{get_constant_element, BasicValueEnum, InstructionValue, Value, MetadataValue};
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct StructValue {
@@ -68,6 +68,32 @@ impl StructValue {
     pub fn replace_all_uses_with(&self, other: StructValue) {
         self.struct_value.replace_all_uses_with(other.as_value_ref())
     }
+
+    /// Gets the field of a constant `StructValue` at an index. Returns `None` if
+    /// the struct isn't a constant, ie is a constant expression, or the index is out of bounds.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use inkwell::context::Context;
+    ///
+    /// let context = Context::create();
+    /// let i8_val = context.i8_type().const_int(1, false);
+    /// let f32_val = context.f32_type().const_float(2.);
+    /// let struct_val = context.const_struct(&[i8_val.into(), f32_val.into()], false);
+    ///
+    /// assert_eq!(struct_val.get_element_as_constant(1), Some(f32_val.into()));
+    /// assert_eq!(struct_val.get_element_as_constant(2), None);
+    /// ```
+    pub fn get_element_as_constant(&self, index: u32) -> Option<BasicValueEnum> {
+        if !self.struct_value.is_const() {
+            return None;
+        }
+
+        let field_type = *self.get_type().get_field_types().get(index as usize)?;
+
+        get_constant_element(self.as_value_ref(), index, &field_type)
+    }
 }
 
 impl AsValueRef for StructValue {
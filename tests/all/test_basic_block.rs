@@ -115,6 +115,46 @@ fn test_get_basic_blocks() {
     assert_eq!(basic_blocks[0], basic_block);
 }
 
+#[test]
+fn test_basic_block_iterators() {
+    let context = Context::create();
+    let module = context.create_module("test");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[i32_type.into()], false);
+    let function = module.add_function("testing", fn_type, None);
+
+    assert!(function.get_basic_blocks_iter().next().is_none());
+
+    let entry = context.append_basic_block(&function, "entry");
+    let exit = context.append_basic_block(&function, "exit");
+
+    assert!(entry.get_instructions().next().is_none());
+
+    builder.position_at_end(&entry);
+
+    let x = function.get_first_param().unwrap().into_int_value();
+    let sum = builder.build_int_add(x, i32_type.const_int(1, false), "sum");
+    let product = builder.build_int_mul(sum, x, "product");
+
+    builder.build_unconditional_branch(&exit);
+    builder.position_at_end(&exit);
+    builder.build_return(Some(&product));
+
+    assert_eq!(function.get_basic_blocks_iter().collect::<Vec<_>>(), function.get_basic_blocks());
+    assert_eq!(function.get_basic_blocks_iter().rev().next().unwrap(), exit);
+
+    let opcodes: Vec<_> = entry.get_instructions().map(|instruction| instruction.get_opcode()).collect();
+
+    assert_eq!(opcodes, vec![InstructionOpcode::Add, InstructionOpcode::Mul, InstructionOpcode::Br]);
+    assert_eq!(entry.get_instructions().next_back(), entry.get_terminator());
+    assert_eq!(entry.get_instructions().rev().last(), entry.get_first_instruction());
+
+    let instruction_count: usize = function.get_basic_blocks_iter().map(|block| block.get_instructions().count()).sum();
+
+    assert_eq!(instruction_count, 4);
+}
+
 #[test]
 fn test_get_terminator() {
     let context = Context::create();
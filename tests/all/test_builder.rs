@@ -1,9 +1,10 @@
 extern crate inkwell;
 
-use self::inkwell::{AddressSpace, OptimizationLevel};
+use self::inkwell::{AddressSpace, AtomicOrdering, AtomicRMWBinOp, OptimizationLevel};
 use self::inkwell::context::Context;
 use self::inkwell::builder::Builder;
-use self::inkwell::values::BasicValue;
+use self::inkwell::types::FieldNameError;
+use self::inkwell::values::{BasicValue, GEPError, InstructionOpcode};
 
 // use std::ffi::CString;
 use std::ptr::null;
@@ -699,3 +700,381 @@ fn test_bitcast() {
 
     assert!(module.verify().is_err());
 }
+
+#[test]
+fn test_safe_gep() {
+    let context = Context::create();
+    let module = context.create_module("gep");
+    let builder = context.create_builder();
+    let void_type = context.void_type();
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+    let f64_type = context.f64_type();
+    let array_type = f64_type.array_type(4);
+    let struct_type = context.struct_type(&[i32_type.into(), array_type.into()], false);
+    let opaque_struct_type = context.opaque_struct_type("opaque");
+    let struct_ptr_type = struct_type.ptr_type(AddressSpace::Generic);
+    let opaque_ptr_type = opaque_struct_type.ptr_type(AddressSpace::Generic);
+    let fn_type = void_type.fn_type(&[struct_ptr_type.into(), opaque_ptr_type.into(), i32_type.into()], false);
+    let fn_value = module.add_function("gep", fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let struct_ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    let opaque_ptr = fn_value.get_nth_param(1).unwrap().into_pointer_value();
+    let dynamic_index = fn_value.get_nth_param(2).unwrap().into_int_value();
+    let zero = i32_type.const_zero();
+    let one = i32_type.const_int(1, false);
+
+    builder.position_at_end(&entry);
+
+    let same_ptr = builder.build_gep(struct_ptr, &[], "same").unwrap();
+    let field_ptr = builder.build_gep(struct_ptr, &[zero, one], "field").unwrap();
+    let elem_ptr = builder.build_in_bounds_gep(struct_ptr, &[dynamic_index, one, dynamic_index], "elem").unwrap();
+    let end_ptr = builder.build_gep(struct_ptr, &[zero, one, i32_type.const_int(4, false)], "end").unwrap();
+    let struct_field_ptr = builder.build_struct_gep(struct_ptr, 0, "struct_field").unwrap();
+
+    assert_eq!(same_ptr.get_type(), struct_ptr_type);
+    assert_eq!(field_ptr.get_type(), array_type.ptr_type(AddressSpace::Generic));
+    assert_eq!(elem_ptr.get_type(), f64_type.ptr_type(AddressSpace::Generic));
+    assert_eq!(end_ptr.get_type(), f64_type.ptr_type(AddressSpace::Generic));
+    assert_eq!(struct_field_ptr.get_type(), i32_type.ptr_type(AddressSpace::Generic));
+
+    assert_eq!(builder.build_gep(struct_ptr, &[zero, i32_type.const_int(2, false)], "oob"), Err(GEPError::StructIndexOutOfBounds { position: 1, index: 2, field_count: 2 }));
+    assert_eq!(builder.build_gep(struct_ptr, &[zero, dynamic_index], "dyn"), Err(GEPError::NonConstantIndex { position: 1 }));
+    assert_eq!(builder.build_gep(struct_ptr, &[zero, i64_type.const_int(1, false)], "i64"), Err(GEPError::InvalidStructIndexType { position: 1, bit_width: 64 }));
+    assert_eq!(builder.build_gep(struct_ptr, &[zero, one, i32_type.const_int(5, false)], "oob"), Err(GEPError::ArrayIndexOutOfBounds { position: 2, index: 5, len: 4 }));
+    assert_eq!(builder.build_gep(struct_ptr, &[zero, one, i32_type.const_int(4, false), zero], "past"), Err(GEPError::ArrayIndexOutOfBounds { position: 2, index: 4, len: 4 }));
+    assert_eq!(builder.build_gep(struct_ptr, &[zero, zero, zero], "scalar"), Err(GEPError::NonAggregateType { position: 2 }));
+    assert_eq!(builder.build_gep(opaque_ptr, &[zero], "opaque"), Err(GEPError::UnsizedPointee));
+    assert_eq!(builder.build_struct_gep(struct_ptr, 2, "oob"), Err(GEPError::StructIndexOutOfBounds { position: 1, index: 2, field_count: 2 }));
+    assert_eq!(builder.build_struct_gep(opaque_ptr, 0, "opaque"), Err(GEPError::OpaqueStruct { position: 1 }));
+    assert_eq!(builder.build_struct_gep(struct_field_ptr, 0, "not_struct"), Err(GEPError::NonStructPointee));
+
+    builder.build_return(None);
+
+    assert!(module.verify().is_ok());
+}
+
+#[llvm_versions(6.0 => latest)]
+#[test]
+fn test_landing_pad() {
+    let context = Context::create();
+    let module = context.create_module("eh");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    let exception_type = context.struct_type(&[i8_ptr_type.into(), i32_type.into()], false);
+    let personality = module.add_function("__gxx_personality_v0", i32_type.fn_type(&[], true), None);
+    let may_throw = module.add_function("may_throw", i32_type.fn_type(&[], false), None);
+    let fn_value = module.add_function("eh", i32_type.fn_type(&[], false), None);
+    let entry = fn_value.append_basic_block("entry");
+    let then_block = fn_value.append_basic_block("then");
+    let catch_block = fn_value.append_basic_block("catch");
+
+    fn_value.set_personality_function(personality);
+
+    builder.position_at_end(&entry);
+
+    let call_site = builder.build_invoke(may_throw, &[], &then_block, &catch_block, "call");
+
+    builder.position_at_end(&then_block);
+    builder.build_return(Some(&call_site.try_as_basic_value().left().unwrap()));
+
+    builder.position_at_end(&catch_block);
+
+    let landing_pad = builder.build_landing_pad(exception_type, personality, "lpad");
+
+    assert!(!landing_pad.is_cleanup());
+    assert_eq!(landing_pad.count_clauses(), 0);
+
+    landing_pad.add_clause(i8_ptr_type.const_null());
+    landing_pad.set_cleanup(true);
+
+    assert!(landing_pad.is_cleanup());
+    assert_eq!(landing_pad.count_clauses(), 1);
+    assert_eq!(landing_pad.get_clause(0), Some(i8_ptr_type.const_null().into()));
+    assert_eq!(landing_pad.get_clause(1), None);
+    assert_eq!(landing_pad.as_basic_value().into_struct_value().get_type(), exception_type);
+
+    builder.build_resume(landing_pad.as_basic_value());
+
+    assert!(module.verify().is_ok(), module.print_to_string().to_string());
+}
+
+#[llvm_versions(6.0 => latest)]
+#[test]
+fn test_funclet_pads() {
+    let context = Context::create();
+    let module = context.create_module("funclets");
+    let builder = context.create_builder();
+    let void_type = context.void_type();
+    let i32_type = context.i32_type();
+    let i8_ptr_null = context.i8_type().ptr_type(AddressSpace::Generic).const_null();
+    let personality = module.add_function("__CxxFrameHandler3", i32_type.fn_type(&[], true), None);
+    let may_throw = module.add_function("may_throw", void_type.fn_type(&[], false), None);
+    let fn_value = module.add_function("funclets", void_type.fn_type(&[], false), None);
+    let entry = fn_value.append_basic_block("entry");
+    let cleanup = fn_value.append_basic_block("cleanup");
+    let dispatch = fn_value.append_basic_block("dispatch");
+    let handler = fn_value.append_basic_block("handler");
+    let next = fn_value.append_basic_block("next");
+    let exit = fn_value.append_basic_block("exit");
+
+    fn_value.set_personality_function(personality);
+
+    builder.position_at_end(&entry);
+    builder.build_invoke(may_throw, &[], &next, &dispatch, "");
+
+    builder.position_at_end(&dispatch);
+
+    let catch_switch = builder.build_catch_switch(None, None, &[&handler], "switch");
+
+    builder.position_at_end(&handler);
+
+    let catch_pad = builder.build_catch_pad(catch_switch, &[i8_ptr_null.into(), i32_type.const_int(64, false).into(), i8_ptr_null.into()], "catch_pad");
+
+    builder.build_catch_ret(catch_pad, &next);
+
+    builder.position_at_end(&next);
+    builder.build_invoke(may_throw, &[], &exit, &cleanup, "");
+
+    builder.position_at_end(&cleanup);
+
+    let cleanup_pad = builder.build_cleanup_pad(None, &[], "cleanup_pad");
+
+    builder.build_cleanup_ret(cleanup_pad, None);
+
+    builder.position_at_end(&exit);
+    builder.build_return(None);
+
+    assert_eq!(cleanup_pad.get_opcode(), InstructionOpcode::CleanupPad);
+    assert_eq!(catch_switch.get_opcode(), InstructionOpcode::CatchSwitch);
+    assert_eq!(catch_pad.get_opcode(), InstructionOpcode::CatchPad);
+    assert!(module.verify().is_ok(), module.print_to_string().to_string());
+}
+
+#[test]
+fn test_atomics() {
+    let context = Context::create();
+    let module = context.create_module("atomics");
+    let builder = context.create_builder();
+    let i1_type = context.bool_type();
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+    let f32_type = context.f32_type();
+    let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+    let fn_type = context.void_type().fn_type(&[i32_ptr_type.into(), i1_type.ptr_type(AddressSpace::Generic).into(), f32_type.ptr_type(AddressSpace::Generic).into()], false);
+    let fn_value = module.add_function("atomics", fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let i32_ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    let i1_ptr = fn_value.get_nth_param(1).unwrap().into_pointer_value();
+    let f32_ptr = fn_value.get_nth_param(2).unwrap().into_pointer_value();
+    let zero = i32_type.const_zero();
+    let one = i32_type.const_int(1, false);
+
+    builder.position_at_end(&entry);
+
+    let old_value = builder.build_atomicrmw(AtomicRMWBinOp::Add, i32_ptr, one, AtomicOrdering::Monotonic, false).unwrap();
+    let single_thread = builder.build_atomicrmw(AtomicRMWBinOp::Sub, i32_ptr, one, AtomicOrdering::Monotonic, true).unwrap();
+    let rmw_instruction = old_value.as_instruction().unwrap();
+
+    assert_eq!(old_value.get_type(), i32_type);
+    assert_eq!(rmw_instruction.get_opcode(), InstructionOpcode::AtomicRMW);
+    assert_eq!(rmw_instruction.is_atomic_single_thread(), Ok(false));
+    assert!(rmw_instruction.set_atomic_single_thread(true).is_ok());
+    assert_eq!(rmw_instruction.is_atomic_single_thread(), Ok(true));
+    assert_eq!(single_thread.as_instruction().unwrap().is_atomic_single_thread(), Ok(true));
+
+    assert!(builder.build_atomicrmw(AtomicRMWBinOp::Xchg, i32_ptr, i64_type.const_zero(), AtomicOrdering::Monotonic, false).is_err());
+    assert!(builder.build_atomicrmw(AtomicRMWBinOp::Xchg, i1_ptr, i1_type.const_zero(), AtomicOrdering::Monotonic, false).is_err());
+    assert!(builder.build_atomicrmw(AtomicRMWBinOp::Xchg, i32_ptr, one, AtomicOrdering::Unordered, false).is_err());
+
+    let result = builder.build_cmpxchg(i32_ptr, zero, one, AtomicOrdering::AcquireRelease, AtomicOrdering::Monotonic, true).unwrap();
+
+    assert_eq!(result.get_type(), context.struct_type(&[i32_type.into(), i1_type.into()], false));
+    assert_eq!(result.as_instruction().unwrap().is_atomic_single_thread(), Ok(true));
+    assert!(builder.build_cmpxchg(i32_ptr, zero, one, AtomicOrdering::Monotonic, AtomicOrdering::Acquire, false).is_err());
+    assert!(builder.build_cmpxchg(i32_ptr, zero, one, AtomicOrdering::Release, AtomicOrdering::Acquire, false).is_ok());
+    assert!(builder.build_cmpxchg(i32_ptr, zero, one, AtomicOrdering::SequentiallyConsistent, AtomicOrdering::Release, false).is_err());
+    assert!(builder.build_cmpxchg(i32_ptr, zero, one, AtomicOrdering::NotAtomic, AtomicOrdering::Monotonic, false).is_err());
+    assert!(builder.build_cmpxchg(f32_ptr, f32_type.const_zero(), f32_type.const_float(1.), AtomicOrdering::Monotonic, AtomicOrdering::Monotonic, false).is_err());
+    assert!(builder.build_cmpxchg(i32_ptr, i64_type.const_zero(), i64_type.const_zero(), AtomicOrdering::Monotonic, AtomicOrdering::Monotonic, false).is_err());
+
+    let load = builder.build_load(i32_ptr, "load").as_instruction_value().unwrap();
+    let store = builder.build_store(i32_ptr, one);
+
+    assert_eq!(load.get_atomic_ordering(), Ok(AtomicOrdering::NotAtomic));
+    assert!(load.set_atomic_ordering(AtomicOrdering::Release).is_err());
+    assert!(load.set_atomic_ordering(AtomicOrdering::Acquire).is_ok());
+    assert!(load.set_alignment(3).is_err());
+    assert!(load.set_alignment(4).is_ok());
+    assert!(store.set_atomic_ordering(AtomicOrdering::Acquire).is_err());
+    assert!(store.set_atomic_ordering(AtomicOrdering::Release).is_ok());
+    assert!(store.set_alignment(4).is_ok());
+    assert_eq!(load.get_atomic_ordering(), Ok(AtomicOrdering::Acquire));
+    assert_eq!(store.get_atomic_ordering(), Ok(AtomicOrdering::Release));
+    assert_eq!(load.get_alignment(), Ok(4));
+    assert_eq!(store.get_alignment(), Ok(4));
+    assert!(rmw_instruction.get_alignment().is_err());
+    assert!(rmw_instruction.get_atomic_ordering().is_err());
+    assert!(load.is_atomic_single_thread().is_err());
+
+    let atomic_load = builder.build_atomic_load(i32_ptr, 4, AtomicOrdering::SequentiallyConsistent, "atomic_load").unwrap();
+    let atomic_load = atomic_load.as_instruction_value().unwrap();
+    let atomic_store = builder.build_atomic_store(f32_ptr, f32_type.const_float(1.), 4, AtomicOrdering::Release).unwrap();
+
+    assert_eq!(atomic_load.get_atomic_ordering(), Ok(AtomicOrdering::SequentiallyConsistent));
+    assert_eq!(atomic_load.get_alignment(), Ok(4));
+    assert_eq!(atomic_store.get_atomic_ordering(), Ok(AtomicOrdering::Release));
+    assert_eq!(atomic_store.get_alignment(), Ok(4));
+
+    assert!(builder.build_atomic_load(i32_ptr, 0, AtomicOrdering::Monotonic, "unaligned").is_err());
+    assert!(builder.build_atomic_load(i32_ptr, 4, AtomicOrdering::Release, "release").is_err());
+    assert!(builder.build_atomic_load(i32_ptr, 4, AtomicOrdering::NotAtomic, "not_atomic").is_err());
+    assert!(builder.build_atomic_load(i1_ptr, 1, AtomicOrdering::Monotonic, "i1").is_err());
+    assert!(builder.build_atomic_store(i32_ptr, one, 4, AtomicOrdering::Acquire).is_err());
+    assert!(builder.build_atomic_store(i32_ptr, i64_type.const_zero(), 8, AtomicOrdering::Monotonic).is_err());
+
+    builder.build_return(None);
+
+    assert!(module.verify().is_ok(), module.print_to_string().to_string());
+}
+
+#[llvm_versions(7.0 => latest)]
+#[test]
+fn test_memory_intrinsics() {
+    let context = Context::create();
+    let module = context.create_module("mem");
+    let builder = context.create_builder();
+    let void_type = context.void_type();
+    let i8_type = context.i8_type();
+    let i32_type = context.i32_type();
+    let i64_type = context.i64_type();
+    let i32_ptr_type = i32_type.ptr_type(AddressSpace::Generic);
+    let fn_type = void_type.fn_type(&[i32_ptr_type.into(), i32_ptr_type.into()], false);
+    let fn_value = module.add_function("mem_ops", fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let dest = fn_value.get_nth_param(0).unwrap().into_pointer_value();
+    let src = fn_value.get_nth_param(1).unwrap().into_pointer_value();
+    let sixteen = i64_type.const_int(16, false);
+
+    // Not positioned inside a function yet
+    assert!(builder.build_memset(dest, 4, i8_type.const_int(0, false), sixteen, false).is_err());
+
+    builder.position_at_end(&entry);
+
+    assert!(builder.build_memcpy(dest, 3, src, 4, sixteen, false).is_err());
+    assert!(builder.build_memmove(dest, 4, src, 6, sixteen, false).is_err());
+    assert!(builder.build_memset(dest, 4, i32_type.const_int(0, false), sixteen, false).is_err());
+
+    // dest = [0xffffffff; 4], then dest[0..2] = src[0..2], then dest[1..3] = dest[0..2]
+    builder.build_memset(dest, 4, i8_type.const_int(0xff, false), sixteen, false).unwrap();
+    builder.build_memcpy(dest, 4, src, 0, i64_type.const_int(8, false), false).unwrap();
+
+    let dest_1 = builder.build_gep(dest, &[i32_type.const_int(1, false)], "dest_1").unwrap();
+
+    builder.build_memmove(dest_1, 4, dest, 4, i64_type.const_int(8, false), true).unwrap();
+    builder.build_return(None);
+
+    assert!(module.verify().is_ok());
+
+    // Every call shares a single declaration per overload
+    assert!(module.get_function("llvm.memcpy.p0i8.p0i8.i64").is_some());
+    assert!(module.get_function("llvm.memmove.p0i8.p0i8.i64").is_some());
+    assert!(module.get_function("llvm.memset.p0i8.i64").is_some());
+
+    let ir = module.print_to_string();
+    let ir = ir.to_str().unwrap();
+
+    // An alignment of 0 adds no attribute
+    assert_eq!(ir.matches("align 4").count(), 4);
+    assert!(ir.contains("i1 true)"));
+
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    let mut dest_array = [0u32; 4];
+    let src_array = [1u32, 2, 3, 4];
+
+    unsafe {
+        let mem_ops = execution_engine.get_function::<unsafe extern "C" fn(*mut u32, *const u32)>("mem_ops").unwrap();
+
+        mem_ops.call(dest_array.as_mut_ptr(), src_array.as_ptr());
+    }
+
+    assert_eq!(dest_array, [1, 1, 2, 0xffff_ffff]);
+}
+
+#[llvm_versions(7.0 => latest)]
+#[test]
+fn test_inline_asm() {
+    use self::inkwell::InlineAsmDialect;
+
+    let context = Context::create();
+    let module = context.create_module("asm");
+    let builder = context.create_builder();
+    let i64_type = context.i64_type();
+    let fn_type = i64_type.fn_type(&[i64_type.into()], false);
+    let att_add_one = context.create_inline_asm(fn_type, "lea 1($1), $0", "=r,r", false, false, None);
+    let intel_add_two = context.create_inline_asm(fn_type, "lea $0, [$1 + 2]", "=r,r", true, true, Some(InlineAsmDialect::Intel));
+    let fn_value = module.add_function("add_three", fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let arg = fn_value.get_first_param().unwrap();
+
+    builder.position_at_end(&entry);
+
+    let plus_one = builder.build_call(att_add_one, &[arg], "plus_one").try_as_basic_value().left().unwrap();
+    let plus_three = builder.build_call(intel_add_two, &[plus_one], "plus_three").try_as_basic_value().left().unwrap();
+
+    builder.build_return(Some(&plus_three));
+
+    assert!(module.verify().is_ok());
+
+    let ir = module.print_to_string();
+    let ir = ir.to_str().unwrap();
+
+    assert!(ir.contains(r#"call i64 asm "lea 1($1), $0", "=r,r"(i64 %0)"#));
+    assert!(ir.contains(r#"call i64 asm sideeffect alignstack inteldialect "lea $0, [$1 + 2]", "=r,r"(i64 %plus_one)"#));
+
+    // The asm strings above are only valid x86 assembly
+    if cfg!(target_arch = "x86_64") {
+        let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+        unsafe {
+            let add_three = execution_engine.get_function::<unsafe extern "C" fn(i64) -> i64>("add_three").unwrap();
+
+            assert_eq!(add_three.call(39), 42);
+        }
+    }
+}
+
+#[test]
+fn test_build_by_field_name() {
+    let context = Context::create();
+    let module = context.create_module("my_mod");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let f64_type = context.f64_type();
+    let struct_type = context.opaque_struct_type("entry");
+
+    struct_type.set_body(&[i32_type.into(), f64_type.into()], false);
+
+    let fn_type = context.void_type().fn_type(&[struct_type.ptr_type(AddressSpace::Generic).into(), i32_type.ptr_type(AddressSpace::Generic).into()], false);
+    let fn_value = module.add_function("my_fn", fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+
+    builder.position_at_end(&entry);
+
+    let struct_ptr = fn_value.get_first_param().unwrap().into_pointer_value();
+    let int_ptr = fn_value.get_nth_param(1).unwrap().into_pointer_value();
+
+    assert_eq!(builder.build_struct_gep_by_name(struct_ptr, "value", "value_ptr"), Err(FieldNameError::NoFieldNames { struct_name: "entry".into() }));
+
+    struct_type.set_field_names(&["key", "value"]);
+
+    let value_ptr = builder.build_struct_gep_by_name(struct_ptr, "value", "value_ptr").unwrap();
+    let struct_value = builder.build_load(struct_ptr, "struct_value").into_struct_value();
+    let key = builder.build_extract_value_by_name(struct_value, "key", "key").unwrap();
+
+    assert_eq!(value_ptr.get_type(), f64_type.ptr_type(AddressSpace::Generic));
+    assert_eq!(key.into_int_value().get_type(), i32_type);
+    assert_eq!(builder.build_struct_gep_by_name(int_ptr, "key", "key_ptr"), Err(FieldNameError::NonStructType));
+    assert!(builder.build_extract_value_by_name(struct_value, "missing", "missing").is_err());
+}
@@ -1,7 +1,7 @@
 extern crate inkwell;
 
 use self::inkwell::AddressSpace;
-use self::inkwell::context::Context;
+use self::inkwell::context::{Context, ThreadSafeContext};
 use self::inkwell::types::{IntType, StructType};
 
 #[test]
@@ -83,3 +83,146 @@ fn test_values_get_context() {
     assert_eq!(*i8_type.get_context(), context);
     assert_eq!(struct_type.get_context(), Context::get_global());
 }
+
+#[test]
+fn test_diagnostic_handler() {
+    use self::inkwell::OptimizationLevel;
+    use self::inkwell::support::error_handling::DiagnosticSeverity;
+    use self::inkwell::targets::{CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    Target::initialize_native(&InitializationConfig::default()).unwrap();
+
+    let triple = TargetMachine::get_default_triple();
+    let triple = triple.to_str().unwrap();
+    let target = Target::from_triple(triple).unwrap();
+    let target_machine = target.create_target_machine(triple, "", "", OptimizationLevel::None, RelocMode::Default, CodeModel::Default).unwrap();
+
+    let context = Context::create();
+    let module = context.create_module("diagnostics");
+    let builder = context.create_builder();
+    let i8_type = context.i8_type();
+    let fn_value = module.add_function("big_frame", context.void_type().fn_type(&[], false), None);
+    let entry = fn_value.append_basic_block("entry");
+
+    // Asks codegen to warn about any stack frame larger than 16 bytes
+    fn_value.add_attribute(!0, context.create_string_attribute("warn-stack-size", "16"));
+
+    builder.position_at_end(&entry);
+
+    let buffer = builder.build_array_alloca(i8_type, context.i32_type().const_int(4096, false), "buffer");
+
+    builder.build_store(buffer, i8_type.const_int(0, false));
+    builder.build_return(None);
+
+    let diagnostics = Rc::new(RefCell::new(Vec::new()));
+    let diagnostics_clone = diagnostics.clone();
+
+    context.set_diagnostic_handler(move |diagnostic_info| {
+        diagnostics_clone.borrow_mut().push((diagnostic_info.get_severity(), diagnostic_info.get_description().to_string()));
+    });
+
+    // The handler is still installed after linking, which temporarily swaps it out
+    let other_module = context.create_module("other");
+
+    module.link_in_module(other_module).unwrap();
+
+    assert!(diagnostics.borrow().is_empty());
+
+    target_machine.write_to_memory_buffer(&module, FileType::Object).unwrap();
+
+    {
+        // Some LLVM versions attach remarks to the warning
+        let diagnostics = diagnostics.borrow();
+        let warnings: Vec<_> = diagnostics.iter().filter(|&&(severity, _)| severity == DiagnosticSeverity::Warning).collect();
+
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].1.contains("big_frame"));
+        assert!(diagnostics.iter().all(|&(severity, _)| severity != DiagnosticSeverity::Error));
+    }
+
+    // Replacing or clearing the handler drops the previous closure
+    context.set_diagnostic_handler(|_| ());
+
+    assert_eq!(Rc::strong_count(&diagnostics), 1);
+
+    let diagnostics_clone = diagnostics.clone();
+
+    context.set_diagnostic_handler(move |_| diagnostics_clone.borrow_mut().clear());
+    context.clear_diagnostic_handler();
+
+    assert_eq!(Rc::strong_count(&diagnostics), 1);
+
+    // A handler may clear itself while it is being called, which defers dropping it until it returns
+    let diagnostics_clone = diagnostics.clone();
+    let context_clone = context.clone();
+
+    context.set_diagnostic_handler(move |diagnostic_info| {
+        context_clone.clear_diagnostic_handler();
+        diagnostics_clone.borrow_mut().push((diagnostic_info.get_severity(), diagnostic_info.get_description().to_string()));
+    });
+
+    diagnostics.borrow_mut().clear();
+    target_machine.write_to_memory_buffer(&module, FileType::Object).unwrap();
+
+    assert_eq!(diagnostics.borrow().len(), 1);
+    assert_eq!(Rc::strong_count(&diagnostics), 1);
+
+    // Disposing of the context drops its handler too
+    let other_context = Context::create();
+    let diagnostics_clone = diagnostics.clone();
+
+    other_context.set_diagnostic_handler(move |_| diagnostics_clone.borrow_mut().clear());
+
+    assert_eq!(Rc::strong_count(&diagnostics), 2);
+
+    drop(other_context);
+
+    assert_eq!(Rc::strong_count(&diagnostics), 1);
+}
+
+#[test]
+fn test_thread_safe_context() {
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let other_module = context.create_module("my_other_module");
+
+    // Every module referencing the context must be included
+    let (context, mut modules) = unsafe { ThreadSafeContext::new(context, vec![module]).unwrap_err() };
+
+    modules.push(other_module);
+
+    // Diagnostic handler closures needn't be Send
+    context.set_diagnostic_handler(|_| ());
+
+    let (context, modules) = unsafe { ThreadSafeContext::new(context, modules).unwrap_err() };
+
+    context.clear_diagnostic_handler();
+
+    let thread_safe_context = unsafe { ThreadSafeContext::new(context, modules).unwrap() };
+    let handle = ::std::thread::spawn(move || {
+        let (context, modules) = thread_safe_context.into_inner();
+        let builder = context.create_builder();
+        let fn_type = context.i32_type().fn_type(&[], false);
+
+        for module in &modules {
+            let function = module.add_function("my_fn", fn_type, None);
+
+            builder.position_at_end(&function.append_basic_block("entry"));
+            builder.build_return(Some(&context.i32_type().const_int(1, false)));
+        }
+
+        drop(builder);
+
+        unsafe { ThreadSafeContext::new(context, modules).unwrap() }
+    });
+    let (context, modules) = handle.join().unwrap().into_inner();
+
+    assert_eq!(modules.len(), 2);
+
+    for module in &modules {
+        assert_eq!(*module.get_context(), context);
+        assert!(module.get_function("my_fn").unwrap().verify(false));
+    }
+}
@@ -0,0 +1,100 @@
+extern crate inkwell;
+
+use self::inkwell::AddressSpace;
+use self::inkwell::context::Context;
+use self::inkwell::debug_info::{AsDIScope, DIFlags, DWARFEmissionKind, DWARFSourceLanguage, DWARFTypeEncoding, debug_metadata_version};
+use self::inkwell::module::FlagBehavior;
+
+#[test]
+fn test_smoke() {
+    let context = Context::create();
+    let module = context.create_module("debug_info");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let debug_version = i32_type.const_int(debug_metadata_version() as u64, false);
+
+    module.add_basic_value_flag("Debug Info Version", FlagBehavior::Warning, debug_version);
+
+    assert_eq!(module.get_debug_metadata_version(), debug_metadata_version());
+
+    let di_builder = module.create_debug_info_builder(true);
+    let file = di_builder.create_file("source.src", "/src");
+    let compile_unit = di_builder.create_compile_unit(DWARFSourceLanguage::C, file, "inkwell", false, "", 0, "", DWARFEmissionKind::Full, 0, false, false);
+    let int_di_type = di_builder.create_basic_type("int", 32, DWARFTypeEncoding::Signed);
+    let int_ptr_di_type = di_builder.create_pointer_type("int*", int_di_type, 64, 64, AddressSpace::Generic);
+    let fn_di_type = di_builder.create_subroutine_type(file, Some(int_di_type), &[int_ptr_di_type], DIFlags::PROTOTYPED);
+    let subprogram = di_builder.create_function(compile_unit, "deref", None, file, 1, fn_di_type, false, true, 1, DIFlags::PROTOTYPED, false);
+
+    assert_eq!(int_di_type.get_size_in_bits(), 32);
+    assert_eq!(int_ptr_di_type.get_align_in_bits(), 64);
+
+    let fn_type = i32_type.fn_type(&[i32_type.ptr_type(AddressSpace::Generic).into()], false);
+    let fn_value = module.add_function("deref", fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+
+    assert!(fn_value.get_subprogram().is_none());
+
+    fn_value.set_subprogram(subprogram);
+
+    assert_eq!(fn_value.get_subprogram(), Some(subprogram));
+
+    builder.position_at_end(&entry);
+
+    let param = fn_value.get_first_param().unwrap().into_pointer_value();
+    let param_variable = di_builder.create_parameter_variable(subprogram, "ptr", 1, file, 1, int_ptr_di_type, true, DIFlags::ZERO);
+    let lexical_block = di_builder.create_lexical_block(subprogram, file, 2, 1);
+    let location = di_builder.create_debug_location(&context, 2, 5, lexical_block, None);
+
+    assert!(builder.get_current_debug_location().is_none());
+
+    builder.set_current_debug_location(&context, location);
+
+    assert_eq!(builder.get_current_debug_location(), Some(location));
+    assert_eq!(location.get_line(), 2);
+    assert_eq!(location.get_column(), 5);
+    assert_eq!(location.get_scope(), lexical_block.as_debug_info_scope());
+
+    let alloca = builder.build_alloca(i32_type.ptr_type(AddressSpace::Generic), "ptr_addr");
+
+    builder.build_store(alloca, param);
+
+    let local_variable = di_builder.create_auto_variable(lexical_block, "value", file, 2, int_di_type, true, DIFlags::ZERO, 32);
+    let load = builder.build_load(param, "value");
+
+    builder.build_return(Some(&load));
+
+    let ret = entry.get_last_instruction().unwrap();
+
+    di_builder.insert_declare_before_instruction(alloca, param_variable, None, location, ret);
+    di_builder.insert_dbg_value_before(load, local_variable, None, location, ret);
+
+    di_builder.finalize();
+
+    assert!(module.verify().is_ok(), module.print_to_string().to_string());
+    assert!(module.strip_debug_info());
+    assert!(module.verify().is_ok());
+}
+
+#[test]
+fn test_types() {
+    let context = Context::create();
+    let module = context.create_module("debug_info");
+    let di_builder = module.create_debug_info_builder(true);
+    let file = di_builder.create_file("source.src", "/src");
+    let compile_unit = di_builder.create_compile_unit(DWARFSourceLanguage::Rust, file, "inkwell", true, "", 0, "", DWARFEmissionKind::LineTablesOnly, 0, false, false);
+    let float_di_type = di_builder.create_basic_type("f32", 32, DWARFTypeEncoding::Float);
+    let bool_di_type = di_builder.create_basic_type("bool", 8, DWARFTypeEncoding::Boolean);
+    let array_di_type = di_builder.create_array_type(float_di_type, 32 * 12, 32, &[0..3, 0..4]);
+    let field1 = di_builder.create_member_type(compile_unit, "values", file, 2, 32 * 12, 32, 0, DIFlags::PUBLIC, array_di_type);
+    let field2 = di_builder.create_member_type(compile_unit, "valid", file, 3, 8, 8, 32 * 12, DIFlags::PUBLIC, bool_di_type);
+    let struct_di_type = di_builder.create_struct_type(compile_unit, "Matrix", file, 1, 32 * 13, 32, DIFlags::ZERO, None, &[field1, field2], 0, None, "Matrix");
+
+    assert_eq!(array_di_type.get_size_in_bits(), 32 * 12);
+    assert_eq!(field2.get_offset_in_bits(), 32 * 12);
+    assert_eq!(struct_di_type.get_size_in_bits(), 32 * 13);
+    assert_eq!(DIFlags::PUBLIC | DIFlags::ZERO, DIFlags::PUBLIC);
+
+    di_builder.finalize();
+
+    assert!(module.verify().is_ok());
+}
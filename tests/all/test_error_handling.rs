@@ -0,0 +1,69 @@
+extern crate inkwell;
+
+use self::inkwell::OptimizationLevel;
+use self::inkwell::context::Context;
+use self::inkwell::support::error_handling::set_fatal_error_handler;
+use self::inkwell::targets::{CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetMachine};
+
+use std::env;
+use std::process::Command;
+
+const FATAL_ERROR_CHILD: &str = "INKWELL_TEST_FATAL_ERROR_CHILD";
+
+// Codegen for an intrinsic belonging to another target can't select an instruction, which is fatal
+#[cfg(target_arch = "x86_64")]
+fn trigger_fatal_error() {
+    Target::initialize_native(&InitializationConfig::default()).unwrap();
+
+    let triple = TargetMachine::get_default_triple();
+    let triple = triple.to_str().unwrap();
+    let target = Target::from_triple(triple).unwrap();
+    let target_machine = target.create_target_machine(triple, "", "", OptimizationLevel::None, RelocMode::Default, CodeModel::Default).unwrap();
+
+    let context = Context::create();
+    let module = context.create_module("unselectable");
+    let builder = context.create_builder();
+    let void_type = context.void_type();
+    let i32_type = context.i32_type();
+    let isb = module.add_function("llvm.aarch64.isb", void_type.fn_type(&[i32_type.into()], false), None);
+    let fn_value = module.add_function("barrier", void_type.fn_type(&[], false), None);
+    let entry = fn_value.append_basic_block("entry");
+
+    builder.position_at_end(&entry);
+    builder.build_call(isb, &[i32_type.const_int(15, false).into()], "");
+    builder.build_return(None);
+
+    let _ = target_machine.write_to_memory_buffer(&module, FileType::Object);
+}
+
+#[cfg(target_arch = "x86_64")]
+#[test]
+fn test_fatal_error_handler() {
+    // LLVM exits after a fatal error, so the error is triggered in a child process running only this test
+    if env::var_os(FATAL_ERROR_CHILD).is_some() {
+        let _outer_guard = set_fatal_error_handler(|reason| println!("outer handler: {}", reason));
+        let inner_guard = set_fatal_error_handler(|reason| println!("inner handler: {}", reason));
+        let newest_guard = set_fatal_error_handler(|reason| println!("newest handler: {}", reason));
+
+        // Guards may be dropped out of order
+        drop(inner_guard);
+        drop(newest_guard);
+
+        trigger_fatal_error();
+
+        unreachable!("LLVM should have exited after a fatal error");
+    }
+
+    let output = Command::new(env::current_exe().unwrap())
+        .args(&["test_error_handling::test_fatal_error_handler", "--exact", "--nocapture", "--test-threads=1"])
+        .env(FATAL_ERROR_CHILD, "1")
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert!(!output.status.success());
+    assert!(stdout.contains("outer handler: "), "{}", stdout);
+    assert!(stdout.contains("Cannot select"), "{}", stdout);
+    assert!(!stdout.contains("inner handler"));
+    assert!(!stdout.contains("newest handler"));
+}
@@ -3,6 +3,7 @@ extern crate inkwell;
 use self::inkwell::{AddressSpace, OptimizationLevel, IntPredicate};
 use self::inkwell::context::Context;
 use self::inkwell::execution_engine::FunctionLookupError;
+use self::inkwell::types::RustStruct;
 use self::inkwell::targets::{InitializationConfig, Target};
 
 // use std::ffi::CString;
@@ -222,3 +223,74 @@ fn test_previous_double_free2() {
         module.create_jit_execution_engine(OptimizationLevel::None).unwrap()
     };
 }
+
+#[derive(RustStruct)]
+#[repr(C)]
+struct Pair {
+    first: i32,
+    second: f64,
+}
+
+#[test]
+fn test_get_checked_function() {
+    Target::initialize_native(&InitializationConfig::default()).unwrap();
+
+    let context = Context::create();
+    let module = context.create_module("checked");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let f64_type = context.f64_type();
+    let pair_type = context.opaque_struct_type("Pair");
+
+    pair_type.set_body(&[i32_type.into(), f64_type.into()], false);
+
+    let pair_ptr_type = pair_type.ptr_type(AddressSpace::Generic);
+
+    // Builds `fn get_first(pair: *const Pair) -> i32`
+    let fn_type = i32_type.fn_type(&[pair_ptr_type.into()], false);
+    let fn_value = module.add_function("get_first", fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let pair = fn_value.get_first_param().unwrap().into_pointer_value();
+
+    builder.position_at_end(&entry);
+
+    let first_ptr = builder.build_struct_gep(pair, 0, "first_ptr").unwrap();
+    let first = builder.build_load(first_ptr, "first");
+
+    builder.build_return(Some(&first));
+
+    let void_fn_value = module.add_function("nothing", context.void_type().fn_type(&[i32_type.into()], false), None);
+
+    builder.position_at_end(&void_fn_value.append_basic_block("entry"));
+    builder.build_return(None);
+
+    let by_value_fn_value = module.add_function("by_value", i32_type.fn_type(&[pair_type.into()], false), None);
+
+    builder.position_at_end(&by_value_fn_value.append_basic_block("entry"));
+    builder.build_return(Some(&i32_type.const_zero()));
+
+    let i1_fn_value = module.add_function("is_set", context.bool_type().fn_type(&[], false), None);
+
+    builder.position_at_end(&i1_fn_value.append_basic_block("entry"));
+    builder.build_return(Some(&context.bool_type().const_int(1, false)));
+
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+
+    assert_eq!(execution_engine.get_checked_function::<unsafe extern "C" fn(*const Pair) -> i64>("get_first").unwrap_err(), FunctionLookupError::SignatureMismatch);
+    assert_eq!(execution_engine.get_checked_function::<unsafe extern "C" fn(*const i32) -> i32>("get_first").unwrap_err(), FunctionLookupError::SignatureMismatch);
+    assert_eq!(execution_engine.get_checked_function::<unsafe extern "C" fn() -> i32>("get_first").unwrap_err(), FunctionLookupError::SignatureMismatch);
+    assert_eq!(execution_engine.get_checked_function::<unsafe extern "C" fn(*const Pair, i32) -> i32>("get_first").unwrap_err(), FunctionLookupError::SignatureMismatch);
+    assert_eq!(execution_engine.get_checked_function::<unsafe extern "C" fn(i32) -> i32>("nothing").unwrap_err(), FunctionLookupError::SignatureMismatch);
+    assert_eq!(execution_engine.get_checked_function::<unsafe extern "C" fn()>("missing").unwrap_err(), FunctionLookupError::FunctionNotFound);
+    assert_eq!(execution_engine.get_checked_function::<unsafe extern "C" fn(Pair) -> i32>("by_value").unwrap_err(), FunctionLookupError::SignatureMismatch);
+    assert_eq!(execution_engine.get_checked_function::<unsafe extern "C" fn() -> bool>("is_set").unwrap_err(), FunctionLookupError::SignatureMismatch);
+
+    assert!(execution_engine.get_checked_function::<unsafe extern "C" fn(u32)>("nothing").is_ok());
+
+    let get_first = execution_engine.get_checked_function::<unsafe extern "C" fn(*const Pair) -> i32>("get_first").unwrap();
+    let pair = Pair { first: 42, second: 1.5 };
+
+    unsafe {
+        assert_eq!(get_first.call(&pair), 42);
+    }
+}
@@ -0,0 +1,96 @@
+extern crate inkwell;
+
+use self::inkwell::AddressSpace;
+use self::inkwell::context::Context;
+use self::inkwell::intrinsics::Intrinsic;
+
+#[test]
+fn test_intrinsic_lookup() {
+    let context = Context::create();
+    let i32_type = context.i32_type();
+    let f32_type = context.f32_type();
+    let i32_vec_type = i32_type.vec_type(4);
+    let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+
+    assert!(Intrinsic::find("llvm.not_an_intrinsic").is_none());
+    assert!(Intrinsic::find("ctpop").is_none());
+
+    let ctpop = Intrinsic::find("llvm.ctpop").unwrap();
+    let trap = Intrinsic::find("llvm.trap").unwrap();
+    let lifetime_start = Intrinsic::find("llvm.lifetime.start").unwrap();
+
+    assert_eq!(ctpop.get_name(), "llvm.ctpop");
+    assert!(ctpop.is_overloaded());
+    assert!(!trap.is_overloaded());
+    assert_eq!(trap.count_overload_types(), 0);
+
+    assert_eq!(ctpop.get_mangled_name(&[i32_type.into()]).unwrap(), "llvm.ctpop.i32");
+    assert_eq!(ctpop.get_mangled_name(&[i32_vec_type.into()]).unwrap(), "llvm.ctpop.v4i32");
+    assert_eq!(lifetime_start.get_mangled_name(&[i8_ptr_type.into()]).unwrap(), "llvm.lifetime.start.p0i8");
+    assert_eq!(trap.get_mangled_name(&[]).unwrap(), "llvm.trap");
+
+    // Wrong number or kind of overload types
+    assert!(ctpop.get_mangled_name(&[]).is_none());
+    assert!(ctpop.get_mangled_name(&[f32_type.into()]).is_none());
+    assert!(trap.get_mangled_name(&[i32_type.into()]).is_none());
+    assert!(ctpop.get_fn_type(&context, &[f32_type.into()]).is_none());
+
+    let sadd = Intrinsic::find("llvm.sadd.with.overflow").unwrap();
+    let sadd_fn_type = sadd.get_fn_type(&context, &[i32_type.into()]).unwrap();
+    let overflow_type = context.struct_type(&[i32_type.into(), context.bool_type().into()], false);
+
+    assert_eq!(sadd_fn_type, overflow_type.fn_type(&[i32_type.into(), i32_type.into()], false));
+}
+
+#[test]
+fn test_intrinsic_declarations() {
+    let context = Context::create();
+    let module = context.create_module("intrinsics");
+    let builder = context.create_builder();
+    let i8_ptr_type = context.i8_type().ptr_type(AddressSpace::Generic);
+    let i64_type = context.i64_type();
+    let f64_type = context.f64_type();
+    let fn_type = f64_type.fn_type(&[i8_ptr_type.into(), i8_ptr_type.into(), i64_type.into(), f64_type.into()], false);
+    let fn_value = module.add_function("uses_intrinsics", fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+
+    let memcpy = Intrinsic::find("llvm.memcpy").unwrap();
+    let memcpy_fn = memcpy.get_declaration(&module, &[i8_ptr_type.into(), i8_ptr_type.into(), i64_type.into()]).unwrap();
+    let fma = Intrinsic::find("llvm.fma").unwrap();
+    let fma_fn = fma.get_declaration(&module, &[f64_type.into()]).unwrap();
+    let ctpop = Intrinsic::find("llvm.ctpop").unwrap();
+    let ctpop_fn = ctpop.get_declaration(&module, &[i64_type.into()]).unwrap();
+
+    assert_eq!(memcpy_fn.get_name().to_str(), Ok("llvm.memcpy.p0i8.p0i8.i64"));
+    assert_ne!(memcpy_fn.get_intrinsic_id(), 0);
+    assert_ne!(fma_fn.get_intrinsic_id(), 0);
+    assert_ne!(fma_fn.get_intrinsic_id(), ctpop_fn.get_intrinsic_id());
+
+    // Declaring the same overload again reuses the existing declaration
+    assert_eq!(fma.get_declaration(&module, &[f64_type.into()]).unwrap(), fma_fn);
+    assert!(fma.get_declaration(&module, &[i64_type.into()]).is_none());
+
+    // A function which only shares the intrinsic's name isn't reused
+    let other_module = context.create_module("other");
+
+    other_module.add_function("llvm.fma.f64", f64_type.fn_type(&[f64_type.into()], false), None);
+
+    assert!(fma.get_declaration(&other_module, &[f64_type.into()]).is_none());
+
+    builder.position_at_end(&entry);
+
+    let dest = fn_value.get_nth_param(0).unwrap();
+    let src = fn_value.get_nth_param(1).unwrap();
+    let len = fn_value.get_nth_param(2).unwrap();
+    let float = fn_value.get_nth_param(3).unwrap();
+
+    builder.build_call(memcpy_fn, &[dest, src, len, context.bool_type().const_int(0, false).into()], "");
+
+    let popcount = builder.build_call(ctpop_fn, &[len], "popcount").try_as_basic_value().left().unwrap().into_int_value();
+    let popcount = builder.build_unsigned_int_to_float(popcount, f64_type, "popcount_float");
+    let result = builder.build_call(fma_fn, &[float, float, popcount.into()], "result").try_as_basic_value().left().unwrap();
+
+    builder.build_return(Some(&result));
+
+    assert!(module.verify().is_ok());
+}
@@ -0,0 +1,71 @@
+extern crate inkwell;
+
+use self::inkwell::OptimizationLevel;
+use self::inkwell::context::Context;
+use self::inkwell::lto::LTOCodeGenerator;
+use self::inkwell::memory_buffer::MemoryBuffer;
+use self::inkwell::module::{Linkage, Module};
+
+use std::env::temp_dir;
+use std::fs::remove_file;
+
+#[test]
+fn test_lto_code_generator() {
+    let main_ir = "\
+declare i32 @helper(i32)
+
+define i32 @main() {
+  %x = call i32 @helper(i32 2)
+  ret i32 %x
+}
+";
+    let lib_ir = "\
+define i32 @helper(i32 %x) {
+  %y = mul i32 %x, %x
+  ret i32 %y
+}
+
+define i32 @unused() {
+  ret i32 0
+}
+";
+    let code_generator = LTOCodeGenerator::create();
+
+    for &(name, ir) in &[("main", main_ir), ("lib", lib_ir)] {
+        let context = Context::create();
+        let module = Module::parse_ir_from_str_in_context(ir, name, &context).unwrap();
+
+        assert!(code_generator.add_bitcode(&module.write_bitcode_to_memory(), name).is_ok());
+    }
+
+    let garbage = MemoryBuffer::create_from_memory_range_copy("not bitcode", "garbage");
+
+    assert!(code_generator.add_bitcode(&garbage, "garbage").is_err());
+
+    code_generator.add_must_preserve_symbol("main");
+
+    assert!(code_generator.set_optimization_level(OptimizationLevel::Aggressive));
+
+    // Only preserved symbols keep their external linkage
+    let mut path = temp_dir();
+
+    path.push("merged.bc");
+
+    assert!(code_generator.write_merged_modules(&path).is_ok());
+
+    let context = Context::create();
+    let merged = Module::parse_bitcode_from_path_in_context(&path, &context).unwrap();
+
+    remove_file(&path).unwrap();
+
+    assert_eq!(merged.get_function("main").unwrap().get_linkage(), Linkage::External);
+    assert_eq!(merged.get_function("helper").unwrap().get_linkage(), Linkage::Internal);
+
+    let object = code_generator.compile().unwrap();
+
+    assert!(object.get_size() > 0);
+    assert!(object.create_object_file().is_some());
+
+    // libLTO has now read its options for the rest of the process
+    assert!(!LTOCodeGenerator::create().set_optimization_level(OptimizationLevel::None));
+}
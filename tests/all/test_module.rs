@@ -3,13 +3,13 @@ extern crate inkwell;
 use self::inkwell::OptimizationLevel;
 use self::inkwell::context::Context;
 use self::inkwell::memory_buffer::MemoryBuffer;
-use self::inkwell::module::Module;
+use self::inkwell::module::{Linkage, Module, ThreadSafeModule};
 use self::inkwell::targets::Target;
 
 use std::env::temp_dir;
 use std::ffi::CString;
 use std::fs::{File, remove_file};
-use std::io::Read;
+use std::io::{Read, Write};
 use std::path::Path;
 use std::str::from_utf8;
 
@@ -70,6 +70,49 @@ fn test_write_bitcode_to_path() {
 //     remove_file(&path).unwrap();
 // }
 
+#[test]
+fn test_parse_ir() {
+    let context = Context::create();
+    let ir = "define i32 @answer() {\nentry:\n  ret i32 42\n}\n";
+    let module = Module::parse_ir_from_str_in_context(ir, "answer.ll", &context).unwrap();
+
+    assert_eq!(*module.get_context(), context);
+    assert!(module.get_function("answer").is_some());
+    assert!(module.verify().is_ok());
+
+    let mut path = temp_dir();
+
+    path.push("temp_parse_ir.ll");
+
+    File::create(&path).unwrap().write_all(ir.as_bytes()).unwrap();
+
+    let module = Module::parse_ir_from_path_in_context(&path, &context).unwrap();
+
+    assert!(module.get_function("answer").is_some());
+
+    remove_file(&path).unwrap();
+
+    // Type mismatch on the return
+    let err = Module::parse_ir_from_str_in_context("define i32 @answer() {\nentry:\n  ret i64 42\n}\n", "bad.ll", &context).unwrap_err();
+
+    assert_eq!(err.get_line(), Some(3));
+    assert_eq!(err.get_column(), Some(7));
+    assert!(err.get_message().contains("i32"), "{}", err.get_message());
+    assert!(err.get_diagnostic().to_str().unwrap().starts_with("bad.ll:3:7: error: "));
+    assert!(err.to_string().starts_with("IRParseError(3:7: "));
+
+    let err = Module::parse_ir_from_str_in_context("define i32 @answer() {", "truncated.ll", &context).unwrap_err();
+
+    assert_eq!(err.get_line(), Some(1));
+
+    // Reading the file fails before there is a location
+    let err = Module::parse_ir_from_path_in_context(&path, &context).unwrap_err();
+
+    assert_eq!(err.get_line(), None);
+    assert_eq!(err.get_column(), None);
+    assert!(!err.get_message().is_empty());
+}
+
 #[test]
 fn test_get_function() {
     let context = Context::create();
@@ -92,6 +135,94 @@ fn test_get_function() {
     assert_eq!(named_fn, some_fn);
 }
 
+#[test]
+fn test_module_iterators() {
+    let context = Context::create();
+    let module = context.create_module("my_module");
+
+    assert_eq!(module.get_functions().next(), None);
+    assert_eq!(module.get_globals().next_back(), None);
+
+    let fn_type = context.void_type().fn_type(&[], false);
+    let fn_values: Vec<_> = ["a", "b", "c"].iter().map(|name| module.add_function(name, fn_type, None)).collect();
+    let i32_type = context.i32_type();
+    let globals: Vec<_> = ["x", "y"].iter().map(|name| module.add_global(i32_type, None, name)).collect();
+
+    assert_eq!(module.get_functions().collect::<Vec<_>>(), fn_values);
+    assert_eq!(module.get_functions().rev().collect::<Vec<_>>(), vec![fn_values[2], fn_values[1], fn_values[0]]);
+    assert_eq!(module.get_globals().collect::<Vec<_>>(), globals);
+    assert_eq!(module.get_globals().rev().collect::<Vec<_>>(), vec![globals[1], globals[0]]);
+
+    // Both ends meet in the middle without yielding a value twice
+    let mut functions = module.get_functions();
+
+    assert_eq!(functions.next(), Some(fn_values[0]));
+    assert_eq!(functions.next_back(), Some(fn_values[2]));
+    assert_eq!(functions.next_back(), Some(fn_values[1]));
+    assert_eq!(functions.next(), None);
+    assert_eq!(functions.next_back(), None);
+
+    let names: Vec<_> = module.get_functions().filter(|function| function.get_name().to_str() != Ok("b")).map(|function| function.get_name().to_owned()).collect();
+
+    assert_eq!(names, vec![CString::new("a").unwrap(), CString::new("c").unwrap()]);
+}
+
+#[llvm_versions(7.0 => latest)]
+#[test]
+fn test_global_aliases() {
+    use self::inkwell::{AddressSpace, GlobalVisibility};
+    use self::inkwell::module::Linkage;
+
+    let context = Context::create();
+    let module = context.create_module("my_module");
+
+    assert!(module.get_aliases().next().is_none());
+    assert!(module.get_aliases().next_back().is_none());
+    assert!(module.get_global_alias("fn_alias").is_none());
+
+    let fn_type = context.i32_type().fn_type(&[], false);
+    let fn_value = module.add_function("real_fn", fn_type, None);
+    let other_fn_value = module.add_function("other_fn", fn_type, None);
+    let fn_ptr = fn_value.as_global_value().as_pointer_value();
+    let other_fn_ptr = other_fn_value.as_global_value().as_pointer_value();
+    let fn_alias = module.add_global_alias(fn_ptr, "fn_alias").unwrap();
+    let alias_alias = module.add_global_alias(fn_alias.as_pointer_value(), "alias_alias").unwrap();
+
+    assert_eq!(fn_alias.get_name().to_str(), Ok("fn_alias"));
+    assert_eq!(fn_alias.get_aliasee(), fn_ptr);
+    assert_eq!(alias_alias.get_aliasee(), fn_alias.as_pointer_value());
+    assert_eq!(module.get_global_alias("fn_alias"), Some(fn_alias));
+    assert_eq!(module.get_aliases().collect::<Vec<_>>(), vec![fn_alias, alias_alias]);
+    assert_eq!(module.get_aliases().rev().collect::<Vec<_>>(), vec![alias_alias, fn_alias]);
+
+    // Aliases are not functions or global variables
+    assert_eq!(module.get_functions().count(), 2);
+    assert!(module.get_function("fn_alias").is_none());
+    assert!(module.get_global("fn_alias").is_none());
+
+    assert_eq!(fn_alias.get_linkage(), Linkage::External);
+    assert_eq!(fn_alias.get_visibility(), GlobalVisibility::Default);
+
+    // Only constants may be aliased
+    let ptr_fn_type = context.void_type().fn_type(&[context.i32_type().ptr_type(AddressSpace::Generic).into()], false);
+    let ptr_fn_value = module.add_function("takes_ptr", ptr_fn_type, None);
+    let param = ptr_fn_value.get_first_param().unwrap().into_pointer_value();
+
+    assert!(module.add_global_alias(param, "param_alias").is_none());
+    assert!(module.get_global_alias("param_alias").is_none());
+    assert!(!fn_alias.set_aliasee(param));
+    assert_eq!(fn_alias.get_aliasee(), fn_ptr);
+
+    assert!(fn_alias.set_aliasee(other_fn_ptr));
+    fn_alias.set_linkage(Linkage::Private);
+    fn_alias.set_visibility(GlobalVisibility::Hidden);
+
+    assert_eq!(fn_alias.get_aliasee(), other_fn_ptr);
+    assert_eq!(fn_alias.get_linkage(), Linkage::Private);
+    assert_eq!(fn_alias.get_visibility(), GlobalVisibility::Hidden);
+    assert!(fn_alias.print_to_string().to_str().unwrap().starts_with("@fn_alias = private hidden alias"));
+}
+
 #[test]
 fn test_module_owned_data_layout_disposed_safely() {
     let context = Context::create();
@@ -467,3 +598,196 @@ fn test_metadata_flags() {
         assert!(module.verify().is_ok());
     }
 }
+
+#[test]
+fn test_extract_functions() {
+    let context = Context::create();
+    let ir = "\
+@used = global i32 1
+@unused = global i32 2
+@table = global [1 x i32 ()*] [i32 ()* @indirect]
+
+define i32 @a() {
+  ret i32 0
+}
+
+define i32 @b() {
+  %x = load i32, i32* @used
+  %y = call i32 @c(i32 %x)
+  %z = load i32 ()*, i32 ()** getelementptr ([1 x i32 ()*], [1 x i32 ()*]* @table, i32 0, i32 0)
+  %w = call i32 @\"\\FF\"()
+  ret i32 %y
+}
+
+define i32 @c(i32 %x) {
+  %y = call i32 @a()
+  ret i32 %x
+}
+
+define internal i32 @indirect() {
+  ret i32 3
+}
+
+define i32 @\"\\FF\"() {
+  ret i32 4
+}
+";
+    let module = Module::parse_ir_from_str_in_context(ir, "extract", &context).unwrap();
+    let b = module.get_function("b").unwrap();
+    let extracted = module.extract_functions(&[b]);
+
+    assert!(extracted.verify().is_ok());
+    assert_eq!(*extracted.get_context(), context);
+
+    // Names needn't be valid UTF-8
+    let names: Vec<_> = extracted.get_functions().map(|f| f.get_name().to_bytes().to_vec()).collect();
+
+    assert_eq!(names, vec![b"b".to_vec(), b"c".to_vec(), b"indirect".to_vec(), b"\xFF".to_vec()]);
+    assert!(extracted.get_function("b").unwrap().get_first_basic_block().is_some());
+    assert!(extracted.get_function("c").unwrap().get_first_basic_block().is_none());
+    assert_eq!(extracted.get_function("c").unwrap().count_params(), 1);
+    assert!(extracted.get_function("indirect").unwrap().get_first_basic_block().is_none());
+    assert!(extracted.get_last_function().unwrap().get_first_basic_block().is_none());
+    assert!(extracted.get_global("used").unwrap().get_initializer().is_some());
+    assert!(extracted.get_global("table").unwrap().get_initializer().is_some());
+    assert!(extracted.get_global("unused").is_none());
+
+    // The original module is left as is
+    assert_eq!(module.get_functions().count(), 5);
+    assert_eq!(module.get_globals().count(), 3);
+    assert!(module.get_function("c").unwrap().get_first_basic_block().is_some());
+}
+
+#[test]
+fn test_split_into() {
+    let context = Context::create();
+    let ir = "\
+@llvm.global_ctors = appending global [1 x { i32, void ()*, i8* }] [{ i32, void ()*, i8* } { i32 65535, void ()* @init, i8* null }]
+@counter = internal global i32 0
+@counter_alias = alias i32, i32* @counter
+@\"\\FE\" = global i32 2
+@\"\\FF\" = alias i32, i32* @\"\\FE\"
+
+define internal void @init() {
+  store i32 1, i32* @counter
+  ret void
+}
+
+define i32 @get() {
+  %x = load i32, i32* @counter_alias
+  %y = load i32, i32* @\"\\FE\"
+  %z = load i32, i32* @\"\\FF\"
+  ret i32 %x
+}
+
+define i32 @twice() {
+  %x = call i32 @get()
+  %y = add i32 %x, %x
+  ret i32 %y
+}
+
+define i32 @unrelated(i32 %x) {
+  %y = mul i32 %x, %x
+  %z = add i32 %y, 1
+  ret i32 %z
+}
+";
+    let module = Module::parse_ir_from_str_in_context(ir, "split", &context).unwrap();
+    let partitions = module.split_into(3);
+
+    assert_eq!(partitions.len(), 3);
+
+    let mut defined = Vec::new();
+
+    for partition in &partitions {
+        assert!(partition.verify().is_ok());
+        assert_ne!(*partition.get_context(), context);
+
+        for function in partition.get_functions() {
+            if function.get_first_basic_block().is_some() {
+                defined.push(function.get_name().to_str().unwrap().to_owned());
+            }
+        }
+    }
+
+    defined.sort();
+
+    assert_eq!(defined, vec!["get", "init", "twice", "unrelated"]);
+
+    // Aliases, their aliasees and appending globals stay in the first partition
+    let first = &partitions[0];
+
+    assert!(first.get_global("llvm.global_ctors").is_some());
+    assert!(first.get_global("counter").unwrap().get_initializer().is_some());
+    assert_eq!(first.get_global_alias("counter_alias").unwrap().get_aliasee(), first.get_global("counter").unwrap().as_pointer_value());
+
+    for partition in &partitions[1..] {
+        assert!(partition.get_global("llvm.global_ctors").is_none());
+        assert!(partition.get_global("counter").map_or(true, |global| global.is_declaration()));
+        assert!(partition.get_global_alias("counter_alias").is_none());
+    }
+
+    // Linking the partitions back together gives a module equivalent to the original
+    let linked = context.create_module("linked");
+
+    for partition in &partitions {
+        let buffer = partition.write_bitcode_to_memory();
+        let partition = Module::parse_bitcode_from_buffer_in_context(&buffer, &context).unwrap();
+
+        assert!(linked.link_in_module(partition).is_ok());
+    }
+
+    assert!(linked.verify().is_ok());
+    assert!(linked.get_function("twice").unwrap().get_first_basic_block().is_some());
+    assert!(linked.get_global_alias("counter_alias").is_some());
+
+    // The original module is left as is
+    assert_eq!(module.get_function("init").unwrap().get_linkage(), Linkage::Internal);
+}
+
+#[test]
+fn test_thread_safe_module() {
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let builder = context.create_builder();
+    let fn_type = context.i32_type().fn_type(&[], false);
+
+    for name in &["a", "b", "c"] {
+        let function = module.add_function(name, fn_type, None);
+
+        builder.position_at_end(&function.append_basic_block("entry"));
+        builder.build_return(Some(&context.i32_type().const_int(1, false)));
+    }
+
+    // Both the context and the module's clone still reference it
+    let clone = unsafe { ThreadSafeModule::new(module.clone()).unwrap_err() };
+    let global_module = unsafe { ThreadSafeModule::new(Module::create("global")).unwrap_err() };
+
+    drop(clone);
+    drop(global_module);
+
+    let handles: Vec<_> = module.split_into(2).into_iter().map(|partition| {
+        let partition = unsafe { ThreadSafeModule::new(partition).unwrap() };
+
+        ::std::thread::spawn(move || {
+            let partition = partition.into_inner();
+
+            assert!(partition.verify().is_ok());
+
+            partition.print_to_string().to_string()
+        })
+    }).collect();
+
+    for handle in handles {
+        let ir = handle.join().unwrap();
+
+        assert!(Module::parse_ir_from_str_in_context(&ir, "partition", &context).is_ok());
+    }
+
+    let owned_by_ee = context.create_module("owned_by_ee");
+
+    drop(context);
+
+    assert!(owned_by_ee.create_interpreter_execution_engine().is_ok());
+    assert!(unsafe { ThreadSafeModule::new(owned_by_ee) }.is_err());
+}
@@ -0,0 +1,92 @@
+extern crate inkwell;
+
+use self::inkwell::OptimizationLevel;
+use self::inkwell::context::Context;
+use self::inkwell::module::Linkage;
+use self::inkwell::object_file::ObjectFile;
+use self::inkwell::targets::{CodeModel, FileType, InitializationConfig, RelocMode, Target};
+
+use std::env::temp_dir;
+use std::fs::remove_file;
+
+#[test]
+fn test_object_file() {
+    Target::initialize_x86(&InitializationConfig::default());
+
+    let target = Target::from_name("x86-64").unwrap();
+    let target_machine = target.create_target_machine("x86_64-pc-linux-gnu", "x86-64", "", OptimizationLevel::None, RelocMode::PIC, CodeModel::Default).unwrap();
+
+    let context = Context::create();
+    let module = context.create_module("my_module");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let fn_type = i32_type.fn_type(&[], false);
+    let external_fn = module.add_function("external_fn", fn_type, None);
+    let function = module.add_function("my_fn", fn_type, None);
+    let weak_fn = module.add_function("my_weak_fn", fn_type, Some(Linkage::WeakAny));
+    let data = module.add_global(i32_type, None, "my_data");
+    let bss = module.add_global(i32_type, None, "my_bss");
+
+    data.set_initializer(&i32_type.const_int(0x0012_3400, false));
+    bss.set_initializer(&i32_type.const_zero());
+
+    builder.position_at_end(&context.append_basic_block(&function, "entry"));
+
+    let call = builder.build_call(external_fn, &[], "call").try_as_basic_value().left().unwrap();
+
+    builder.build_return(Some(&call));
+    builder.position_at_end(&context.append_basic_block(&weak_fn, "entry"));
+    builder.build_return(Some(&i32_type.const_int(0, false)));
+
+    let buffer = target_machine.write_to_memory_buffer(&module, FileType::Object).unwrap();
+    let object_file = buffer.create_object_file().unwrap();
+
+    // Sections stay valid after the iterator they came from moves on
+    let sections: Vec<_> = object_file.get_sections().collect();
+    let data_section = sections.iter().find(|section| section.get_name().to_bytes() == b".data").unwrap();
+    let bss_section = sections.iter().find(|section| section.get_name().to_bytes() == b".bss").unwrap();
+
+    // Contents are no longer truncated at the first null byte
+    assert_eq!(data_section.get_contents(), &[0x00, 0x34, 0x12, 0x00]);
+    assert_eq!(bss_section.size(), 4);
+
+    let mut found = 0;
+
+    for symbol in object_file.get_symbols() {
+        let section = symbol.get_section().map(|section| section.get_name().to_bytes().to_vec());
+        let expected = match symbol.get_name().to_bytes() {
+            b"my_fn" | b"my_weak_fn" => Some(b".text".to_vec()),
+            b"my_data" => Some(b".data".to_vec()),
+            b"my_bss" => Some(b".bss".to_vec()),
+            b"external_fn" => None,
+            _ => continue,
+        };
+
+        assert_eq!(section, expected);
+
+        found += 1;
+    }
+
+    assert_eq!(found, 5);
+
+    let relocation_section = sections.iter().find(|section| section.get_name().to_bytes() == b".rela.text").unwrap();
+    let relocation = relocation_section.get_relocations().next().unwrap();
+    let symbol = relocation.get_symbol().unwrap();
+
+    assert_eq!(symbol.get_name().to_bytes(), b"external_fn");
+    assert!(symbol.get_section().is_none());
+
+    let mut path = temp_dir();
+
+    path.push("test_object_file.o");
+
+    target_machine.write_to_file(&module, FileType::Object, &path).unwrap();
+
+    let object_file = ObjectFile::create_from_file(&path).unwrap();
+
+    assert!(object_file.get_symbols().any(|symbol| symbol.get_name().to_bytes() == b"my_fn"));
+
+    remove_file(&path).unwrap();
+
+    assert!(ObjectFile::create_from_file(&path).is_err());
+}
@@ -0,0 +1,143 @@
+extern crate inkwell;
+
+use self::inkwell::OptimizationLevel;
+use self::inkwell::context::Context;
+use self::inkwell::execution_engine::FunctionLookupError;
+use self::inkwell::module::Module;
+use self::inkwell::orc::OrcEngine;
+use self::inkwell::targets::{CodeModel, InitializationConfig, RelocMode, Target, TargetMachine};
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+type I64Thunk = unsafe extern "C" fn() -> i64;
+
+fn create_orc_engine() -> OrcEngine {
+    Target::initialize_native(&InitializationConfig::default()).expect("Failed to initialize native target");
+
+    let triple = TargetMachine::get_default_triple();
+    let triple = triple.to_str().unwrap();
+    let target = Target::from_triple(triple).unwrap();
+    let target_machine = target.create_target_machine(triple, "", "", OptimizationLevel::None, RelocMode::Default, CodeModel::JITDefault).unwrap();
+
+    OrcEngine::create(target_machine)
+}
+
+// Builds `fn $name() -> i64 { $callee() + $addend }`, or just returns `$addend` without a callee
+fn build_module(context: &Context, name: &str, callee: Option<&str>, addend: u64) -> Module {
+    let module = context.create_module(name);
+    let builder = context.create_builder();
+    let i64_type = context.i64_type();
+    let fn_type = i64_type.fn_type(&[], false);
+    let fn_value = module.add_function(name, fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+
+    builder.position_at_end(&entry);
+
+    let addend = i64_type.const_int(addend, false);
+    let ret = match callee {
+        Some(callee) => {
+            let callee = module.add_function(callee, fn_type, None);
+            let result = builder.build_call(callee, &[], "result").try_as_basic_value().left().unwrap().into_int_value();
+
+            builder.build_int_add(result, addend, "ret")
+        },
+        None => addend,
+    };
+
+    builder.build_return(Some(&ret));
+
+    module
+}
+
+#[test]
+fn test_orc_incremental_modules() {
+    let orc_engine = create_orc_engine();
+    let context = Context::create();
+
+    let base = orc_engine.add_module(build_module(&context, "base", None, 40)).unwrap();
+    let derived = orc_engine.add_module(build_module(&context, "derived", Some("base"), 2)).unwrap();
+
+    assert_ne!(base, derived);
+
+    unsafe {
+        assert_eq!(orc_engine.get_function::<I64Thunk>("base").unwrap().call(), 40);
+        assert_eq!(orc_engine.get_function::<I64Thunk>("derived").unwrap().call(), 42);
+        assert_eq!(orc_engine.get_function::<I64Thunk>("missing").unwrap_err(), FunctionLookupError::FunctionNotFound);
+    }
+
+    assert!(orc_engine.get_symbol_address_in(base, "base").unwrap().is_some());
+    assert!(orc_engine.get_symbol_address_in(base, "derived").unwrap().is_none());
+
+    orc_engine.remove_module(derived).unwrap();
+
+    assert!(orc_engine.get_symbol_address("derived").unwrap().is_none());
+
+    // A module may be re-added once its previous definition has been removed
+    orc_engine.add_module(build_module(&context, "derived", Some("base"), 3)).unwrap();
+
+    unsafe {
+        assert_eq!(orc_engine.get_function::<I64Thunk>("derived").unwrap().call(), 43);
+    }
+}
+
+#[test]
+fn test_orc_lazy_module() {
+    let orc_engine = create_orc_engine();
+    let context = Context::create();
+    let mangled_host_value = orc_engine.get_mangled_symbol("host_value");
+    let resolved = Rc::new(Cell::new(0));
+    let resolved_clone = resolved.clone();
+
+    orc_engine.set_symbol_resolver(move |name| {
+        if name != mangled_host_value {
+            return None;
+        }
+
+        resolved_clone.set(resolved_clone.get() + 1);
+
+        Some(host_value as usize as u64)
+    });
+    orc_engine.add_lazy_module(build_module(&context, "lazy", Some("host_value"), 7)).unwrap();
+
+    let lazy = unsafe { orc_engine.get_function::<I64Thunk>("lazy").unwrap() };
+
+    // Only a stub exists until the function is first called, so its callee hasn't been resolved
+    assert_eq!(resolved.get(), 0);
+
+    unsafe {
+        assert_eq!(lazy.call(), 107);
+    }
+
+    assert!(resolved.get() > 0);
+}
+
+extern "C" fn host_value() -> i64 {
+    100
+}
+
+#[test]
+fn test_orc_symbol_resolver() {
+    let orc_engine = create_orc_engine();
+    let context = Context::create();
+    let mangled_host_value = orc_engine.get_mangled_symbol("host_value");
+    let resolved = Rc::new(Cell::new(0));
+    let resolved_clone = resolved.clone();
+
+    orc_engine.set_symbol_resolver(move |name| {
+        if name != mangled_host_value {
+            return None;
+        }
+
+        resolved_clone.set(resolved_clone.get() + 1);
+
+        Some(host_value as usize as u64)
+    });
+    orc_engine.add_module(build_module(&context, "guest", Some("host_value"), 1)).unwrap();
+
+    unsafe {
+        assert_eq!(orc_engine.get_function::<I64Thunk>("guest").unwrap().call(), 101);
+    }
+
+    assert!(resolved.get() > 0);
+}
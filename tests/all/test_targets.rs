@@ -2,7 +2,7 @@ extern crate inkwell;
 
 use self::inkwell::{AddressSpace, OptimizationLevel};
 use self::inkwell::context::Context;
-use self::inkwell::targets::{ByteOrdering, CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetData, TargetMachine};
+use self::inkwell::targets::{ByteOrdering, CodeModel, FileType, InitializationConfig, RelocMode, Target, TargetData, TargetMachine, TargetMachineOptions};
 
 use std::env::temp_dir;
 use std::ffi::CString;
@@ -358,3 +358,35 @@ fn test_write_target_machine_to_memory_buffer() {
     assert!(string.contains("my_module"));
     assert!(string.contains(".section"));
 }
+
+#[test]
+fn test_target_machine_options() {
+    Target::initialize_x86(&InitializationConfig::default());
+
+    let target = Target::from_name("x86-64").unwrap();
+    let options = TargetMachineOptions::new();
+
+    assert_eq!(options.get_cpu(), "");
+    assert_eq!(options.get_features(), "");
+    assert_eq!(options.get_level(), OptimizationLevel::Default);
+    assert_eq!(options.get_reloc_mode(), RelocMode::Default);
+    assert_eq!(options.get_code_model(), CodeModel::Default);
+
+    let target_machine = target.create_target_machine("x86_64-pc-linux-gnu", "x86-64", "+avx2", OptimizationLevel::Less, RelocMode::Static, CodeModel::Default).unwrap();
+    let options = target_machine.get_options();
+
+    assert_eq!(options.get_cpu(), "x86-64");
+    assert_eq!(options.get_features(), "+avx2");
+    assert_eq!(options.get_level(), OptimizationLevel::Less);
+    assert_eq!(options.get_reloc_mode(), RelocMode::Static);
+    assert_eq!(options.get_code_model(), CodeModel::Default);
+
+    let options = TargetMachineOptions::new()
+        .set_cpu("x86-64")
+        .set_level(OptimizationLevel::None)
+        .set_reloc_mode(RelocMode::PIC);
+    let target_machine = target.create_target_machine_from_options("x86_64-pc-linux-gnu", options.clone()).unwrap();
+
+    assert_eq!(*target_machine.get_options(), options);
+    assert_eq!(*target_machine.get_cpu(), *CString::new("x86-64").unwrap());
+}
@@ -2,9 +2,10 @@ extern crate inkwell;
 
 use std::ffi::CString;
 
-use self::inkwell::AddressSpace;
+use self::inkwell::{AddressSpace, OptimizationLevel};
 use self::inkwell::context::Context;
-use self::inkwell::types::{FloatType, IntType, StructType, VoidType};
+use self::inkwell::targets::{InitializationConfig, Target};
+use self::inkwell::types::{BasicType, FieldNameError, FloatType, IntType, RustStruct, RustType, StructType, VoidType};
 
 #[test]
 fn test_struct_type() {
@@ -413,3 +414,92 @@ fn test_ptr_type() {
     assert_eq!(fn_ptr_type.get_element_type().into_function_type(), fn_type);
     assert_eq!(*fn_ptr_type.get_context(), context);
 }
+
+#[derive(RustStruct)]
+#[repr(C)]
+struct Header {
+    tag: u8,
+    len: u32,
+    data: *const u8,
+}
+
+#[derive(RustStruct)]
+#[repr(C)]
+struct Entry {
+    header: Header,
+    values: [f64; 3],
+    is_valid: bool,
+}
+
+#[derive(RustStruct)]
+#[repr(C, packed)]
+struct Packed(u8, u64);
+
+#[test]
+fn test_rust_struct() {
+    Target::initialize_native(&InitializationConfig::default()).unwrap();
+
+    let context = Context::create();
+    let i8_type = context.i8_type();
+    let header_type = Header::struct_type(&context);
+    let entry_type = Entry::struct_type(&context);
+    let packed_type = Packed::struct_type(&context);
+
+    assert_eq!(header_type, context.struct_type(&[i8_type.into(), context.i32_type().into(), i8_type.ptr_type(AddressSpace::Generic).into()], false));
+    assert_eq!(entry_type, context.struct_type(&[header_type.into(), context.f64_type().array_type(3).into(), i8_type.into()], false));
+    assert_eq!(packed_type, context.struct_type(&[i8_type.into(), context.i64_type().into()], true));
+    assert_eq!(Entry::basic_type(&context), entry_type.as_basic_type_enum());
+
+    assert_eq!(Entry::FIELD_NAMES, &["header", "values", "is_valid"]);
+    assert_eq!(Entry::field_index("values"), Some(1));
+    assert_eq!(Entry::field_index("missing"), None);
+    assert_eq!(Packed::field_index("1"), Some(1));
+    assert_eq!(Packed::field_offsets(), vec![0, 1]);
+
+    let module = context.create_module("my_module");
+    let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
+    let target_data = execution_engine.get_target_data();
+
+    Header::assert_layout_matches(&context, target_data);
+    Entry::assert_layout_matches(&context, target_data);
+    Packed::assert_layout_matches(&context, target_data);
+}
+
+#[test]
+fn test_struct_field_names() {
+    let context = Context::create();
+    let i32_type = context.i32_type();
+    let literal_struct = context.struct_type(&[i32_type.into(), i32_type.into()], false);
+    let named_struct = context.opaque_struct_type("pair");
+
+    // Opaque and literal structs can't have field names
+    assert!(!named_struct.set_field_names(&[]));
+    assert!(!literal_struct.set_field_names(&["first", "second"]));
+    assert_eq!(literal_struct.get_field_names(), None);
+
+    named_struct.set_body(&[i32_type.into(), i32_type.into()], false);
+
+    assert!(!named_struct.set_field_names(&["first"]));
+    assert!(!named_struct.set_field_names(&["first", "first"]));
+    assert_eq!(named_struct.get_field_index("first"), Err(FieldNameError::NoFieldNames { struct_name: "pair".into() }));
+    assert!(named_struct.set_field_names(&["first", "second"]));
+    assert_eq!(named_struct.get_field_names(), Some(vec!["first".into(), "second".into()]));
+    assert_eq!(named_struct.get_field_index("second"), Ok(1));
+
+    let error = named_struct.get_field_index("third").unwrap_err();
+
+    assert_eq!(error, FieldNameError::UnknownField {
+        struct_name: "pair".into(),
+        field_name: "third".into(),
+        field_names: vec!["first".into(), "second".into()],
+    });
+    assert_eq!(error.to_string(), r#"FieldNameError(The struct has no field with this name: pair has no field "third", only ["first", "second"])"#);
+
+    // Field names belong to a single context
+    let other_context = Context::create();
+    let other_struct = other_context.opaque_struct_type("pair");
+
+    other_struct.set_body(&[i32_type.into(), i32_type.into()], false);
+
+    assert_eq!(other_struct.get_field_names(), None);
+}
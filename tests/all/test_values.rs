@@ -4,7 +4,7 @@ use self::inkwell::{DLLStorageClass, FloatPredicate, GlobalVisibility, ThreadLoc
 use self::inkwell::context::Context;
 use self::inkwell::module::Linkage::*;
 use self::inkwell::types::{StructType, VectorType};
-use self::inkwell::values::{InstructionOpcode::*, MetadataValue, FIRST_CUSTOM_METADATA_KIND_ID, VectorValue};
+use self::inkwell::values::{ConstantValue, GEPError, InstructionOpcode::*, MetadataValue, FIRST_CUSTOM_METADATA_KIND_ID, VectorValue};
 #[llvm_versions(7.0 => latest)]
 use self::inkwell::comdat::ComdatSelectionKind;
 
@@ -686,8 +686,7 @@ fn test_function_value_no_params() {
     let fn_type = void_type.fn_type(&[], false);
     let fn_value = module.add_function("no_params", fn_type, None);
 
-    // REVIEW: According to this, fn_value.get_type() is a void ptr??
-    // assert_eq!(fn_value.get_type(), fn_type);
+    assert_eq!(fn_value.get_type(), fn_type);
     assert_eq!(fn_value.count_params(), 0);
     assert_eq!(fn_value.get_param_iter().collect::<Vec<_>>().len(), 0);
     assert_eq!(fn_value.get_params().len(), 0);
@@ -1142,6 +1141,163 @@ fn test_consts() {
     assert!(f32_param.get_constant().is_none());
 }
 
+#[test]
+fn test_const_value() {
+    let context = Context::create();
+    let i8_type = context.i8_type();
+    let i32_type = context.i32_type();
+    let f64_type = context.f64_type();
+
+    let negative = context.const_value(&-2i16).into_int_value();
+    let wide = context.const_value(&(u128::max_value() - 1)).into_int_value();
+    let flag = context.const_value(&true).into_int_value();
+    let float = context.const_value(&1.5f64).into_float_value();
+
+    assert_eq!(negative.get_type(), context.i16_type());
+    assert_eq!(negative.get_sign_extended_constant(), Some(-2));
+    assert_eq!(wide.get_type(), context.i128_type());
+    assert_eq!(*wide.print_to_string(), *CString::new("i128 -2").unwrap());
+    assert_eq!(flag.get_type(), i8_type);
+    assert_eq!(flag.get_zero_extended_constant(), Some(1));
+    assert_eq!(float.get_constant(), Some((1.5, false)));
+
+    let table = context.const_value(&[1i32, 2, 3]).into_array_value();
+    let slice = context.const_value(&[1.0f64, 2.0][..]).into_array_value();
+    let empty = context.const_value(&Vec::<u8>::new()).into_array_value();
+
+    assert!(table.is_const());
+    assert_eq!(table.get_type(), i32_type.array_type(3));
+    assert_eq!(*table.print_to_string(), *CString::new("[3 x i32] [i32 1, i32 2, i32 3]").unwrap());
+    assert_eq!(slice.get_type(), f64_type.array_type(2));
+    assert_eq!(empty.get_type(), i8_type.array_type(0));
+
+    let tuple = context.const_value(&(7u8, [1.5f64, 2.5], 'a')).into_struct_value();
+
+    assert_eq!(tuple.get_type(), context.struct_type(&[i8_type.into(), f64_type.array_type(2).into(), i32_type.into()], false));
+
+    let string = context.const_value("hello").into_array_value();
+    let c_string = CString::new("hello").unwrap();
+    let c_string = context.const_value(c_string.as_c_str()).into_array_value();
+
+    assert_eq!(string.get_type(), i8_type.array_type(5));
+    assert_eq!(c_string.get_type(), i8_type.array_type(6));
+    assert_eq!(*c_string.print_to_string(), *CString::new("[6 x i8] c\"hello\\00\"").unwrap());
+}
+
+#[test]
+fn test_arbitrary_precision_ints() {
+    let context = Context::create();
+    let i8_type = context.i8_type();
+    let i64_type = context.i64_type();
+    let i128_type = context.i128_type();
+    let i200_type = context.custom_width_int_type(200);
+
+    // 200 bits round up to four words
+    let words = [0x0123_4567_89ab_cdef, 0xfedc_ba98_7654_3210, 0xff];
+    let i200_val = i200_type.const_int_arbitrary_precision(&words);
+
+    assert_eq!(i200_val.get_constant_words(), Some(vec![words[0], words[1], words[2], 0]));
+    assert_eq!(i200_val.get_u128_constant(), None);
+    assert_eq!(i200_val.get_i128_constant(), None);
+    assert_eq!(i64_type.const_int(42, false).get_constant_words(), Some(vec![42]));
+
+    let max = i128_type.const_u128(u128::max_value());
+    let min = i128_type.const_i128(i128::min_value());
+    let negative = i200_type.const_i128(-2);
+
+    assert_eq!(max.get_u128_constant(), Some(u128::max_value()));
+    assert_eq!(max.get_i128_constant(), Some(-1));
+    assert_eq!(min.get_i128_constant(), Some(i128::min_value()));
+    assert_eq!(min.get_constant_words(), Some(vec![0, 1 << 63]));
+    assert_eq!(negative.get_constant_words(), Some(vec![u64::max_value() - 1, u64::max_value(), u64::max_value(), 0xff]));
+    assert_eq!(i200_type.const_u128(u128::max_value()).get_constant_words(), Some(vec![u64::max_value(), u64::max_value(), 0, 0]));
+
+    // Narrower types are truncated to and extended from their width
+    let i8_val = i8_type.const_i128(-3);
+
+    assert_eq!(i8_val.get_u128_constant(), Some(253));
+    assert_eq!(i8_val.get_i128_constant(), Some(-3));
+    assert_eq!(i8_type.const_u128(0x1ff).get_u128_constant(), Some(0xff));
+
+    // Non const values have no words
+    let module = context.create_module("ints");
+    let fn_type = context.void_type().fn_type(&[i128_type.into()], false);
+    let function = module.add_function("fn", fn_type, None);
+    let param = function.get_first_param().unwrap().into_int_value();
+    let global = module.add_global(i8_type, None, "global");
+    let ptr_to_int = global.as_pointer_value().const_to_int(i128_type);
+
+    assert_eq!(param.get_constant_words(), None);
+    assert_eq!(param.get_u128_constant(), None);
+    assert!(ptr_to_int.is_const());
+    assert_eq!(ptr_to_int.get_constant_words(), None);
+}
+
+#[test]
+fn test_constant_values() {
+    let context = Context::create();
+    let module = context.create_module("constants");
+    let i8_type = context.i8_type();
+    let i32_type = context.i32_type();
+    let f32_type = context.f32_type();
+    let i8_ptr_type = i8_type.ptr_type(AddressSpace::Generic);
+    let struct_type = context.struct_type(&[i32_type.into(), i8_ptr_type.into()], false);
+
+    let table = context.const_value(&[1i32, 2, 3]).into_array_value();
+    let zeros = i32_type.array_type(2).const_zero();
+    let undef = i32_type.array_type(2).get_undef();
+
+    assert_eq!(table.get_element_as_constant(2), Some(i32_type.const_int(3, false).into()));
+    assert_eq!(table.get_element_as_constant(3), None);
+    assert_eq!(zeros.get_element_as_constant(1), Some(i32_type.const_zero().into()));
+    assert!(undef.get_element_as_constant(0).unwrap().into_int_value().is_undef());
+
+    let global = module.add_global(i8_type, None, "global");
+    let global_ptr = global.as_pointer_value();
+    let fields = context.const_struct(&[i32_type.const_int(7, false).into(), global_ptr.into()], false);
+    let null_fields = struct_type.const_zero();
+
+    assert_eq!(fields.get_element_as_constant(0), Some(i32_type.const_int(7, false).into()));
+    assert_eq!(fields.get_element_as_constant(1), Some(global_ptr.into()));
+    assert_eq!(fields.get_element_as_constant(2), None);
+    assert_eq!(null_fields.get_element_as_constant(1), Some(i8_ptr_type.const_null().into()));
+
+    let string = context.const_value("hi").into_array_value();
+    let c_string = CString::new("hi").unwrap();
+    let c_string = context.const_value(c_string.as_c_str()).into_array_value();
+
+    assert!(string.is_const_string());
+    assert_eq!(string.get_string_constant(), Some(&b"hi"[..]));
+    assert_eq!(c_string.get_string_constant(), Some(&b"hi\0"[..]));
+    assert!(!table.is_const_string());
+    assert!(!i8_type.array_type(2).const_zero().is_const_string());
+    assert_eq!(table.get_string_constant(), None);
+
+    let vector = VectorType::const_vector(&[f32_type.const_float(1.5), f32_type.get_undef()]);
+    let wide_int = context.i128_type().const_int(1, false);
+    let initializer = context.const_struct(&[table.into(), null_fields.into(), fields.into(), vector.into(), wide_int.into()], false);
+    let constants = module.add_global(initializer.get_type(), None, "constants");
+
+    constants.set_initializer(&initializer);
+
+    let int = |bit_width, value| ConstantValue::Int { bit_width, value };
+
+    assert_eq!(constants.get_initializer().unwrap().get_constant_value(), Some(ConstantValue::Struct(vec![
+        ConstantValue::Array(vec![int(32, 1), int(32, 2), int(32, 3)]),
+        ConstantValue::Struct(vec![int(32, 0), ConstantValue::NullPointer]),
+        ConstantValue::Struct(vec![int(32, 7), ConstantValue::Other(global_ptr.into())]),
+        ConstantValue::Vector(vec![ConstantValue::Float(1.5), ConstantValue::Undef]),
+        ConstantValue::Other(wide_int.into()),
+    ])));
+
+    // Non const values aren't constants
+    let fn_type = context.void_type().fn_type(&[i32_type.into()], false);
+    let function = module.add_function("fn", fn_type, None);
+    let param = function.get_first_param().unwrap();
+
+    assert_eq!(param.get_constant_value(), None);
+}
+
 #[test]
 fn test_function_value_to_global_to_pointer() {
     let context = Context::create();
@@ -1176,3 +1332,39 @@ fn test_function_value_to_global_to_pointer() {
     assert_eq!(*fn_ptr_value.get_name(), *CString::new("my_func").unwrap());
     assert!(module.verify().is_ok());
 }
+
+#[test]
+fn test_const_gep() {
+    let context = Context::create();
+    let module = context.create_module("const_gep");
+    let builder = context.create_builder();
+    let i32_type = context.i32_type();
+    let i8_type = context.i8_type();
+    let vec_type = i8_type.vec_type(4);
+    let struct_type = context.struct_type(&[i32_type.into(), vec_type.into()], false);
+    let global = module.add_global(struct_type, Some(AddressSpace::Generic), "my_global");
+    let global_ptr = global.as_pointer_value();
+    let zero = i32_type.const_zero();
+    let one = i32_type.const_int(1, false);
+
+    let vec_elem_ptr = global_ptr.const_gep(&[zero, one, i32_type.const_int(3, false)]).unwrap();
+    let in_bounds_ptr = global_ptr.const_in_bounds_gep(&[zero, zero]).unwrap();
+
+    assert!(vec_elem_ptr.is_const());
+    assert_eq!(vec_elem_ptr.get_type(), i8_type.ptr_type(AddressSpace::Generic));
+    assert_eq!(in_bounds_ptr.get_type(), i32_type.ptr_type(AddressSpace::Generic));
+    assert_eq!(global_ptr.const_gep(&[zero, one, i32_type.const_int(5, false)]), Err(GEPError::VectorIndexOutOfBounds { position: 2, index: 5, len: 4 }));
+    assert_eq!(global_ptr.const_in_bounds_gep(&[zero, i32_type.const_int(2, false)]), Err(GEPError::StructIndexOutOfBounds { position: 1, index: 2, field_count: 2 }));
+
+    let fn_type = context.void_type().fn_type(&[i32_type.into()], false);
+    let fn_value = module.add_function("my_fn", fn_type, None);
+    let entry = fn_value.append_basic_block("entry");
+    let param = fn_value.get_first_param().unwrap().into_int_value();
+
+    builder.position_at_end(&entry);
+
+    let alloca = builder.build_alloca(struct_type, "alloca");
+
+    assert_eq!(global_ptr.const_gep(&[zero, param]), Err(GEPError::NonConstantIndex { position: 1 }));
+    assert_eq!(alloca.const_gep(&[zero]), Err(GEPError::NonConstantPointer));
+}